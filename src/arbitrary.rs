@@ -0,0 +1,123 @@
+use crate::generate::Roundtrip;
+use crate::generate::MAX_DEPTH;
+use crate::generate::MAX_LEN;
+use crate::generate::NAMES;
+use crate::Data;
+use crate::Enum;
+use crate::Number;
+use crate::Struct;
+use crate::Value;
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use arbitrary::Arbitrary;
+use arbitrary::Result;
+use arbitrary::Unstructured;
+
+impl<'a> Arbitrary<'a> for Number {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(match u.int_in_range(0..=11)? {
+            0 => Number::I8(u.arbitrary()?),
+            1 => Number::U8(u.arbitrary()?),
+            2 => Number::I16(u.arbitrary()?),
+            3 => Number::U16(u.arbitrary()?),
+            4 => Number::I32(u.arbitrary()?),
+            5 => Number::U32(u.arbitrary()?),
+            6 => Number::F32(u.arbitrary()?),
+            7 => Number::I64(u.arbitrary()?),
+            8 => Number::U64(u.arbitrary()?),
+            9 => Number::F64(u.arbitrary()?),
+            10 => Number::I128(u.arbitrary()?),
+            _ => Number::U128(u.arbitrary()?),
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Value<'a> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        value(u, 1, false)
+    }
+}
+
+impl<'a> Arbitrary<'a> for Roundtrip<'a> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        value(u, 1, true).map(Roundtrip)
+    }
+}
+
+// Generates a value at `depth`, only nesting further while there is data left.
+fn value<'a>(u: &mut Unstructured<'a>, depth: usize, roundtrip: bool) -> Result<Value<'a>> {
+    let kinds = match (depth < MAX_DEPTH && !u.is_empty(), roundtrip) {
+        (false, _) => 6,
+        (true, true) => 9,
+        (true, false) => 12,
+    };
+    let depth = depth + 1;
+    Ok(match u.int_in_range(0..=kinds - 1)? {
+        0 => Value::Unit,
+        1 => Value::Bool(u.arbitrary()?),
+        2 => Value::Number(number(u, roundtrip)?),
+        3 => Value::Char(u.arbitrary()?),
+        4 => Value::String(Cow::Borrowed(u.arbitrary()?)),
+        5 => Value::Bytes(Cow::Borrowed(u.arbitrary()?)),
+        6 => Value::Seq(values(u, depth, roundtrip)?),
+        7 => Value::Map(collect(u, |u| {
+            Ok((value(u, depth, roundtrip)?, value(u, depth, roundtrip)?))
+        })?),
+        8 => match u.arbitrary()? {
+            true => Value::Option(Some(Box::new(value(u, depth, roundtrip)?))),
+            false => Value::Option(None),
+        },
+        9 => Value::Tuple(values(u, depth, false)?),
+        10 => Value::Struct(Box::new(Struct {
+            name: name(u)?,
+            data: data(u, depth)?,
+        })),
+        _ => Value::Enum(Box::new(Enum {
+            name: name(u)?,
+            variant_index: u.arbitrary()?,
+            variant: name(u)?,
+            data: data(u, depth)?,
+        })),
+    })
+}
+
+fn number(u: &mut Unstructured<'_>, roundtrip: bool) -> Result<Number> {
+    match Number::arbitrary(u)? {
+        // NaN isn't equal to itself, so it never round-trips.
+        Number::F32(v) if roundtrip && v.is_nan() => Ok(Number::F32(0.0)),
+        Number::F64(v) if roundtrip && v.is_nan() => Ok(Number::F64(0.0)),
+        number => Ok(number),
+    }
+}
+
+fn data<'a>(u: &mut Unstructured<'a>, depth: usize) -> Result<Data<'a>> {
+    Ok(match u.int_in_range(0..=3)? {
+        0 => Data::Unit,
+        1 => Data::NewType {
+            value: value(u, depth, false)?,
+        },
+        2 => Data::Tuple {
+            values: values(u, depth, false)?,
+        },
+        _ => Data::Struct {
+            fields: collect(u, |u| Ok((name(u)?, value(u, depth, false)?)))?,
+        },
+    })
+}
+
+fn values<'a>(u: &mut Unstructured<'a>, depth: usize, roundtrip: bool) -> Result<Vec<Value<'a>>> {
+    collect(u, |u| value(u, depth, roundtrip))
+}
+
+fn collect<'a, T>(
+    u: &mut Unstructured<'a>,
+    mut element: impl FnMut(&mut Unstructured<'a>) -> Result<T>,
+) -> Result<Vec<T>> {
+    let len = u.int_in_range(0..=MAX_LEN)?;
+    (0..len).map(|_| element(u)).collect()
+}
+
+fn name(u: &mut Unstructured<'_>) -> Result<Cow<'static, str>> {
+    u.choose(NAMES).map(|name| Cow::Borrowed(*name))
+}
@@ -0,0 +1,488 @@
+use super::ArenaData;
+use super::ArenaEnum;
+use super::ArenaValue;
+use super::Bump;
+use crate::limits::capacity;
+use crate::Error;
+use crate::Number;
+use bumpalo::collections::Vec;
+use core::fmt;
+use core::slice;
+use serde::de;
+use serde::de::value::BorrowedStrDeserializer;
+use serde::de::IntoDeserializer;
+use serde::de::Unexpected;
+
+/// A Serde visitor that deserializes any value into an [ArenaValue] allocated in an arena.
+///
+/// Strings and byte arrays are copied into the arena, so the value doesn't borrow from the
+/// input.
+///
+/// ## Example
+///
+/// ```
+/// # use serde::de::DeserializeSeed;
+/// # use serde_content::arena::{ArenaValue, Bump, ValueVisitor};
+/// # use serde_content::{value, Deserializer};
+/// let arena = Bump::new();
+/// let deserializer = Deserializer::new(value!(["a", 1u8]));
+/// let value = ValueVisitor::new(&arena).deserialize(deserializer).unwrap();
+/// assert_eq!(value.to_value(), value!(["a", 1u8]));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct ValueVisitor<'arena> {
+    arena: &'arena Bump,
+}
+
+impl<'arena> ValueVisitor<'arena> {
+    /// Creates a visitor allocating values in `arena`.
+    pub const fn new(arena: &'arena Bump) -> Self {
+        Self { arena }
+    }
+}
+
+impl<'de, 'arena> de::DeserializeSeed<'de> for ValueVisitor<'arena> {
+    type Value = ArenaValue<'arena>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_any(self)
+    }
+}
+
+macro_rules! number {
+    ($($method:ident($ty:ty) => $variant:ident,)*) => {
+        $(
+            fn $method<E>(self, value: $ty) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Ok(ArenaValue::Number(Number::$variant(value)))
+            }
+        )*
+    };
+}
+
+impl<'de, 'arena> de::Visitor<'de> for ValueVisitor<'arena> {
+    type Value = ArenaValue<'arena>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("any value")
+    }
+
+    number! {
+        visit_i8(i8) => I8,
+        visit_i16(i16) => I16,
+        visit_i32(i32) => I32,
+        visit_i64(i64) => I64,
+        visit_i128(i128) => I128,
+        visit_u8(u8) => U8,
+        visit_u16(u16) => U16,
+        visit_u32(u32) => U32,
+        visit_u64(u64) => U64,
+        visit_u128(u128) => U128,
+        visit_f32(f32) => F32,
+        visit_f64(f64) => F64,
+    }
+
+    fn visit_bool<E>(self, value: bool) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(ArenaValue::Bool(value))
+    }
+
+    fn visit_char<E>(self, value: char) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(ArenaValue::Char(value))
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(ArenaValue::String(self.arena.alloc_str(value)))
+    }
+
+    fn visit_bytes<E>(self, value: &[u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(ArenaValue::Bytes(self.arena.alloc_slice_copy(value)))
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(ArenaValue::Unit)
+    }
+
+    fn visit_none<E>(self) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(ArenaValue::Option(None))
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        let value = de::DeserializeSeed::deserialize(self, deserializer)?;
+        Ok(ArenaValue::Option(Some(self.arena.alloc(value))))
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        de::DeserializeSeed::deserialize(self, deserializer)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::SeqAccess<'de>,
+    {
+        let len = capacity::<ArenaValue>(seq.size_hint());
+        let mut values = Vec::with_capacity_in(len, self.arena);
+        while let Some(value) = seq.next_element_seed(self)? {
+            values.push(value);
+        }
+        Ok(ArenaValue::Seq(values.into_bump_slice()))
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::MapAccess<'de>,
+    {
+        let len = capacity::<(ArenaValue, ArenaValue)>(map.size_hint());
+        let mut entries = Vec::with_capacity_in(len, self.arena);
+        while let Some(entry) = map.next_entry_seed(self, self)? {
+            entries.push(entry);
+        }
+        Ok(ArenaValue::Map(entries.into_bump_slice()))
+    }
+}
+
+/// A structure that deserializes Rust values from an [ArenaValue].
+///
+/// Strings and byte arrays in the arena can be borrowed by the value deserialized.
+///
+/// ## Example
+///
+/// ```
+/// # use serde_content::arena::{ArenaValue, Bump, Deserializer, Serializer};
+/// let arena = Bump::new();
+/// let value = Serializer::new(&arena).serialize(Some("Ferris")).unwrap();
+/// let name: Option<&str> = Deserializer::new(value).deserialize().unwrap();
+/// assert_eq!(name, Some("Ferris"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Deserializer<'arena> {
+    value: ArenaValue<'arena>,
+    human_readable: bool,
+}
+
+impl<'arena> Deserializer<'arena> {
+    /// Creates a deserializer for `value`.
+    ///
+    /// The deserializer created doesn't deserialize in human-readable form. To deserialize
+    /// in human-readable form, call [Deserializer::human_readable] on the resulting deserializer.
+    pub const fn new(value: ArenaValue<'arena>) -> Self {
+        Self {
+            value,
+            human_readable: false,
+        }
+    }
+
+    /// Make `Deserialize` implementations deserialize in human-readable form.
+    pub const fn human_readable(mut self) -> Self {
+        self.human_readable = true;
+        self
+    }
+
+    /// Deserializes a `T` from the value.
+    pub fn deserialize<T>(self) -> Result<T, Error>
+    where
+        T: de::Deserialize<'arena>,
+    {
+        T::deserialize(self)
+    }
+
+    // Creates a deserializer for a value nested inside this one.
+    const fn nested(self, value: ArenaValue<'arena>) -> Self {
+        Self { value, ..self }
+    }
+
+    fn seq(self, values: &'arena [ArenaValue<'arena>]) -> Seq<'arena> {
+        Seq {
+            values: values.iter(),
+            de: self,
+        }
+    }
+
+    fn fields(self, fields: &'arena [(&'static str, ArenaValue<'arena>)]) -> Map<'arena> {
+        Map {
+            entries: Entries::Fields(fields.iter()),
+            value: None,
+            de: self,
+        }
+    }
+}
+
+impl<'de> de::Deserializer<'de> for Deserializer<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self.value {
+            ArenaValue::Unit => visitor.visit_unit(),
+            ArenaValue::Bool(v) => visitor.visit_bool(v),
+            ArenaValue::Number(n) => match n {
+                Number::I8(v) => visitor.visit_i8(v),
+                Number::U8(v) => visitor.visit_u8(v),
+                Number::I16(v) => visitor.visit_i16(v),
+                Number::U16(v) => visitor.visit_u16(v),
+                Number::I32(v) => visitor.visit_i32(v),
+                Number::U32(v) => visitor.visit_u32(v),
+                Number::F32(v) => visitor.visit_f32(v),
+                Number::I64(v) => visitor.visit_i64(v),
+                Number::U64(v) => visitor.visit_u64(v),
+                Number::F64(v) => visitor.visit_f64(v),
+                Number::I128(v) => visitor.visit_i128(v),
+                Number::U128(v) => visitor.visit_u128(v),
+            },
+            ArenaValue::Char(v) => visitor.visit_char(v),
+            ArenaValue::String(v) => visitor.visit_borrowed_str(v),
+            ArenaValue::Bytes(v) => visitor.visit_borrowed_bytes(v),
+            ArenaValue::Seq(v) | ArenaValue::Tuple(v) => visitor.visit_seq(self.seq(v)),
+            ArenaValue::Map(v) => visitor.visit_map(Map {
+                entries: Entries::Map(v.iter()),
+                value: None,
+                de: self,
+            }),
+            ArenaValue::Option(Some(v)) => visitor.visit_some(self.nested(*v)),
+            ArenaValue::Option(None) => visitor.visit_none(),
+            ArenaValue::Struct(v) => match v.data {
+                ArenaData::Unit => visitor.visit_unit(),
+                ArenaData::NewType { value } => visitor.visit_newtype_struct(self.nested(value)),
+                ArenaData::Tuple { values } => visitor.visit_seq(self.seq(values)),
+                ArenaData::Struct { fields } => visitor.visit_map(self.fields(fields)),
+            },
+            ArenaValue::Enum(v) => visitor.visit_enum(Variant {
+                r#enum: v,
+                de: self,
+            }),
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self.value {
+            ArenaValue::Option(None) | ArenaValue::Unit => visitor.visit_none(),
+            ArenaValue::Option(Some(v)) => visitor.visit_some(self.nested(*v)),
+            // Any other value stands for itself wrapped in `Some`.
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self.value {
+            ArenaValue::Struct(v) => match v.data {
+                ArenaData::NewType { value } => visitor.visit_newtype_struct(self.nested(value)),
+                _ => visitor.visit_newtype_struct(self),
+            },
+            _ => visitor.visit_newtype_struct(self),
+        }
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self.value {
+            // A unit variant can also be given by its name alone.
+            ArenaValue::String(variant) => visitor.visit_enum(variant.into_deserializer()),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.human_readable
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
+
+struct Seq<'arena> {
+    values: slice::Iter<'arena, ArenaValue<'arena>>,
+    de: Deserializer<'arena>,
+}
+
+impl<'arena> de::SeqAccess<'arena> for Seq<'arena> {
+    type Error = Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Error>
+    where
+        T: de::DeserializeSeed<'arena>,
+    {
+        match self.values.next() {
+            Some(value) => seed.deserialize(self.de.nested(*value)).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.values.len())
+    }
+}
+
+enum Entries<'arena> {
+    Map(slice::Iter<'arena, (ArenaValue<'arena>, ArenaValue<'arena>)>),
+    Fields(slice::Iter<'arena, (&'static str, ArenaValue<'arena>)>),
+}
+
+struct Map<'arena> {
+    entries: Entries<'arena>,
+    // The value of the last key returned.
+    value: Option<ArenaValue<'arena>>,
+    de: Deserializer<'arena>,
+}
+
+impl<'arena> de::MapAccess<'arena> for Map<'arena> {
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Error>
+    where
+        K: de::DeserializeSeed<'arena>,
+    {
+        let (key, value) = match &mut self.entries {
+            Entries::Map(entries) => match entries.next() {
+                Some((key, value)) => (*key, *value),
+                None => return Ok(None),
+            },
+            Entries::Fields(fields) => match fields.next() {
+                Some((key, value)) => (ArenaValue::String(key), *value),
+                None => return Ok(None),
+            },
+        };
+        self.value = Some(value);
+        seed.deserialize(self.de.nested(key)).map(Some)
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Error>
+    where
+        V: de::DeserializeSeed<'arena>,
+    {
+        let value = self
+            .value
+            .take()
+            .ok_or_else(|| Error::custom("next_value_seed called before next_key_seed"))?;
+        seed.deserialize(self.de.nested(value))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        match &self.entries {
+            Entries::Map(entries) => Some(entries.len()),
+            Entries::Fields(fields) => Some(fields.len()),
+        }
+    }
+}
+
+struct Variant<'arena> {
+    r#enum: &'arena ArenaEnum<'arena>,
+    de: Deserializer<'arena>,
+}
+
+impl<'arena> de::EnumAccess<'arena> for Variant<'arena> {
+    type Error = Error;
+    type Variant = Self;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self), Error>
+    where
+        V: de::DeserializeSeed<'arena>,
+    {
+        let deserializer = BorrowedStrDeserializer::<Error>::new(self.r#enum.variant);
+        Ok((seed.deserialize(deserializer)?, self))
+    }
+}
+
+impl<'arena> de::VariantAccess<'arena> for Variant<'arena> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        match self.r#enum.data {
+            ArenaData::Unit => Ok(()),
+            data => Err(invalid_variant(data, &"unit variant")),
+        }
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, Error>
+    where
+        T: de::DeserializeSeed<'arena>,
+    {
+        match self.r#enum.data {
+            ArenaData::NewType { value } => seed.deserialize(self.de.nested(value)),
+            data => Err(invalid_variant(data, &"newtype variant")),
+        }
+    }
+
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value, Error>
+    where
+        V: de::Visitor<'arena>,
+    {
+        match self.r#enum.data {
+            ArenaData::Tuple { values } => visitor.visit_seq(self.de.seq(values)),
+            data => Err(invalid_variant(data, &"tuple variant")),
+        }
+    }
+
+    fn struct_variant<V>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: de::Visitor<'arena>,
+    {
+        match self.r#enum.data {
+            ArenaData::Struct { fields } => visitor.visit_map(self.de.fields(fields)),
+            data => Err(invalid_variant(data, &"struct variant")),
+        }
+    }
+}
+
+fn invalid_variant(data: ArenaData<'_>, expected: &dyn de::Expected) -> Error {
+    let unexpected = match data {
+        ArenaData::Unit => Unexpected::UnitVariant,
+        ArenaData::NewType { .. } => Unexpected::NewtypeVariant,
+        ArenaData::Tuple { .. } => Unexpected::TupleVariant,
+        ArenaData::Struct { .. } => Unexpected::StructVariant,
+    };
+    de::Error::invalid_type(unexpected, expected)
+}
@@ -0,0 +1,296 @@
+//! Values allocated in an arena, for building and discarding many values quickly.
+//!
+//! Each container of a [crate::Value] is a separate allocation, which adds up when building
+//! lots of small values. An [ArenaValue] keeps its strings, byte arrays and containers in a
+//! [Bump] arena instead, so building one only bumps a pointer and dropping it is free. All of
+//! its memory is released at once when the arena is reset or dropped. Arena values are `Copy`,
+//! since they only hold references into the arena.
+//!
+//! [Serializer] serializes any `Serialize` type into an arena value, [ValueVisitor]
+//! deserializes one from any self-describing format, and [Deserializer] deserializes any
+//! `Deserialize` type from one. [ArenaValue::from_value] and [ArenaValue::to_value] convert
+//! between arena values and [crate::Value].
+//!
+//! ## Example
+//!
+//! ```
+//! # use serde_content::arena::{Bump, Deserializer, Serializer};
+//! let mut arena = Bump::new();
+//! for i in 0..3u8 {
+//!     let value = Serializer::new(&arena).serialize((i, "text")).unwrap();
+//!     let found: (u8, &str) = Deserializer::new(value).deserialize().unwrap();
+//!     assert_eq!(found, (i, "text"));
+//!     arena.reset();
+//! }
+//! ```
+
+mod de;
+mod ser;
+
+use crate::Data;
+use crate::Enum;
+use crate::Error;
+use crate::Number;
+use crate::Struct;
+use crate::Value;
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use serde::ser::SerializeMap;
+use serde::ser::SerializeSeq;
+use serde::ser::SerializeStruct;
+use serde::ser::SerializeStructVariant;
+use serde::ser::SerializeTuple;
+use serde::ser::SerializeTupleStruct;
+use serde::ser::SerializeTupleVariant;
+
+pub use bumpalo::Bump;
+pub use de::Deserializer;
+pub use de::ValueVisitor;
+pub use ser::Serializer;
+
+/// Represents struct and enum data in an arena.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub enum ArenaData<'arena> {
+    /// Represents unit structs and unit enum variants.
+    Unit,
+    /// Represents newtype structs and enum variants.
+    NewType {
+        /// The value of the newtype struct or enum variant.
+        value: ArenaValue<'arena>,
+    },
+    /// Represents tuple structs and enum variants.
+    Tuple {
+        /// The values of the tuple struct or enum variant.
+        values: &'arena [ArenaValue<'arena>],
+    },
+    /// Represents object-like structs and enum variants.
+    Struct {
+        /// The field names and their values.
+        fields: &'arena [(&'static str, ArenaValue<'arena>)],
+    },
+}
+
+/// Represents a Rust struct in an arena.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct ArenaStruct<'arena> {
+    /// The name of the struct.
+    pub name: &'static str,
+    /// The data of the struct.
+    pub data: ArenaData<'arena>,
+}
+
+/// Represents a Rust enum in an arena.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct ArenaEnum<'arena> {
+    /// The name of the enum.
+    pub name: &'static str,
+    /// The index of the enum variant.
+    pub variant_index: u32,
+    /// The name of the enum variant.
+    pub variant: &'static str,
+    /// The data of the enum.
+    pub data: ArenaData<'arena>,
+}
+
+/// Represents any valid Rust value, allocated in an arena.
+///
+/// See the [module documentation](self) for details.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub enum ArenaValue<'arena> {
+    /// Represents the Rust unit type, `()`.
+    Unit,
+    /// Represents a Rust boolean.
+    Bool(bool),
+    /// Represents any Rust number.
+    Number(Number),
+    /// Represents a Rust character.
+    Char(char),
+    /// Represents a Rust string.
+    String(&'arena str),
+    /// Represents a Rust byte array.
+    Bytes(&'arena [u8]),
+    /// Represents an array of Rust values.
+    Seq(&'arena [ArenaValue<'arena>]),
+    /// Represents a map of Rust values.
+    Map(&'arena [(ArenaValue<'arena>, ArenaValue<'arena>)]),
+    /// Represents optional Rust values.
+    Option(Option<&'arena ArenaValue<'arena>>),
+    /// Represents a Rust struct.
+    Struct(&'arena ArenaStruct<'arena>),
+    /// Represents a Rust enum.
+    Enum(&'arena ArenaEnum<'arena>),
+    /// Represents a Rust tuple.
+    Tuple(&'arena [ArenaValue<'arena>]),
+}
+
+impl<'arena> ArenaValue<'arena> {
+    /// Copies `value` into `arena`.
+    ///
+    /// This serializes `value` with [Serializer], so structs and enums with owned names are
+    /// converted like [crate::Serializer] converts them.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use serde_content::arena::{ArenaValue, Bump};
+    /// # use serde_content::value;
+    /// let arena = Bump::new();
+    /// let value = value!(Point { x: 1i32, y: "two" });
+    /// let copy = ArenaValue::from_value(&arena, &value).unwrap();
+    /// assert_eq!(copy.to_value(), value);
+    /// ```
+    pub fn from_value(arena: &'arena Bump, value: &Value<'_>) -> Result<Self, Error> {
+        Serializer::new(arena).serialize(value)
+    }
+
+    /// Converts the value into a [Value] borrowing its strings and byte arrays from the arena.
+    pub fn to_value(&self) -> Value<'arena> {
+        match *self {
+            ArenaValue::Unit => Value::Unit,
+            ArenaValue::Bool(v) => Value::Bool(v),
+            ArenaValue::Number(v) => Value::Number(v),
+            ArenaValue::Char(v) => Value::Char(v),
+            ArenaValue::String(v) => Value::String(Cow::Borrowed(v)),
+            ArenaValue::Bytes(v) => Value::Bytes(Cow::Borrowed(v)),
+            ArenaValue::Seq(v) => Value::Seq(v.iter().map(Self::to_value).collect()),
+            ArenaValue::Map(v) => Value::Map(
+                v.iter()
+                    .map(|(key, value)| (key.to_value(), value.to_value()))
+                    .collect(),
+            ),
+            ArenaValue::Option(v) => Value::Option(v.map(|v| Box::new(v.to_value()))),
+            ArenaValue::Struct(v) => Value::Struct(Box::new(Struct {
+                name: Cow::Borrowed(v.name),
+                data: v.data.to_data(),
+            })),
+            ArenaValue::Enum(v) => Value::Enum(Box::new(Enum {
+                name: Cow::Borrowed(v.name),
+                variant_index: v.variant_index,
+                variant: Cow::Borrowed(v.variant),
+                data: v.data.to_data(),
+            })),
+            ArenaValue::Tuple(v) => Value::Tuple(v.iter().map(Self::to_value).collect()),
+        }
+    }
+}
+
+impl<'arena> ArenaData<'arena> {
+    /// Converts the data into [Data] borrowing its strings and byte arrays from the arena.
+    pub fn to_data(&self) -> Data<'arena> {
+        match *self {
+            ArenaData::Unit => Data::Unit,
+            ArenaData::NewType { value } => Data::NewType {
+                value: value.to_value(),
+            },
+            ArenaData::Tuple { values } => Data::Tuple {
+                values: values.iter().map(ArenaValue::to_value).collect(),
+            },
+            ArenaData::Struct { fields } => Data::Struct {
+                fields: fields
+                    .iter()
+                    .map(|(key, value)| (Cow::Borrowed(*key), value.to_value()))
+                    .collect(),
+            },
+        }
+    }
+}
+
+impl serde::Serialize for ArenaValue<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match *self {
+            ArenaValue::Unit => serializer.serialize_unit(),
+            ArenaValue::Bool(v) => serializer.serialize_bool(v),
+            ArenaValue::Number(v) => v.serialize(serializer),
+            ArenaValue::Char(v) => serializer.serialize_char(v),
+            ArenaValue::String(v) => serializer.serialize_str(v),
+            ArenaValue::Bytes(v) => serializer.serialize_bytes(v),
+            ArenaValue::Seq(v) => {
+                let mut seq = serializer.serialize_seq(Some(v.len()))?;
+                for value in v {
+                    seq.serialize_element(value)?;
+                }
+                seq.end()
+            }
+            ArenaValue::Map(v) => {
+                let mut map = serializer.serialize_map(Some(v.len()))?;
+                for (key, value) in v {
+                    map.serialize_entry(key, value)?;
+                }
+                map.end()
+            }
+            ArenaValue::Option(v) => match v {
+                Some(v) => serializer.serialize_some(v),
+                None => serializer.serialize_none(),
+            },
+            ArenaValue::Struct(v) => v.serialize(serializer),
+            ArenaValue::Enum(v) => v.serialize(serializer),
+            ArenaValue::Tuple(v) => {
+                let mut tup = serializer.serialize_tuple(v.len())?;
+                for value in v {
+                    tup.serialize_element(value)?;
+                }
+                tup.end()
+            }
+        }
+    }
+}
+
+impl serde::Serialize for ArenaStruct<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self.data {
+            ArenaData::Unit => serializer.serialize_unit_struct(self.name),
+            ArenaData::NewType { value } => serializer.serialize_newtype_struct(self.name, &value),
+            ArenaData::Tuple { values } => {
+                let mut tup = serializer.serialize_tuple_struct(self.name, values.len())?;
+                for value in values {
+                    tup.serialize_field(value)?;
+                }
+                tup.end()
+            }
+            ArenaData::Struct { fields } => {
+                let mut st = serializer.serialize_struct(self.name, fields.len())?;
+                for (key, value) in fields {
+                    st.serialize_field(key, value)?;
+                }
+                st.end()
+            }
+        }
+    }
+}
+
+impl serde::Serialize for ArenaEnum<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let (name, index, variant) = (self.name, self.variant_index, self.variant);
+        match self.data {
+            ArenaData::Unit => serializer.serialize_unit_variant(name, index, variant),
+            ArenaData::NewType { value } => {
+                serializer.serialize_newtype_variant(name, index, variant, &value)
+            }
+            ArenaData::Tuple { values } => {
+                let mut tup =
+                    serializer.serialize_tuple_variant(name, index, variant, values.len())?;
+                for value in values {
+                    tup.serialize_field(value)?;
+                }
+                tup.end()
+            }
+            ArenaData::Struct { fields } => {
+                let mut st =
+                    serializer.serialize_struct_variant(name, index, variant, fields.len())?;
+                for (key, value) in fields {
+                    st.serialize_field(key, value)?;
+                }
+                st.end()
+            }
+        }
+    }
+}
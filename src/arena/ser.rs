@@ -0,0 +1,507 @@
+use super::ArenaData;
+use super::ArenaEnum;
+use super::ArenaStruct;
+use super::ArenaValue;
+use super::Bump;
+use crate::Error;
+use crate::Number;
+use bumpalo::collections::String;
+use bumpalo::collections::Vec;
+use core::fmt;
+use core::fmt::Write as _;
+use serde::ser;
+
+/// A structure for serializing Rust values into [ArenaValue]s.
+///
+/// ## Example
+///
+/// ```
+/// # use serde_content::arena::{ArenaValue, Bump, Serializer};
+/// let arena = Bump::new();
+/// let value = Serializer::new(&arena).serialize(["a", "b"]).unwrap();
+/// assert_eq!(value, ArenaValue::Tuple(&[ArenaValue::String("a"), ArenaValue::String("b")]));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Serializer<'arena> {
+    arena: &'arena Bump,
+    human_readable: bool,
+}
+
+impl<'arena> Serializer<'arena> {
+    /// Creates a serializer allocating values in `arena`.
+    ///
+    /// The serializer created doesn't serialize in human-readable form. To serialize
+    /// in human-readable form, call [Serializer::human_readable] on the resulting serializer.
+    pub const fn new(arena: &'arena Bump) -> Self {
+        Self {
+            arena,
+            human_readable: false,
+        }
+    }
+
+    /// Make `Serialize` implementations serialize in human-readable form.
+    pub const fn human_readable(mut self) -> Self {
+        self.human_readable = true;
+        self
+    }
+
+    /// Convert a `T` into an [ArenaValue] allocated in the arena.
+    pub fn serialize<T>(self, value: T) -> Result<ArenaValue<'arena>, Error>
+    where
+        T: ser::Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn r#struct(self, name: &'static str, data: ArenaData<'arena>) -> ArenaValue<'arena> {
+        ArenaValue::Struct(self.arena.alloc(ArenaStruct { name, data }))
+    }
+
+    fn variant(self, variant: Variant, data: ArenaData<'arena>) -> ArenaValue<'arena> {
+        ArenaValue::Enum(self.arena.alloc(ArenaEnum {
+            name: variant.name,
+            variant_index: variant.variant_index,
+            variant: variant.variant,
+            data,
+        }))
+    }
+}
+
+impl<'arena> ser::Serializer for Serializer<'arena> {
+    type Ok = ArenaValue<'arena>;
+    type Error = Error;
+
+    type SerializeSeq = Seq<'arena>;
+    type SerializeTuple = Seq<'arena>;
+    type SerializeTupleStruct = Seq<'arena>;
+    type SerializeTupleVariant = Seq<'arena>;
+    type SerializeMap = Map<'arena>;
+    type SerializeStruct = Struct<'arena>;
+    type SerializeStructVariant = Struct<'arena>;
+
+    fn serialize_bool(self, value: bool) -> Result<Self::Ok, Error> {
+        Ok(ArenaValue::Bool(value))
+    }
+
+    fn serialize_i8(self, value: i8) -> Result<Self::Ok, Error> {
+        Ok(ArenaValue::Number(Number::I8(value)))
+    }
+
+    fn serialize_i16(self, value: i16) -> Result<Self::Ok, Error> {
+        Ok(ArenaValue::Number(Number::I16(value)))
+    }
+
+    fn serialize_i32(self, value: i32) -> Result<Self::Ok, Error> {
+        Ok(ArenaValue::Number(Number::I32(value)))
+    }
+
+    fn serialize_i64(self, value: i64) -> Result<Self::Ok, Error> {
+        Ok(ArenaValue::Number(Number::I64(value)))
+    }
+
+    fn serialize_i128(self, value: i128) -> Result<Self::Ok, Error> {
+        Ok(ArenaValue::Number(Number::I128(value)))
+    }
+
+    fn serialize_u8(self, value: u8) -> Result<Self::Ok, Error> {
+        Ok(ArenaValue::Number(Number::U8(value)))
+    }
+
+    fn serialize_u16(self, value: u16) -> Result<Self::Ok, Error> {
+        Ok(ArenaValue::Number(Number::U16(value)))
+    }
+
+    fn serialize_u32(self, value: u32) -> Result<Self::Ok, Error> {
+        Ok(ArenaValue::Number(Number::U32(value)))
+    }
+
+    fn serialize_u64(self, value: u64) -> Result<Self::Ok, Error> {
+        Ok(ArenaValue::Number(Number::U64(value)))
+    }
+
+    fn serialize_u128(self, value: u128) -> Result<Self::Ok, Error> {
+        Ok(ArenaValue::Number(Number::U128(value)))
+    }
+
+    fn serialize_f32(self, value: f32) -> Result<Self::Ok, Error> {
+        Ok(ArenaValue::Number(Number::F32(value)))
+    }
+
+    fn serialize_f64(self, value: f64) -> Result<Self::Ok, Error> {
+        Ok(ArenaValue::Number(Number::F64(value)))
+    }
+
+    fn serialize_char(self, value: char) -> Result<Self::Ok, Error> {
+        Ok(ArenaValue::Char(value))
+    }
+
+    fn serialize_str(self, value: &str) -> Result<Self::Ok, Error> {
+        Ok(ArenaValue::String(self.arena.alloc_str(value)))
+    }
+
+    fn serialize_bytes(self, value: &[u8]) -> Result<Self::Ok, Error> {
+        Ok(ArenaValue::Bytes(self.arena.alloc_slice_copy(value)))
+    }
+
+    fn serialize_unit(self) -> Result<Self::Ok, Error> {
+        Ok(ArenaValue::Unit)
+    }
+
+    fn serialize_unit_struct(self, name: &'static str) -> Result<Self::Ok, Error> {
+        Ok(self.r#struct(name, ArenaData::Unit))
+    }
+
+    fn serialize_unit_variant(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Self::Ok, Error> {
+        let variant = Variant {
+            name,
+            variant_index,
+            variant,
+        };
+        Ok(self.variant(variant, ArenaData::Unit))
+    }
+
+    fn serialize_newtype_struct<T>(self, name: &'static str, value: &T) -> Result<Self::Ok, Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        let value = value.serialize(self)?;
+        Ok(self.r#struct(name, ArenaData::NewType { value }))
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        let variant = Variant {
+            name,
+            variant_index,
+            variant,
+        };
+        let value = value.serialize(self)?;
+        Ok(self.variant(variant, ArenaData::NewType { value }))
+    }
+
+    fn serialize_none(self) -> Result<Self::Ok, Error> {
+        Ok(ArenaValue::Option(None))
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<Self::Ok, Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        let value = value.serialize(self)?;
+        Ok(ArenaValue::Option(Some(self.arena.alloc(value))))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, Error> {
+        Ok(Seq::new(self, len.unwrap_or_default(), SeqKind::Seq))
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Error> {
+        Ok(Seq::new(self, len, SeqKind::Tuple))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Error> {
+        Ok(Seq::new(self, len, SeqKind::Struct(name)))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Error> {
+        let variant = Variant {
+            name,
+            variant_index,
+            variant,
+        };
+        Ok(Seq::new(self, len, SeqKind::Variant(variant)))
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap, Error> {
+        Ok(Map {
+            entries: Vec::with_capacity_in(len.unwrap_or_default(), self.arena),
+            key: None,
+            serializer: self,
+        })
+    }
+
+    fn serialize_struct(
+        self,
+        name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStruct, Error> {
+        Ok(Struct::new(self, len, StructKind::Struct(name)))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStructVariant, Error> {
+        let variant = Variant {
+            name,
+            variant_index,
+            variant,
+        };
+        Ok(Struct::new(self, len, StructKind::Variant(variant)))
+    }
+
+    fn collect_str<T>(self, value: &T) -> Result<Self::Ok, Error>
+    where
+        T: ?Sized + fmt::Display,
+    {
+        let mut string = String::new_in(self.arena);
+        write!(string, "{value}").map_err(Error::custom)?;
+        Ok(ArenaValue::String(string.into_bump_str()))
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.human_readable
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Variant {
+    name: &'static str,
+    variant_index: u32,
+    variant: &'static str,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum SeqKind {
+    Seq,
+    Tuple,
+    Struct(&'static str),
+    Variant(Variant),
+}
+
+/// Serializes sequences, tuples, tuple structs and tuple variants.
+pub struct Seq<'arena> {
+    values: Vec<'arena, ArenaValue<'arena>>,
+    serializer: Serializer<'arena>,
+    kind: SeqKind,
+}
+
+impl<'arena> Seq<'arena> {
+    fn new(serializer: Serializer<'arena>, len: usize, kind: SeqKind) -> Self {
+        Self {
+            values: Vec::with_capacity_in(len, serializer.arena),
+            serializer,
+            kind,
+        }
+    }
+
+    fn push<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        self.values.push(value.serialize(self.serializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> ArenaValue<'arena> {
+        let values = self.values.into_bump_slice();
+        match self.kind {
+            SeqKind::Seq => ArenaValue::Seq(values),
+            SeqKind::Tuple => ArenaValue::Tuple(values),
+            SeqKind::Struct(name) => self.serializer.r#struct(name, ArenaData::Tuple { values }),
+            SeqKind::Variant(variant) => self
+                .serializer
+                .variant(variant, ArenaData::Tuple { values }),
+        }
+    }
+}
+
+impl<'arena> ser::SerializeSeq for Seq<'arena> {
+    type Ok = ArenaValue<'arena>;
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Error> {
+        Ok(Seq::end(self))
+    }
+}
+
+impl<'arena> ser::SerializeTuple for Seq<'arena> {
+    type Ok = ArenaValue<'arena>;
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Error> {
+        Ok(Seq::end(self))
+    }
+}
+
+impl<'arena> ser::SerializeTupleStruct for Seq<'arena> {
+    type Ok = ArenaValue<'arena>;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Error> {
+        Ok(Seq::end(self))
+    }
+}
+
+impl<'arena> ser::SerializeTupleVariant for Seq<'arena> {
+    type Ok = ArenaValue<'arena>;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Error> {
+        Ok(Seq::end(self))
+    }
+}
+
+/// Serializes maps.
+pub struct Map<'arena> {
+    entries: Vec<'arena, (ArenaValue<'arena>, ArenaValue<'arena>)>,
+    // The key waiting for its value.
+    key: Option<ArenaValue<'arena>>,
+    serializer: Serializer<'arena>,
+}
+
+impl<'arena> ser::SerializeMap for Map<'arena> {
+    type Ok = ArenaValue<'arena>;
+    type Error = Error;
+
+    fn serialize_key<T>(&mut self, key: &T) -> Result<(), Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        self.key = Some(key.serialize(self.serializer)?);
+        Ok(())
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        let key = self
+            .key
+            .take()
+            .ok_or_else(|| Error::custom("serialize_value called before serialize_key"))?;
+        self.entries.push((key, value.serialize(self.serializer)?));
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok, Error> {
+        Ok(ArenaValue::Map(self.entries.into_bump_slice()))
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum StructKind {
+    Struct(&'static str),
+    Variant(Variant),
+}
+
+/// Serializes object-like structs and enum variants.
+pub struct Struct<'arena> {
+    fields: Vec<'arena, (&'static str, ArenaValue<'arena>)>,
+    serializer: Serializer<'arena>,
+    kind: StructKind,
+}
+
+impl<'arena> Struct<'arena> {
+    fn new(serializer: Serializer<'arena>, len: usize, kind: StructKind) -> Self {
+        Self {
+            fields: Vec::with_capacity_in(len, serializer.arena),
+            serializer,
+            kind,
+        }
+    }
+
+    fn push<T>(&mut self, key: &'static str, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        self.fields.push((key, value.serialize(self.serializer)?));
+        Ok(())
+    }
+
+    fn end(self) -> ArenaValue<'arena> {
+        let data = ArenaData::Struct {
+            fields: self.fields.into_bump_slice(),
+        };
+        match self.kind {
+            StructKind::Struct(name) => self.serializer.r#struct(name, data),
+            StructKind::Variant(variant) => self.serializer.variant(variant, data),
+        }
+    }
+}
+
+impl<'arena> ser::SerializeStruct for Struct<'arena> {
+    type Ok = ArenaValue<'arena>;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        self.push(key, value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Error> {
+        Ok(Struct::end(self))
+    }
+}
+
+impl<'arena> ser::SerializeStructVariant for Struct<'arena> {
+    type Ok = ArenaValue<'arena>;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        self.push(key, value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Error> {
+        Ok(Struct::end(self))
+    }
+}
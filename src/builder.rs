@@ -0,0 +1,224 @@
+use crate::Data;
+use crate::Enum;
+use crate::Error;
+use crate::Expected;
+use crate::Found;
+use crate::Result;
+use crate::Struct;
+use crate::Value;
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+
+mod private {
+    use crate::Data;
+
+    /// A type that holds struct or enum data.
+    pub trait Sealed<'a> {
+        fn data(&mut self) -> &mut Data<'a>;
+    }
+}
+
+use private::Sealed;
+
+impl<'a> Sealed<'a> for Data<'a> {
+    fn data(&mut self) -> &mut Data<'a> {
+        self
+    }
+}
+
+impl<'a> Sealed<'a> for Struct<'a> {
+    fn data(&mut self) -> &mut Data<'a> {
+        &mut self.data
+    }
+}
+
+impl<'a> Sealed<'a> for Enum<'a> {
+    fn data(&mut self) -> &mut Data<'a> {
+        &mut self.data
+    }
+}
+
+/// Builds tuple data, structs or enum variants one value at a time.
+///
+/// Created by [Data::tuple], [Struct::tuple] and [Enum::tuple].
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct TupleBuilder<T> {
+    inner: T,
+}
+
+impl<'a, T> TupleBuilder<T>
+where
+    T: Sealed<'a>,
+{
+    fn new(mut inner: T) -> Self {
+        *inner.data() = Data::Tuple { values: Vec::new() };
+        Self { inner }
+    }
+
+    /// Appends a value to the tuple.
+    pub fn field(mut self, value: impl Into<Value<'a>>) -> Self {
+        if let Data::Tuple { values } = self.inner.data() {
+            values.push(value.into());
+        }
+        self
+    }
+
+    /// Returns the tuple data, struct or enum variant.
+    pub fn build(self) -> T {
+        self.inner
+    }
+}
+
+/// Builds object-like data, structs or enum variants one field at a time.
+///
+/// Created by [Data::object], [Struct::object] and [Enum::object].
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct ObjectBuilder<T> {
+    inner: T,
+    duplicate: Option<&'static str>,
+}
+
+impl<'a, T> ObjectBuilder<T>
+where
+    T: Sealed<'a>,
+{
+    fn new(mut inner: T) -> Self {
+        *inner.data() = Data::Struct { fields: Vec::new() };
+        Self {
+            inner,
+            duplicate: None,
+        }
+    }
+
+    /// Appends a field to the object.
+    pub fn field(mut self, name: &'static str, value: impl Into<Value<'a>>) -> Self {
+        if let Data::Struct { fields } = self.inner.data() {
+            if self.duplicate.is_none() && fields.iter().any(|(field, _)| field == name) {
+                self.duplicate = Some(name);
+            }
+            fields.push((Cow::Borrowed(name), value.into()));
+        }
+        self
+    }
+
+    /// Returns the object-like data, struct or enum variant.
+    ///
+    /// Fails if the same field name was added more than once.
+    pub fn build(self) -> Result<T> {
+        match self.duplicate {
+            Some(field) => Err(Error::duplicate_field(field)),
+            None => Ok(self.inner),
+        }
+    }
+}
+
+impl<'a> Data<'a> {
+    /// Creates newtype data.
+    pub fn newtype(value: impl Into<Value<'a>>) -> Self {
+        Data::NewType {
+            value: value.into(),
+        }
+    }
+
+    /// Starts building tuple data.
+    pub fn tuple() -> TupleBuilder<Self> {
+        TupleBuilder::new(Data::Unit)
+    }
+
+    /// Starts building object-like data.
+    pub fn object() -> ObjectBuilder<Self> {
+        ObjectBuilder::new(Data::Unit)
+    }
+}
+
+impl<'a> Struct<'a> {
+    /// Creates a unit struct.
+    pub const fn unit(name: &'static str) -> Self {
+        Struct {
+            name: Cow::Borrowed(name),
+            data: Data::Unit,
+        }
+    }
+
+    /// Creates a newtype struct.
+    pub fn newtype(name: &'static str, value: impl Into<Value<'a>>) -> Self {
+        Struct {
+            name: Cow::Borrowed(name),
+            data: Data::newtype(value),
+        }
+    }
+
+    /// Starts building a tuple struct.
+    pub fn tuple(name: &'static str) -> TupleBuilder<Self> {
+        TupleBuilder::new(Self::unit(name))
+    }
+
+    /// Starts building an object-like struct.
+    pub fn object(name: &'static str) -> ObjectBuilder<Self> {
+        ObjectBuilder::new(Self::unit(name))
+    }
+}
+
+impl<'a> Enum<'a> {
+    /// Creates a unit enum variant.
+    ///
+    /// Use [Enum::newtype], [Enum::tuple] or [Enum::object] to give the variant other data.
+    pub const fn new(name: &'static str, variant_index: u32, variant: &'static str) -> Self {
+        Enum {
+            name: Cow::Borrowed(name),
+            variant_index,
+            variant: Cow::Borrowed(variant),
+            data: Data::Unit,
+        }
+    }
+
+    /// Creates a unit enum variant, taking its index from the position of `variant` in `variants`.
+    ///
+    /// `variants` is usually the same list `serde` passes to `Deserializer::deserialize_enum`.
+    pub fn from_variants(
+        name: &'static str,
+        variants: &[&'static str],
+        variant: &str,
+    ) -> Result<Self> {
+        match variants.iter().position(|v| *v == variant) {
+            Some(index) => Ok(Self::new(name, index as u32, variants[index])),
+            None => Err(Error::unexpected(
+                Found::Identifier(variant.to_string()),
+                Expected::Enum {
+                    name: Some(name.to_string()),
+                    typ: None,
+                },
+            )),
+        }
+    }
+
+    /// Turns this variant into a newtype variant.
+    pub fn newtype(mut self, value: impl Into<Value<'a>>) -> Self {
+        self.data = Data::newtype(value);
+        self
+    }
+
+    /// Starts turning this variant into a tuple variant.
+    pub fn tuple(self) -> TupleBuilder<Self> {
+        TupleBuilder::new(self)
+    }
+
+    /// Starts turning this variant into an object-like variant.
+    pub fn object(self) -> ObjectBuilder<Self> {
+        ObjectBuilder::new(self)
+    }
+}
+
+impl<'a> From<Struct<'a>> for Value<'a> {
+    fn from(value: Struct<'a>) -> Self {
+        Self::Struct(Box::new(value))
+    }
+}
+
+impl<'a> From<Enum<'a>> for Value<'a> {
+    fn from(value: Enum<'a>) -> Self {
+        Self::Enum(Box::new(value))
+    }
+}
@@ -0,0 +1,125 @@
+use alloc::string::String;
+#[cfg(feature = "serde")]
+use alloc::vec::Vec;
+
+/// A text encoding for byte arrays in human-readable formats.
+///
+/// See `Deserializer::bytes_encoding` and [json::Policy::bytes_encoding](crate::json::Policy::bytes_encoding).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive] // In case we support more encodings in future.
+pub enum BytesEncoding {
+    /// Base64 with the standard alphabet (RFC 4648 §4). Padding is optional.
+    Base64,
+    /// Base64 with the URL and filename safe alphabet (RFC 4648 §5). Padding is optional.
+    Base64Url,
+    /// Hexadecimal, in either lower or upper case.
+    Hex,
+}
+
+impl BytesEncoding {
+    /// Decodes `text`, returning `None` if it's not valid in this encoding.
+    #[cfg(feature = "serde")]
+    pub(crate) fn decode(self, text: &str) -> Option<Vec<u8>> {
+        match self {
+            BytesEncoding::Base64 => base64(text, b'+', b'/'),
+            BytesEncoding::Base64Url => base64(text, b'-', b'_'),
+            BytesEncoding::Hex => hex(text),
+        }
+    }
+
+    /// Encodes `bytes` as text. Base64 is padded, while the URL and filename safe variant isn't.
+    pub(crate) fn encode(self, bytes: &[u8]) -> String {
+        match self {
+            BytesEncoding::Base64 => encode_base64(bytes, b'+', b'/', true),
+            BytesEncoding::Base64Url => encode_base64(bytes, b'-', b'_', false),
+            BytesEncoding::Hex => encode_hex(bytes),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+fn base64(text: &str, c62: u8, c63: u8) -> Option<Vec<u8>> {
+    let text = text.trim_end_matches('=').as_bytes();
+    // A single trailing character can't encode a whole byte.
+    if text.len() % 4 == 1 {
+        return None;
+    }
+    let mut bytes = Vec::with_capacity(text.len() * 3 / 4);
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for &c in text {
+        let sextet = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            c if c == c62 => 62,
+            c if c == c63 => 63,
+            _ => return None,
+        };
+        buffer = (buffer << 6) | sextet as u32;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+    Some(bytes)
+}
+
+#[cfg(feature = "serde")]
+fn hex(text: &str) -> Option<Vec<u8>> {
+    let text = text.as_bytes();
+    if text.len() % 2 == 1 {
+        return None;
+    }
+    let nibble = |c: u8| match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'a'..=b'f' => Some(c - b'a' + 10),
+        b'A'..=b'F' => Some(c - b'A' + 10),
+        _ => None,
+    };
+    text.chunks(2)
+        .map(|pair| Some((nibble(pair[0])? << 4) | nibble(pair[1])?))
+        .collect()
+}
+
+fn encode_base64(bytes: &[u8], c62: u8, c63: u8, pad: bool) -> String {
+    let char = |sextet: u32| {
+        let sextet = (sextet & 0x3f) as u8;
+        let c = match sextet {
+            0..=25 => b'A' + sextet,
+            26..=51 => b'a' + sextet - 26,
+            52..=61 => b'0' + sextet - 52,
+            62 => c62,
+            _ => c63,
+        };
+        c as char
+    };
+    let mut text = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let buffer = chunk
+            .iter()
+            .fold(0u32, |buffer, &byte| (buffer << 8) | byte as u32)
+            << (8 * (3 - chunk.len()));
+        // Each byte needs at least one more character to be encoded.
+        for i in 0..4 {
+            if i <= chunk.len() {
+                text.push(char(buffer >> (18 - 6 * i)));
+            } else if pad {
+                text.push('=');
+            }
+        }
+    }
+    text
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+    let mut text = String::with_capacity(bytes.len() * 2);
+    for &byte in bytes {
+        text.push(DIGITS[(byte >> 4) as usize] as char);
+        text.push(DIGITS[(byte & 0xf) as usize] as char);
+    }
+    text
+}
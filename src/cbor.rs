@@ -0,0 +1,226 @@
+use crate::Data;
+use crate::Enum;
+use crate::Error;
+use crate::Number;
+use crate::Value;
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::string::ToString;
+use alloc::vec;
+use alloc::vec::Vec;
+use ciborium::value::Integer;
+use ciborium::Value as Cbor;
+
+/// The name of the enum a CBOR tag is stored in.
+///
+/// This, [TAGGED] and its index are how `ciborium::tag::Captured` serializes a tagged item, so
+/// a `Captured` serialized by [Serializer](crate::Serializer) converts into a CBOR tag, and a
+/// converted one deserializes back into a `Captured`.
+const TAG: &str = "@@TAG@@";
+/// The name of the variant of [TAG] holding the tag number and the tagged item as a tuple.
+const TAGGED: &str = "@@TAGGED@@";
+const TAGGED_INDEX: u32 = 1;
+/// The tags for positive and negative bignums, holding their magnitude as big-endian bytes.
+const BIGPOS: u64 = 2;
+const BIGNEG: u64 = 3;
+
+impl From<Value<'_>> for Cbor {
+    /// Converts a [Value] into CBOR, the way `ciborium` serializes the same data.
+    ///
+    /// Byte arrays, map keys of any type and 128-bit integers convert natively, with integers
+    /// too big for CBOR becoming bignums. An enum variant of the `@@TAG@@` convention, described
+    /// on the conversion from CBOR, becomes a tagged item. The unit type and `None` become null,
+    /// and characters become text. Struct names are dropped, while enums are externally tagged
+    /// by their variant.
+    fn from(value: Value<'_>) -> Self {
+        match value {
+            Value::Unit | Value::Option(None) => Cbor::Null,
+            Value::Bool(v) => Cbor::Bool(v),
+            Value::Number(v) => v.into(),
+            Value::Char(v) => Cbor::Text(v.to_string()),
+            Value::String(v) => Cbor::Text(v.into_owned()),
+            Value::Bytes(v) => Cbor::Bytes(v.into_owned()),
+            Value::Seq(v) | Value::Tuple(v) => array(v),
+            Value::Map(v) => Cbor::Map(
+                v.into_iter()
+                    .map(|(key, value)| (key.into(), value.into()))
+                    .collect(),
+            ),
+            Value::Option(Some(v)) => (*v).into(),
+            Value::Struct(v) => data(v.data),
+            Value::Enum(v) => match captured(*v) {
+                Ok(cbor) => cbor,
+                Err(v) => match v.data {
+                    Data::Unit => Cbor::Text(v.variant.into_owned()),
+                    data => Cbor::Map(vec![(Cbor::Text(v.variant.into_owned()), self::data(data))]),
+                },
+            },
+        }
+    }
+}
+
+/// Converts an enum variant of the [TAG] convention into a tagged item.
+fn captured(variant: Enum<'_>) -> Result<Cbor, Enum<'_>> {
+    if variant.name != TAG || variant.variant != TAGGED {
+        return Err(variant);
+    }
+    match variant.data {
+        Data::Tuple { values } => match <[Value; 2]>::try_from(values) {
+            Ok([Value::Number(Number::U64(tag)), value]) => {
+                Ok(Cbor::Tag(tag, Box::new(value.into())))
+            }
+            Ok(values) => Err(Enum {
+                data: Data::Tuple {
+                    values: values.into(),
+                },
+                ..variant
+            }),
+            Err(values) => Err(Enum {
+                data: Data::Tuple { values },
+                ..variant
+            }),
+        },
+        data => Err(Enum { data, ..variant }),
+    }
+}
+
+impl From<Number> for Cbor {
+    fn from(number: Number) -> Self {
+        match number {
+            Number::I8(v) => Cbor::Integer(v.into()),
+            Number::U8(v) => Cbor::Integer(v.into()),
+            Number::I16(v) => Cbor::Integer(v.into()),
+            Number::U16(v) => Cbor::Integer(v.into()),
+            Number::I32(v) => Cbor::Integer(v.into()),
+            Number::U32(v) => Cbor::Integer(v.into()),
+            Number::I64(v) => Cbor::Integer(v.into()),
+            Number::U64(v) => Cbor::Integer(v.into()),
+            Number::F32(v) => Cbor::Float(v.into()),
+            Number::F64(v) => Cbor::Float(v),
+            Number::I128(v) => match Integer::try_from(v) {
+                Ok(v) => Cbor::Integer(v),
+                Err(_) if v < 0 => bignum(BIGNEG, !v as u128),
+                Err(_) => bignum(BIGPOS, v as u128),
+            },
+            Number::U128(v) => match Integer::try_from(v) {
+                Ok(v) => Cbor::Integer(v),
+                Err(_) => bignum(BIGPOS, v),
+            },
+        }
+    }
+}
+
+/// Creates a bignum with `tag`, holding `magnitude` without leading zero bytes.
+fn bignum(tag: u64, magnitude: u128) -> Cbor {
+    let bytes = magnitude.to_be_bytes();
+    let start = bytes
+        .iter()
+        .position(|&byte| byte != 0)
+        .unwrap_or(bytes.len());
+    Cbor::Tag(tag, Box::new(Cbor::Bytes(bytes[start..].to_vec())))
+}
+
+fn array(values: Vec<Value<'_>>) -> Cbor {
+    Cbor::Array(values.into_iter().map(Cbor::from).collect())
+}
+
+/// Converts the data of a struct or enum variant, without its name.
+fn data(data: Data<'_>) -> Cbor {
+    match data {
+        Data::Unit => Cbor::Null,
+        Data::NewType { value } => value.into(),
+        Data::Tuple { values } => array(values),
+        Data::Struct { fields } => Cbor::Map(
+            fields
+                .into_iter()
+                .map(|(key, value)| (Cbor::Text(key.into_owned()), value.into()))
+                .collect(),
+        ),
+    }
+}
+
+impl TryFrom<Cbor> for Value<'static> {
+    type Error = Error;
+
+    /// Converts CBOR into a [Value].
+    ///
+    /// Null becomes [Value::Unit], and integers the first of [Number::U64], [Number::I64] and
+    /// [Number::I128] that holds them. Bignums that fit become [Number::U128] or
+    /// [Number::I128]. Maps keep keys of any type.
+    ///
+    /// Any other tagged item becomes the `@@TAGGED@@` tuple variant of an enum named `@@TAG@@`,
+    /// holding the tag as a [Number::U64] and the item, which is how `ciborium::tag::Captured`
+    /// serializes it.
+    ///
+    /// `ciborium::Value` may gain more kinds of values in future, which are an error until they
+    /// are supported here.
+    fn try_from(cbor: Cbor) -> Result<Self, Self::Error> {
+        match cbor {
+            Cbor::Integer(v) => Ok(Value::Number(integer(i128::from(v)))),
+            Cbor::Bytes(v) => Ok(Value::Bytes(Cow::Owned(v))),
+            Cbor::Float(v) => Ok(Value::Number(Number::F64(v))),
+            Cbor::Text(v) => Ok(Value::String(Cow::Owned(v))),
+            Cbor::Bool(v) => Ok(Value::Bool(v)),
+            Cbor::Null => Ok(Value::Unit),
+            Cbor::Tag(tag, value) => tagged(tag, *value),
+            Cbor::Array(v) => v
+                .into_iter()
+                .map(Value::try_from)
+                .collect::<Result<_, _>>()
+                .map(Value::Seq),
+            Cbor::Map(v) => v
+                .into_iter()
+                .map(|(key, value)| Ok((key.try_into()?, value.try_into()?)))
+                .collect::<Result<_, Error>>()
+                .map(Value::Map),
+            cbor => Err(Error::custom(format_args!(
+                "unsupported CBOR value {cbor:?}"
+            ))),
+        }
+    }
+}
+
+fn tagged(tag: u64, value: Cbor) -> Result<Value<'static>, Error> {
+    if let Cbor::Bytes(bytes) = &value {
+        match (tag, magnitude(bytes)) {
+            (BIGPOS, Some(v)) => return Ok(Value::Number(Number::U128(v))),
+            (BIGNEG, Some(v)) if v <= i128::MAX as u128 => {
+                // A negative bignum holding `n` stands for `-1 - n`.
+                return Ok(Value::Number(Number::I128(!(v as i128))));
+            }
+            _ => {}
+        }
+    }
+    Ok(Value::Enum(Box::new(Enum {
+        name: Cow::Borrowed(TAG),
+        variant_index: TAGGED_INDEX,
+        variant: Cow::Borrowed(TAGGED),
+        data: Data::Tuple {
+            values: vec![Value::Number(Number::U64(tag)), value.try_into()?],
+        },
+    })))
+}
+
+fn integer(v: i128) -> Number {
+    if let Ok(v) = u64::try_from(v) {
+        Number::U64(v)
+    } else if let Ok(v) = i64::try_from(v) {
+        Number::I64(v)
+    } else {
+        Number::I128(v)
+    }
+}
+
+/// Reads the big-endian magnitude of a bignum, if it fits in a `u128`.
+fn magnitude(bytes: &[u8]) -> Option<u128> {
+    let start = bytes
+        .iter()
+        .position(|&byte| byte != 0)
+        .unwrap_or(bytes.len());
+    let bytes = &bytes[start..];
+    let mut buffer = [0; 16];
+    buffer
+        .get_mut(16usize.checked_sub(bytes.len())?..)?
+        .copy_from_slice(bytes);
+    Some(u128::from_be_bytes(buffer))
+}
@@ -0,0 +1,388 @@
+//! Round-trip tests for third-party serde formats.
+//!
+//! [check] serializes a sample of every [Feature] of the serde data model with a format, reads
+//! it back as the same Rust type, and reports which samples came back intact. Both the samples
+//! and what the format gives back are compared as [Value]s made by [Serializer], so a format
+//! passes for a feature when the Rust type round-trips, whatever the bytes look like.
+//!
+//! The format is given as two closures. The first serializes a [Value], which makes the same
+//! calls into the serializer as the Rust type it was made from. The second deserializes with a
+//! [Seed], which asks the deserializer for the Rust type the way its `Deserialize` implementation
+//! would, so formats that aren't self-describing can be checked too.
+//!
+//! ## Example
+//!
+//! Checking the JSON text of [json](crate::json), read back through [Deserializer](crate::Deserializer):
+//!
+//! ```
+//! # use serde::de::DeserializeSeed;
+//! # use serde_content::conformance::{self, Feature};
+//! # use serde_content::{json, Deserializer};
+//! let report = conformance::check(
+//!     |value| json::to_string(value).map(String::into_bytes),
+//!     |bytes, seed| {
+//!         let text = core::str::from_utf8(bytes).map_err(|e| e.to_string())?;
+//!         let value = json::from_str(text).map_err(|e| e.to_string())?;
+//!         let deserializer = Deserializer::new(value).coerce_numbers();
+//!         seed.deserialize(deserializer).map_err(|e| e.to_string())
+//!     },
+//! );
+//! assert!(report.is_supported(Feature::StructVariant));
+//! assert!(!report.is_supported(Feature::CompoundKeys));
+//! println!("{report}");
+//! ```
+
+use crate::Serializer;
+use crate::Value;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use core::fmt;
+use serde::de;
+use serde::de::Deserialize;
+use serde::de::DeserializeSeed;
+use serde::ser;
+use serde::Serialize;
+
+macro_rules! features {
+    ($(
+        $(#[doc = $doc:literal])*
+        $feature:ident($name:literal): $ty:ty = $sample:expr,
+    )*) => {
+        /// A part of the serde data model, as checked by [check].
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        #[non_exhaustive] // In case we check more features in future.
+        pub enum Feature {
+            $($(#[doc = $doc])* $feature,)*
+        }
+
+        impl Feature {
+            /// Every feature, in the order [check] tries them.
+            pub const ALL: &'static [Feature] = &[$(Feature::$feature,)*];
+
+            /// A short description of the feature, like `"i128"` or `"unit structs"`.
+            pub const fn name(self) -> &'static str {
+                match self {
+                    $(Feature::$feature => $name,)*
+                }
+            }
+
+            // The sample of this feature as a value.
+            fn sample(self) -> Result<Value<'static>, crate::Error> {
+                match self {
+                    $(Feature::$feature => {
+                        let sample: $ty = $sample;
+                        Serializer::new().serialize(sample)
+                    })*
+                }
+            }
+        }
+
+        impl<'de> DeserializeSeed<'de> for Seed {
+            type Value = Value<'static>;
+
+            fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+            where
+                D: de::Deserializer<'de>,
+            {
+                match self.feature {
+                    $(Feature::$feature => {
+                        let found = <$ty>::deserialize(deserializer)?;
+                        Serializer::new().serialize(found).map_err(de::Error::custom)
+                    })*
+                }
+            }
+        }
+    };
+}
+
+features! {
+    /// `bool`.
+    Bool("bool"): bool = true,
+    /// `i8`, `i16`, `i32` and `i64` at their extremes.
+    SignedIntegers("signed integers"): (i8, i16, i32, i64) = (i8::MIN, i16::MIN, i32::MIN, i64::MIN),
+    /// `u8`, `u16`, `u32` and `u64` at their extremes.
+    UnsignedIntegers("unsigned integers"): (u8, u16, u32, u64) = (u8::MAX, u16::MAX, u32::MAX, u64::MAX),
+    /// `i128` beyond the range of `i64`.
+    I128("i128"): i128 = i128::MIN,
+    /// `u128` beyond the range of `u64`.
+    U128("u128"): u128 = u128::MAX,
+    /// `f32` without rounding it through `f64`.
+    F32("f32"): f32 = 0.1,
+    /// `f64` with all its precision.
+    F64("f64"): f64 = 0.1 + 0.2,
+    /// Infinite floats.
+    NonFinite("non-finite floats"): (f64, f64) = (f64::INFINITY, f64::NEG_INFINITY),
+    /// `char` outside ASCII.
+    Char("chars"): char = '🦀',
+    /// Strings with characters that need escaping in text formats.
+    String("strings"): String = "\"quoted\"\n\t\\ ünïcödé \u{0}".to_string(),
+    /// Byte arrays, serialized with `serialize_bytes`.
+    Bytes("bytes"): Bytes = Bytes(alloc::vec![0, 1, 0xfe, 0xff]),
+    /// `()`.
+    Unit("unit"): () = (),
+    /// `Option` that is `None`.
+    None("none"): Option<u8> = None,
+    /// `Option` that is `Some`.
+    Some("some"): Option<u8> = Some(1),
+    /// `Option` nested in an `Option`, like `Some(None)`.
+    NestedOptions("nested options"): Option<Option<u8>> = Some(None),
+    /// Structs without fields, like `struct Unit;`.
+    UnitStruct("unit structs"): UnitStruct = UnitStruct,
+    /// Structs wrapping one value, like `struct Meters(u32);`.
+    NewtypeStruct("newtype structs"): NewtypeStruct = NewtypeStruct(1),
+    /// Structs with unnamed fields, like `struct Pair(u8, String);`.
+    TupleStruct("tuple structs"): TupleStruct = TupleStruct(1, "two".to_string()),
+    /// Structs with named fields.
+    Struct("structs"): Struct = Struct { x: -1, y: None },
+    /// Enum variants without data.
+    UnitVariant("unit variants"): Enum = Enum::Unit,
+    /// Enum variants wrapping one value.
+    NewtypeVariant("newtype variants"): Enum = Enum::Newtype(1),
+    /// Enum variants with unnamed fields.
+    TupleVariant("tuple variants"): Enum = Enum::Tuple(1, 2),
+    /// Enum variants with named fields.
+    StructVariant("struct variants"): Enum = Enum::Struct { x: 1 },
+    /// Sequences, like `Vec`.
+    Seq("sequences"): Vec<u8> = alloc::vec![1, 2, 3],
+    /// Empty sequences.
+    EmptySeq("empty sequences"): Vec<u8> = Vec::new(),
+    /// Tuples of different types.
+    Tuple("tuples"): (u8, String, bool) = (1, "two".to_string(), true),
+    /// Maps with string keys.
+    Map("maps"): BTreeMap<String, u8> = BTreeMap::from([("a".to_string(), 1), ("b".to_string(), 2)]),
+    /// Maps with integer keys.
+    IntegerKeys("integer map keys"): BTreeMap<i32, u8> = BTreeMap::from([(-1, 1), (2, 2)]),
+    /// Maps with keys that aren't strings or integers.
+    CompoundKeys("compound map keys"): BTreeMap<(u8, u8), u8> = BTreeMap::from([((1, 2), 3)]),
+    /// Sequences and maps nested in each other.
+    Nested("nested collections"): Vec<BTreeMap<String, Vec<u8>>> = alloc::vec![BTreeMap::from([("a".to_string(), alloc::vec![1])])],
+}
+
+impl fmt::Display for Feature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+struct UnitStruct;
+
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+struct NewtypeStruct(u32);
+
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+struct TupleStruct(u8, String);
+
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+struct Struct {
+    x: i32,
+    y: Option<u8>,
+}
+
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+enum Enum {
+    Unit,
+    Newtype(u32),
+    Tuple(u8, u8),
+    Struct { x: u8 },
+}
+
+// A byte array, serialized with `serialize_bytes` like `serde_bytes` does.
+#[derive(Debug, PartialEq)]
+struct Bytes(Vec<u8>);
+
+impl Serialize for Bytes {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        serializer.serialize_bytes(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for Bytes {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = Bytes;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a byte array")
+            }
+
+            fn visit_bytes<E>(self, v: &[u8]) -> Result<Bytes, E>
+            where
+                E: de::Error,
+            {
+                Ok(Bytes(v.to_vec()))
+            }
+
+            // Formats without byte arrays write them as sequences of integers.
+            fn visit_seq<A>(self, mut seq: A) -> Result<Bytes, A::Error>
+            where
+                A: de::SeqAccess<'de>,
+            {
+                let mut bytes = Vec::new();
+                while let Some(byte) = seq.next_element()? {
+                    bytes.push(byte);
+                }
+                Ok(Bytes(bytes))
+            }
+        }
+
+        deserializer.deserialize_bytes(Visitor)
+    }
+}
+
+/// Deserializes the sample of a [Feature] back into a [Value].
+///
+/// Pass this to the deserializer of the format being checked, with
+/// [DeserializeSeed::deserialize]. It deserializes the Rust type the sample was serialized from,
+/// and turns it into a value with [Serializer] to compare with the sample.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Seed {
+    feature: Feature,
+}
+
+impl Seed {
+    /// The feature being deserialized.
+    pub const fn feature(self) -> Feature {
+        self.feature
+    }
+}
+
+/// Why a [Feature] didn't survive the round-trip.
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub enum Failure {
+    /// The format failed to serialize the sample.
+    Serialize(String),
+    /// The format failed to deserialize what it serialized.
+    Deserialize(String),
+    /// The sample came back different.
+    Mismatch {
+        /// The sample.
+        expected: Value<'static>,
+        /// What came back.
+        found: Value<'static>,
+    },
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Failure::Serialize(error) => write!(f, "serializing: {error}"),
+            Failure::Deserialize(error) => write!(f, "deserializing: {error}"),
+            Failure::Mismatch { expected, found } => {
+                write!(f, "expected {expected:?}, found {found:?}")
+            }
+        }
+    }
+}
+
+/// Which [Feature]s survive a round-trip through a format.
+///
+/// Its [Display](fmt::Display) lists every feature on its own line, with why it failed if it did.
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct Report {
+    outcomes: Vec<(Feature, Result<(), Failure>)>,
+}
+
+impl Report {
+    /// Every feature checked with its outcome, in the order of [Feature::ALL].
+    pub fn iter(&self) -> impl Iterator<Item = (Feature, &Result<(), Failure>)> {
+        self.outcomes
+            .iter()
+            .map(|(feature, outcome)| (*feature, outcome))
+    }
+
+    /// The features that survived the round-trip.
+    pub fn supported(&self) -> impl Iterator<Item = Feature> + '_ {
+        self.iter()
+            .filter(|(_, outcome)| outcome.is_ok())
+            .map(|(feature, _)| feature)
+    }
+
+    /// The features that didn't survive the round-trip, with why.
+    pub fn unsupported(&self) -> impl Iterator<Item = (Feature, &Failure)> {
+        self.iter()
+            .filter_map(|(feature, outcome)| Some((feature, outcome.as_ref().err()?)))
+    }
+
+    /// Whether `feature` survived the round-trip.
+    pub fn is_supported(&self, feature: Feature) -> bool {
+        self.iter()
+            .any(|(found, outcome)| found == feature && outcome.is_ok())
+    }
+
+    /// Why `feature` didn't survive the round-trip, if it didn't.
+    pub fn failure(&self, feature: Feature) -> Option<&Failure> {
+        self.unsupported()
+            .find(|(found, _)| *found == feature)
+            .map(|(_, failure)| failure)
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (feature, outcome) in self.iter() {
+            match outcome {
+                Ok(()) => writeln!(f, "ok   {feature}")?,
+                Err(failure) => writeln!(f, "FAIL {feature}: {failure}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Round-trips the sample of every [Feature] through a format and reports which survive.
+///
+/// `serialize` writes a value with the format. `deserialize` reads the bytes back by passing
+/// the [Seed] it is given to the deserializer of the format, like
+/// `seed.deserialize(&mut serde_json::Deserializer::from_slice(bytes))`. Errors of either only
+/// need to implement [Display](fmt::Display).
+pub fn check<S, D, SE, DE>(mut serialize: S, mut deserialize: D) -> Report
+where
+    S: FnMut(&Value<'static>) -> Result<Vec<u8>, SE>,
+    D: FnMut(&[u8], Seed) -> Result<Value<'static>, DE>,
+    SE: fmt::Display,
+    DE: fmt::Display,
+{
+    let outcomes = Feature::ALL
+        .iter()
+        .map(|&feature| {
+            let outcome = roundtrip(feature, &mut serialize, &mut deserialize);
+            (feature, outcome)
+        })
+        .collect();
+    Report { outcomes }
+}
+
+fn roundtrip<S, D, SE, DE>(
+    feature: Feature,
+    serialize: &mut S,
+    deserialize: &mut D,
+) -> Result<(), Failure>
+where
+    S: FnMut(&Value<'static>) -> Result<Vec<u8>, SE>,
+    D: FnMut(&[u8], Seed) -> Result<Value<'static>, DE>,
+    SE: fmt::Display,
+    DE: fmt::Display,
+{
+    // Samples are plain Rust values, which `Serializer` never fails on.
+    let expected = feature
+        .sample()
+        .map_err(|error| Failure::Serialize(error.to_string()))?;
+    let bytes = serialize(&expected).map_err(|error| Failure::Serialize(error.to_string()))?;
+    let found = deserialize(&bytes, Seed { feature })
+        .map_err(|error| Failure::Deserialize(error.to_string()))?;
+    match found == expected {
+        true => Ok(()),
+        false => Err(Failure::Mismatch { expected, found }),
+    }
+}
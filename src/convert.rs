@@ -0,0 +1,237 @@
+use crate::Error;
+use crate::Expected;
+use crate::Number;
+use crate::Value;
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::iter::FromIterator;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+impl<'a, T> From<Option<T>> for Value<'a>
+where
+    T: Into<Value<'a>>,
+{
+    fn from(value: Option<T>) -> Self {
+        Self::Option(value.map(|v| Box::new(v.into())))
+    }
+}
+
+// `Vec<u8>` and `&[u8]` are byte arrays, so sequences are implemented per element type
+// rather than for any `T: Into<Value>`.
+macro_rules! seq {
+    ($($ty:ty),+ $(,)?) => {
+        $(
+            impl<'a> From<Vec<$ty>> for Value<'a> {
+                fn from(value: Vec<$ty>) -> Self {
+                    Self::Seq(value.into_iter().map(Into::into).collect())
+                }
+            }
+
+            impl<'a> From<&[$ty]> for Value<'a> {
+                fn from(value: &[$ty]) -> Self {
+                    Self::Seq(value.iter().cloned().map(Into::into).collect())
+                }
+            }
+        )+
+    };
+}
+
+seq! {
+    bool, i8, i16, u16, i32, u32, f32, i64, u64, f64, i128, u128, char, String,
+    &'a str, Cow<'a, str>, Number, Value<'a>,
+}
+
+macro_rules! tuple {
+    ($($ty:ident $index:tt),+) => {
+        impl<'a, $($ty),+> From<($($ty,)+)> for Value<'a>
+        where
+            $($ty: Into<Value<'a>>,)+
+        {
+            fn from(value: ($($ty,)+)) -> Self {
+                Self::Tuple(alloc::vec![$(value.$index.into()),+])
+            }
+        }
+    };
+}
+
+tuple!(T0 0);
+tuple!(T0 0, T1 1);
+tuple!(T0 0, T1 1, T2 2);
+tuple!(T0 0, T1 1, T2 2, T3 3);
+tuple!(T0 0, T1 1, T2 2, T3 3, T4 4);
+tuple!(T0 0, T1 1, T2 2, T3 3, T4 4, T5 5);
+tuple!(T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6);
+tuple!(T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7);
+tuple!(T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8);
+tuple!(T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8, T9 9);
+tuple!(T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8, T9 9, T10 10);
+tuple!(T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8, T9 9, T10 10, T11 11);
+tuple!(T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8, T9 9, T10 10, T11 11, T12 12);
+tuple!(T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8, T9 9, T10 10, T11 11, T12 12, T13 13);
+tuple!(T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8, T9 9, T10 10, T11 11, T12 12, T13 13, T14 14);
+tuple!(T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8, T9 9, T10 10, T11 11, T12 12, T13 13, T14 14, T15 15);
+
+impl<'a, K, V> From<BTreeMap<K, V>> for Value<'a>
+where
+    K: Into<Value<'a>>,
+    V: Into<Value<'a>>,
+{
+    fn from(value: BTreeMap<K, V>) -> Self {
+        value.into_iter().collect()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a, K, V, S> From<HashMap<K, V, S>> for Value<'a>
+where
+    K: Into<Value<'a>>,
+    V: Into<Value<'a>>,
+{
+    fn from(value: HashMap<K, V, S>) -> Self {
+        value.into_iter().collect()
+    }
+}
+
+/// Collects values into a [Value::Seq].
+impl<'a> FromIterator<Value<'a>> for Value<'a> {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = Value<'a>>,
+    {
+        Self::Seq(iter.into_iter().collect())
+    }
+}
+
+/// Collects key-value pairs into a [Value::Map].
+impl<'a, K, V> FromIterator<(K, V)> for Value<'a>
+where
+    K: Into<Value<'a>>,
+    V: Into<Value<'a>>,
+{
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+    {
+        Self::Map(
+            iter.into_iter()
+                .map(|(key, value)| (key.into(), value.into()))
+                .collect(),
+        )
+    }
+}
+
+impl TryFrom<Value<'_>> for () {
+    type Error = Error;
+
+    fn try_from(value: Value<'_>) -> Result<Self, Self::Error> {
+        match value {
+            Value::Unit => Ok(()),
+            value => Err(Error::unexpected(value.into_found(), Expected::Unit)),
+        }
+    }
+}
+
+impl TryFrom<Value<'_>> for bool {
+    type Error = Error;
+
+    fn try_from(value: Value<'_>) -> Result<Self, Self::Error> {
+        match value {
+            Value::Bool(v) => Ok(v),
+            value => Err(Error::unexpected(value.into_found(), Expected::Bool)),
+        }
+    }
+}
+
+impl TryFrom<Value<'_>> for char {
+    type Error = Error;
+
+    fn try_from(value: Value<'_>) -> Result<Self, Self::Error> {
+        match value {
+            Value::Char(v) => Ok(v),
+            value => Err(Error::unexpected(value.into_found(), Expected::Char)),
+        }
+    }
+}
+
+impl TryFrom<Value<'_>> for Number {
+    type Error = Error;
+
+    fn try_from(value: Value<'_>) -> Result<Self, Self::Error> {
+        match value {
+            Value::Number(v) => Ok(v),
+            value => Err(Error::unexpected(value.into_found(), Expected::Number)),
+        }
+    }
+}
+
+macro_rules! number {
+    ($($ty:ty => $variant:ident),+ $(,)?) => {
+        $(
+            impl TryFrom<Value<'_>> for $ty {
+                type Error = Error;
+
+                fn try_from(value: Value<'_>) -> Result<Self, Self::Error> {
+                    match value {
+                        Value::Number(Number::$variant(v)) => Ok(v),
+                        value => Err(Error::unexpected(value.into_found(), Expected::$variant)),
+                    }
+                }
+            }
+        )+
+    };
+}
+
+number! {
+    i8 => I8, u8 => U8, i16 => I16, u16 => U16, i32 => I32, u32 => U32, f32 => F32,
+    i64 => I64, u64 => U64, f64 => F64, i128 => I128, u128 => U128,
+}
+
+impl TryFrom<Value<'_>> for String {
+    type Error = Error;
+
+    fn try_from(value: Value<'_>) -> Result<Self, Self::Error> {
+        match value {
+            Value::String(v) => Ok(v.into_owned()),
+            value => Err(Error::unexpected(value.into_found(), Expected::String)),
+        }
+    }
+}
+
+impl TryFrom<Value<'_>> for Vec<u8> {
+    type Error = Error;
+
+    fn try_from(value: Value<'_>) -> Result<Self, Self::Error> {
+        match value {
+            Value::Bytes(v) => Ok(v.into_owned()),
+            value => Err(Error::unexpected(value.into_found(), Expected::Bytes)),
+        }
+    }
+}
+
+impl<'a> TryFrom<Value<'a>> for Vec<Value<'a>> {
+    type Error = Error;
+
+    fn try_from(value: Value<'a>) -> Result<Self, Self::Error> {
+        match value {
+            Value::Seq(v) => Ok(v),
+            value => Err(Error::unexpected(value.into_found(), Expected::Seq)),
+        }
+    }
+}
+
+impl<'a> Value<'a> {
+    /// Returns the inner value of a [Value::Option].
+    ///
+    /// This is the equivalent of `TryFrom<Value>` for `Option<Value>`, which can't be implemented
+    /// because `Option<T>` already implements `From<T>`.
+    pub fn try_into_option(self) -> Result<Option<Value<'a>>, Error> {
+        match self {
+            Value::Option(v) => Ok(v.map(|v| *v)),
+            value => Err(Error::unexpected(value.into_found(), Expected::Option)),
+        }
+    }
+}
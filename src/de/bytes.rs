@@ -0,0 +1,34 @@
+use crate::Number;
+use crate::Value;
+use alloc::vec::Vec;
+
+/// Converts a sequence of integers into bytes, returning `None` if any of them is not a byte.
+pub(super) fn from_seq(values: &[Value<'_>]) -> Option<Vec<u8>> {
+    values
+        .iter()
+        .map(|value| match *value {
+            Value::Number(number) => match number {
+                Number::I8(v) => u8::try_from(v).ok(),
+                Number::U8(v) => Some(v),
+                Number::I16(v) => u8::try_from(v).ok(),
+                Number::U16(v) => u8::try_from(v).ok(),
+                Number::I32(v) => u8::try_from(v).ok(),
+                Number::U32(v) => u8::try_from(v).ok(),
+                Number::I64(v) => u8::try_from(v).ok(),
+                Number::U64(v) => u8::try_from(v).ok(),
+                Number::I128(v) => u8::try_from(v).ok(),
+                Number::U128(v) => u8::try_from(v).ok(),
+                Number::F32(_) | Number::F64(_) => None,
+            },
+            _ => None,
+        })
+        .collect()
+}
+
+/// Converts bytes into a sequence of `u8` numbers.
+pub(super) fn to_seq(bytes: &[u8]) -> Vec<Value<'static>> {
+    bytes
+        .iter()
+        .map(|byte| Value::Number(Number::U8(*byte)))
+        .collect()
+}
@@ -0,0 +1,126 @@
+use crate::path;
+use crate::path::Segment;
+use crate::Error;
+use crate::Errors;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::cell::RefCell;
+
+/// Collects the errors found by [Deserializer::deserialize_all](crate::Deserializer::deserialize_all).
+#[derive(Debug, Default, PartialEq, PartialOrd)]
+pub(super) struct Collector {
+    // The path of the value being deserialized.
+    path: RefCell<Vec<Segment>>,
+    errors: RefCell<Vec<(String, Error)>>,
+    // The path of the error that stopped deserialization, if any.
+    fatal: RefCell<Option<String>>,
+    // The enum variants that default values were built with and that turned out to hold data.
+    variants: RefCell<Vec<Known>>,
+}
+
+/// An enum variant that holds data.
+#[derive(Debug, PartialEq, PartialOrd)]
+struct Known {
+    variants: &'static [&'static str],
+    index: usize,
+    // In which order a default value was built with the variant, if one was.
+    built: Option<usize>,
+}
+
+impl Known {
+    // Enums are told apart by their variant names, for the same reason as in
+    // `Skipped::contains`.
+    fn is(&self, variants: &'static [&'static str], index: usize) -> bool {
+        self.variants == variants && self.index == index
+    }
+}
+
+impl Collector {
+    pub(super) const fn new() -> Self {
+        Self {
+            path: RefCell::new(Vec::new()),
+            errors: RefCell::new(Vec::new()),
+            fatal: RefCell::new(None),
+            variants: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Runs `f` with `segment` appended to the current path.
+    pub(super) fn nested<T>(
+        &self,
+        segment: Segment,
+        f: impl FnOnce() -> Result<T, Error>,
+    ) -> Result<T, Error> {
+        self.path.borrow_mut().push(segment);
+        let result = f();
+        if result.is_err() {
+            let mut fatal = self.fatal.borrow_mut();
+            // The innermost value an error passes through is where it was found.
+            if fatal.is_none() {
+                *fatal = Some(path::join(&self.path.borrow()));
+            }
+        }
+        self.path.borrow_mut().pop();
+        result
+    }
+
+    /// Records an error found at the current path.
+    pub(super) fn record(&self, error: Error) {
+        let path = path::join(&self.path.borrow());
+        self.errors.borrow_mut().push((path, error));
+    }
+
+    /// Returns the index of the variant of the enum with `variants` to build a default value with.
+    ///
+    /// That is the first variant not known to hold data, or else the one that a default value
+    /// was built with first, which is the least likely to recurse, or else the first variant.
+    pub(super) fn fallback_variant(&self, variants: &'static [&'static str]) -> usize {
+        let known = self.variants.borrow();
+        let state = |index| {
+            known
+                .iter()
+                .find(|known| known.is(variants, index))
+                .map(|known| known.built)
+        };
+        let indices = 0..variants.len();
+        indices
+            .clone()
+            .find(|index| state(*index).is_none())
+            .or_else(|| {
+                let built = indices.filter_map(|index| Some((state(index).flatten()?, index)));
+                built.min().map(|(_, index)| index)
+            })
+            .unwrap_or(0)
+    }
+
+    /// Notes that the variant at `index` of the enum with `variants` holds data, and whether a
+    /// default value was `built` with it.
+    pub(super) fn fallback_holds_data(
+        &self,
+        variants: &'static [&'static str],
+        index: usize,
+        built: bool,
+    ) {
+        let mut known = self.variants.borrow_mut();
+        let order = built.then(|| known.iter().filter(|k| k.built.is_some()).count());
+        match known.iter_mut().find(|known| known.is(variants, index)) {
+            Some(known) => known.built = known.built.or(order),
+            None => known.push(Known {
+                variants,
+                index,
+                built: order,
+            }),
+        }
+    }
+
+    /// Returns the deserialized value if no errors were found, or all the errors otherwise.
+    pub(super) fn finish<T>(self, result: Result<T, Error>) -> Result<T, Errors> {
+        let mut errors = self.errors.into_inner();
+        match result {
+            Ok(value) if errors.is_empty() => return Ok(value),
+            Ok(_) => {}
+            Err(error) => errors.push((self.fatal.into_inner().unwrap_or_default(), error)),
+        }
+        Err(Errors::new(errors))
+    }
+}
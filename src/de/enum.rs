@@ -1,12 +1,19 @@
+use crate::de::check_fields;
 use crate::de::error::Unexpected;
+use crate::de::field;
 use crate::de::identifier::Identifier;
+use crate::de::key;
 use crate::de::Map;
+use crate::de::Options;
 use crate::de::Seq;
+use crate::de::ValueDeserializer;
+use crate::path::Segment;
 use crate::Data;
 use crate::DataType;
 use crate::Enum;
 use crate::Error;
 use crate::Expected;
+use crate::Number;
 use crate::Value;
 use alloc::borrow::Cow;
 use alloc::borrow::ToOwned;
@@ -25,15 +32,14 @@ impl<'de> serde::de::IntoDeserializer<'de, Error> for Enum<'de> {
     }
 }
 
-pub(super) struct Deserializer<'de> {
+pub(super) struct Deserializer<'de, 'c> {
     // The name of the enum we are expecting
     expected: Cow<'static, str>,
     enum_box: Box<Enum<'de>>,
-    human_readable: bool,
-    coerce_numbers: bool,
+    options: Options<'c>,
 }
 
-impl<'de> de::EnumAccess<'de> for Deserializer<'de> {
+impl<'de, 'c> de::EnumAccess<'de> for Deserializer<'de, 'c> {
     type Error = Error;
     type Variant = Self;
 
@@ -49,7 +55,15 @@ impl<'de> de::EnumAccess<'de> for Deserializer<'de> {
     }
 }
 
-impl<'de> de::VariantAccess<'de> for Deserializer<'de> {
+impl Deserializer<'_, '_> {
+    /// Returns the path segment of the variant data, if it's needed.
+    fn segment(&self) -> Option<Segment> {
+        self.options
+            .segment(|| Segment::Variant(self.enum_box.variant.clone()))
+    }
+}
+
+impl<'de, 'c> de::VariantAccess<'de> for Deserializer<'de, 'c> {
     type Error = Error;
 
     fn unit_variant(self) -> Result<(), Self::Error> {
@@ -66,14 +80,15 @@ impl<'de> de::VariantAccess<'de> for Deserializer<'de> {
     where
         T: de::DeserializeSeed<'de>,
     {
+        let segment = self.segment();
         match self.enum_box.data {
             Data::NewType { value } => {
-                let deserializer = crate::Deserializer {
+                let deserializer = ValueDeserializer {
                     value,
-                    human_readable: self.human_readable,
-                    coerce_numbers: self.coerce_numbers,
+                    options: self.options,
                 };
-                seed.deserialize(deserializer)
+                self.options
+                    .nested(segment, || seed.deserialize(deserializer))
             }
             _ => Err(self.enum_box.unexpected(Expected::Enum {
                 name: Some(self.expected.into_owned()),
@@ -82,13 +97,18 @@ impl<'de> de::VariantAccess<'de> for Deserializer<'de> {
         }
     }
 
-    fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    fn tuple_variant<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
+        let segment = self.segment();
         match self.enum_box.data {
+            Data::Tuple { values } if self.options.config.strict && values.len() != len => {
+                Err(Value::Tuple(values).unexpected(Expected::Tuple(len)))
+            }
             Data::Tuple { values } => {
-                visitor.visit_seq(Seq::new(values, self.human_readable, self.coerce_numbers))
+                let seq = Seq::new(values, self.options);
+                self.options.nested(segment, || visitor.visit_seq(seq))
             }
             _ => Err(self.enum_box.unexpected(Expected::Enum {
                 name: Some(self.expected.into_owned()),
@@ -99,18 +119,29 @@ impl<'de> de::VariantAccess<'de> for Deserializer<'de> {
 
     fn struct_variant<V>(
         self,
-        _fields: &'static [&'static str],
+        fields: &'static [&'static str],
         visitor: V,
     ) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
+        let segment = self.segment();
         match self.enum_box.data {
-            Data::Struct { fields } => visitor.visit_map(Map::from((
-                fields,
-                self.human_readable,
-                self.coerce_numbers,
-            ))),
+            Data::Struct { fields: values } => {
+                let values = self.options.fields(values, fields);
+                if self.options.config.strict {
+                    if let Err(error) = check_fields(&values, fields) {
+                        self.options.report(error)?;
+                    }
+                }
+                let missing = self
+                    .options
+                    .missing(values.iter().filter_map(field), fields);
+                let map = Map::from((values, self.options)).missing(missing);
+                self.options
+                    .nested(segment, || visitor.visit_map(map))
+                    .map_err(|error| error.in_struct(&self.expected))
+            }
             _ => Err(self.enum_box.unexpected(Expected::Enum {
                 name: Some(self.expected.into_owned()),
                 typ: Some(DataType::Struct),
@@ -119,11 +150,10 @@ impl<'de> de::VariantAccess<'de> for Deserializer<'de> {
     }
 }
 
-pub(super) fn visit_enum<'de, V>(
+pub(super) fn visit_enum<'de, 'c, V>(
     expected: Cow<'static, str>,
     enum_box: Box<Enum<'de>>,
-    human_readable: bool,
-    coerce_numbers: bool,
+    options: Options<'c>,
     visitor: V,
 ) -> Result<V::Value, Error>
 where
@@ -132,33 +162,101 @@ where
     let deserializer = Deserializer {
         expected,
         enum_box,
-        human_readable,
-        coerce_numbers,
+        options,
     };
     visitor.visit_enum(deserializer)
 }
 
-pub(super) struct Access<'de> {
+/// Converts a number into a field or variant index.
+pub(super) fn index(number: Number) -> Option<usize> {
+    match number {
+        Number::I8(v) => usize::try_from(v).ok(),
+        Number::U8(v) => Some(v as usize),
+        Number::I16(v) => usize::try_from(v).ok(),
+        Number::U16(v) => Some(v as usize),
+        Number::I32(v) => usize::try_from(v).ok(),
+        Number::U32(v) => usize::try_from(v).ok(),
+        Number::I64(v) => usize::try_from(v).ok(),
+        Number::U64(v) => usize::try_from(v).ok(),
+        Number::I128(v) => usize::try_from(v).ok(),
+        Number::U128(v) => usize::try_from(v).ok(),
+        Number::F32(_) | Number::F64(_) => None,
+    }
+}
+
+/// Resolves a variant given by its index in `variants` into the name of the variant.
+///
+/// Variants given in any other way are returned as is.
+pub(super) fn variant_name<'de>(
+    variant: Value<'de>,
+    name: &'static str,
+    variants: &'static [&'static str],
+) -> Result<Value<'de>, Error> {
+    let index = match variant {
+        Value::Number(number) => index(number),
+        variant => return Ok(variant),
+    };
+    match index.and_then(|index| variants.get(index)) {
+        Some(variant) => Ok(Value::String(Cow::Borrowed(variant))),
+        None => Err(variant.unexpected(Expected::Enum {
+            name: Some(name.to_owned()),
+            typ: None,
+        })),
+    }
+}
+
+/// Whether the keys of a map or struct are those of an adjacently tagged enum.
+pub(super) fn is_adjacent<'k>(
+    mut keys: impl Iterator<Item = Option<&'k str>>,
+    tag: &str,
+    content: &str,
+) -> bool {
+    match [keys.next(), keys.next(), keys.next()] {
+        [Some(Some(t)), None, None] => t == tag,
+        [Some(Some(a)), Some(Some(b)), None] => {
+            (a == tag && b == content) || (a == content && b == tag)
+        }
+        _ => false,
+    }
+}
+
+/// Splits the entries of an adjacently tagged enum into its variant and data.
+///
+/// Each entry is flagged with whether it's the tag.
+pub(super) fn split_adjacent<'de>(
+    entries: impl Iterator<Item = (bool, Value<'de>)>,
+) -> (Value<'de>, Option<Value<'de>>) {
+    let mut variant = Value::Unit;
+    let mut data = None;
+    for (is_tag, value) in entries {
+        match is_tag {
+            true => variant = value,
+            false => data = Some(value),
+        }
+    }
+    (variant, data)
+}
+
+pub(super) struct Access<'de, 'c> {
     // The name of the enum we are expecting
     pub(super) expected: &'static str,
     pub(super) name: Value<'de>,
     pub(super) data: Option<Value<'de>>,
-    pub(super) human_readable: bool,
-    pub(super) coerce_numbers: bool,
+    pub(super) options: Options<'c>,
 }
 
-impl<'de> de::EnumAccess<'de> for Access<'de> {
+impl<'de, 'c> de::EnumAccess<'de> for Access<'de, 'c> {
     type Error = Error;
-    type Variant = VariantAccess<'de>;
+    type Variant = VariantAccess<'de, 'c>;
 
     fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant), Self::Error>
     where
         V: de::DeserializeSeed<'de>,
     {
-        let deserializer = crate::Deserializer {
+        let segment = self.options.segment(|| Segment::key(&self.name));
+        let deserializer = ValueDeserializer {
             value: self.name,
-            human_readable: self.human_readable,
-            coerce_numbers: self.coerce_numbers,
+            options: self.options,
         };
         seed.deserialize(deserializer).map(|v| {
             (
@@ -166,23 +264,24 @@ impl<'de> de::EnumAccess<'de> for Access<'de> {
                 VariantAccess {
                     expected: self.expected,
                     data: self.data,
-                    human_readable: self.human_readable,
-                    coerce_numbers: self.coerce_numbers,
+                    segment,
+                    options: self.options,
                 },
             )
         })
     }
 }
 
-pub(super) struct VariantAccess<'de> {
+pub(super) struct VariantAccess<'de, 'c> {
     // The name of the enum we are expecting
     expected: &'static str,
     data: Option<Value<'de>>,
-    human_readable: bool,
-    coerce_numbers: bool,
+    // The path segment of the variant data, if it's needed.
+    segment: Option<Segment>,
+    options: Options<'c>,
 }
 
-impl<'de> de::VariantAccess<'de> for VariantAccess<'de> {
+impl<'de, 'c> de::VariantAccess<'de> for VariantAccess<'de, 'c> {
     type Error = Error;
 
     fn unit_variant(self) -> Result<(), Self::Error> {
@@ -201,12 +300,12 @@ impl<'de> de::VariantAccess<'de> for VariantAccess<'de> {
     {
         match self.data {
             Some(value) => {
-                let deserializer = crate::Deserializer {
+                let deserializer = ValueDeserializer {
                     value,
-                    human_readable: self.human_readable,
-                    coerce_numbers: self.coerce_numbers,
+                    options: self.options,
                 };
-                seed.deserialize(deserializer)
+                self.options
+                    .nested(self.segment, || seed.deserialize(deserializer))
             }
             None => Err(Value::Unit.unexpected(Expected::Enum {
                 name: Some(self.expected.to_owned()),
@@ -221,12 +320,12 @@ impl<'de> de::VariantAccess<'de> for VariantAccess<'de> {
     {
         match self.data {
             Some(Value::Seq(seq)) => {
-                let deserializer = crate::Deserializer {
+                let deserializer = ValueDeserializer {
                     value: Value::Seq(seq),
-                    human_readable: self.human_readable,
-                    coerce_numbers: self.coerce_numbers,
+                    options: self.options,
                 };
-                deserializer.deserialize_seq(visitor)
+                self.options
+                    .nested(self.segment, || deserializer.deserialize_seq(visitor))
             }
             Some(v) => Err(v.unexpected(Expected::Enum {
                 name: Some(self.expected.to_owned()),
@@ -241,7 +340,7 @@ impl<'de> de::VariantAccess<'de> for VariantAccess<'de> {
 
     fn struct_variant<V>(
         self,
-        _fields: &'static [&'static str],
+        fields: &'static [&'static str],
         visitor: V,
     ) -> Result<V::Value, Self::Error>
     where
@@ -249,12 +348,12 @@ impl<'de> de::VariantAccess<'de> for VariantAccess<'de> {
     {
         match self.data {
             Some(Value::Map(map)) => {
-                let deserializer = crate::Deserializer {
-                    value: Value::Map(map),
-                    human_readable: self.human_readable,
-                    coerce_numbers: self.coerce_numbers,
-                };
-                deserializer.deserialize_map(visitor)
+                let map = self.options.keys(map, fields);
+                let missing = self.options.missing(map.iter().filter_map(key), fields);
+                let map = Map::from((map, self.options)).missing(missing);
+                self.options
+                    .nested(self.segment, || visitor.visit_map(map))
+                    .map_err(|error| error.in_struct(self.expected))
             }
             Some(v) => Err(v.unexpected(Expected::Enum {
                 name: Some(self.expected.to_owned()),
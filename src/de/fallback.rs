@@ -0,0 +1,446 @@
+use crate::de::collect::Collector;
+use crate::de::identifier::Identifier;
+use crate::Error;
+use alloc::borrow::Cow;
+use serde::de;
+use serde::de::Deserializer;
+use serde::de::Visitor;
+
+/// Deserializes a default value in place of one that failed to deserialize.
+///
+/// Numbers are zero, strings and byte arrays are empty, options are `None`, sequences and maps
+/// are empty, tuples and struct fields are filled with default values and enums take their
+/// first variant that isn't known to hold data. Which variants hold data is only found out as
+/// they are taken, so recursive enums end in a unit variant, or one that doesn't recurse, if
+/// they have one. Default values nested more than [MAX_DEPTH] levels deep are an error.
+#[derive(Clone, Copy)]
+pub(super) struct Fallback<'c> {
+    collector: Option<&'c Collector>,
+    depth: usize,
+}
+
+/// How deeply default values can be nested.
+const MAX_DEPTH: usize = 64;
+
+impl<'c> Fallback<'c> {
+    pub(super) const fn new(collector: Option<&'c Collector>) -> Self {
+        Self {
+            collector,
+            depth: 0,
+        }
+    }
+
+    /// Returns a fallback for the values nested in this one.
+    fn nested(&self) -> Result<Self, Error> {
+        if self.depth == MAX_DEPTH {
+            return Err(Error::custom(
+                "default values are nested too deeply, the type may be recursive",
+            ));
+        }
+        Ok(Self {
+            collector: self.collector,
+            depth: self.depth + 1,
+        })
+    }
+}
+
+impl<'de> Deserializer<'de> for Fallback<'_> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_bool<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_bool(false)
+    }
+
+    fn deserialize_i8<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_i8(0)
+    }
+
+    fn deserialize_i16<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_i16(0)
+    }
+
+    fn deserialize_i32<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_i32(0)
+    }
+
+    fn deserialize_i64<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_i64(0)
+    }
+
+    fn deserialize_i128<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_i128(0)
+    }
+
+    fn deserialize_u8<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_u8(0)
+    }
+
+    fn deserialize_u16<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_u16(0)
+    }
+
+    fn deserialize_u32<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_u32(0)
+    }
+
+    fn deserialize_u64<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_u64(0)
+    }
+
+    fn deserialize_u128<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_u128(0)
+    }
+
+    fn deserialize_f32<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_f32(0.0)
+    }
+
+    fn deserialize_f64<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_f64(0.0)
+    }
+
+    fn deserialize_char<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_char('\0')
+    }
+
+    fn deserialize_str<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_borrowed_str("")
+    }
+
+    fn deserialize_string<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_borrowed_bytes(&[])
+    }
+
+    fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_none()
+    }
+
+    fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self.nested()?)
+    }
+
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_seq(Elements::new(self.nested()?, 0))
+    }
+
+    fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_seq(Elements::new(self.nested()?, len))
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_seq(Elements::new(self.nested()?, len))
+    }
+
+    fn deserialize_map<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_map(Fields::new(self.nested()?, &[]))
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_map(Fields::new(self.nested()?, fields))
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        let index = match self.collector {
+            Some(collector) => collector.fallback_variant(variants),
+            None => 0,
+        };
+        visitor.visit_enum(Variant {
+            fallback: self.nested()?,
+            variants,
+            index,
+        })
+    }
+
+    fn deserialize_identifier<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+}
+
+/// The given number of default elements.
+struct Elements<'c> {
+    fallback: Fallback<'c>,
+    len: usize,
+}
+
+impl<'c> Elements<'c> {
+    const fn new(fallback: Fallback<'c>, len: usize) -> Self {
+        Self { fallback, len }
+    }
+}
+
+impl<'de> de::SeqAccess<'de> for Elements<'_> {
+    type Error = Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+    where
+        T: de::DeserializeSeed<'de>,
+    {
+        match self.len {
+            0 => Ok(None),
+            _ => {
+                self.len -= 1;
+                seed.deserialize(self.fallback).map(Some)
+            }
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.len)
+    }
+}
+
+/// The given struct fields, each with a default value.
+struct Fields<'c> {
+    fallback: Fallback<'c>,
+    fields: &'static [&'static str],
+    index: usize,
+}
+
+impl<'c> Fields<'c> {
+    const fn new(fallback: Fallback<'c>, fields: &'static [&'static str]) -> Self {
+        Self {
+            fallback,
+            fields,
+            index: 0,
+        }
+    }
+}
+
+impl<'de> de::MapAccess<'de> for Fields<'_> {
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: de::DeserializeSeed<'de>,
+    {
+        match self.fields.get(self.index) {
+            Some(field) => {
+                let identifier = Identifier::new(Cow::Borrowed(field), self.index as u64);
+                self.index += 1;
+                seed.deserialize(identifier).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        seed.deserialize(self.fallback)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.fields.len() - self.index)
+    }
+}
+
+/// An enum variant with default data.
+struct Variant<'c> {
+    fallback: Fallback<'c>,
+    variants: &'static [&'static str],
+    index: usize,
+}
+
+impl Variant<'_> {
+    /// Builds the data of the variant with `build`, noting that the variant holds data so that
+    /// the ones that don't are taken first.
+    fn build<T>(self, build: impl FnOnce(Fallback<'_>) -> Result<T, Error>) -> Result<T, Error> {
+        let Some(collector) = self.fallback.collector else {
+            return build(self.fallback);
+        };
+        collector.fallback_holds_data(self.variants, self.index, false);
+        let result = build(self.fallback);
+        if result.is_ok() {
+            collector.fallback_holds_data(self.variants, self.index, true);
+        }
+        result
+    }
+}
+
+impl<'de> de::EnumAccess<'de> for Variant<'_> {
+    type Error = Error;
+    type Variant = Self;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant), Self::Error>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        let name = self.variants.get(self.index).copied().unwrap_or_default();
+        let identifier = Identifier::new(Cow::Borrowed(name), self.index as u64);
+        seed.deserialize(identifier).map(|v| (v, self))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for Variant<'_> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, Self::Error>
+    where
+        T: de::DeserializeSeed<'de>,
+    {
+        self.build(|fallback| seed.deserialize(fallback))
+    }
+
+    fn tuple_variant<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.build(|fallback| visitor.visit_seq(Elements::new(fallback, len)))
+    }
+
+    fn struct_variant<V>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.build(|fallback| visitor.visit_map(Fields::new(fallback, fields)))
+    }
+}
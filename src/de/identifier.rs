@@ -1,4 +1,5 @@
 use crate::de::error::Unexpected;
+use crate::path::Segment;
 use crate::DataType;
 use crate::Error;
 use crate::Expected;
@@ -21,10 +22,38 @@ impl Unexpected for Identifier {
     }
 }
 
+/// Resolves `name` into the entry of `names` it matches, ignoring case and separators.
+///
+/// Exact matches take precedence. Names that don't match anything are returned as is.
+pub(super) fn resolve<'a>(name: Cow<'a, str>, names: &'static [&'static str]) -> Cow<'a, str> {
+    if names.contains(&name.as_ref()) {
+        return name;
+    }
+    match names.iter().find(|candidate| loosely_eq(candidate, &name)) {
+        Some(candidate) => Cow::Borrowed(candidate),
+        None => name,
+    }
+}
+
+fn loosely_eq(a: &str, b: &str) -> bool {
+    let normalize = |s: &str| {
+        s.chars()
+            .filter(|c| !matches!(c, '_' | '-' | ' ' | '.'))
+            .flat_map(char::to_lowercase)
+            .collect::<alloc::vec::Vec<_>>()
+    };
+    normalize(a) == normalize(b)
+}
+
 impl Identifier {
     pub(super) const fn new(name: Cow<'static, str>, index: u64) -> Self {
         Self { name, index }
     }
+
+    /// Returns the path segment of the struct field this identifies.
+    pub(super) fn segment(&self) -> Segment {
+        Segment::Field(self.name.clone())
+    }
 }
 
 impl<'de> Deserializer<'de> for Identifier {
@@ -0,0 +1,110 @@
+use crate::path;
+use crate::path::Segment;
+use crate::Deserializer;
+use crate::Error;
+use crate::Errors;
+use crate::Value;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::cell::RefCell;
+use serde::de;
+
+/// Records the values a [Deserializer](crate::Deserializer) ignores, along with their paths.
+///
+/// Values are ignored when the type being deserialized has no use for them, like unknown struct
+/// fields or map entries skipped by a custom `Deserialize` implementation. This is useful for
+/// warning about typos in configuration files, or for carrying ignored data forward.
+///
+/// Paths use the same syntax as [Redactor](crate::Redactor) path patterns, like `users[0].name`.
+///
+/// ## Example
+///
+/// ```
+/// # use serde_content::{value, Deserializer, Ignored};
+/// #[derive(serde::Deserialize)]
+/// struct Config {
+///     port: u16,
+/// }
+///
+/// let ignored = Ignored::new();
+/// let value = value!({ "port": 80u16, "prot": "tcp" });
+/// let config: Config = ignored.deserialize(Deserializer::new(value)).unwrap();
+/// assert_eq!(config.port, 80);
+/// assert_eq!(ignored.paths(), ["prot"]);
+/// ```
+#[derive(Debug, Default, PartialEq, PartialOrd)]
+pub struct Ignored {
+    // The path of the value being deserialized.
+    path: RefCell<Vec<Segment>>,
+    values: RefCell<Vec<(String, Value<'static>)>>,
+}
+
+impl Ignored {
+    /// Creates an empty collector.
+    pub const fn new() -> Self {
+        Self {
+            path: RefCell::new(Vec::new()),
+            values: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Whether no values have been ignored.
+    pub fn is_empty(&self) -> bool {
+        self.values.borrow().is_empty()
+    }
+
+    /// Returns the paths of the ignored values, in the order they were ignored.
+    pub fn paths(&self) -> Vec<String> {
+        let values = self.values.borrow();
+        values.iter().map(|(path, _)| path.clone()).collect()
+    }
+
+    /// Takes the ignored values and their paths out of the collector, leaving it empty.
+    pub fn take(&self) -> Vec<(String, Value<'static>)> {
+        self.values.take()
+    }
+
+    /// Deserializes a value `T` with `deserializer`, recording the values it ignores.
+    ///
+    /// The collector is only borrowed while deserializing, so the deserializer itself can be
+    /// shared between threads.
+    pub fn deserialize<'de, T>(&self, deserializer: Deserializer<'de>) -> Result<T, Error>
+    where
+        T: de::Deserialize<'de>,
+    {
+        deserializer.fill(Some(self), |deserializer| T::deserialize(deserializer))
+    }
+
+    /// Like [Ignored::deserialize], but reports every error found instead of the first, as
+    /// [Deserializer::deserialize_all] does.
+    pub fn deserialize_all<'de, T>(&self, deserializer: Deserializer<'de>) -> Result<T, Errors>
+    where
+        T: de::Deserialize<'de>,
+    {
+        deserializer.collect(Some(self))
+    }
+
+    /// Returns the number of values ignored so far.
+    pub(super) fn len(&self) -> usize {
+        self.values.borrow().len()
+    }
+
+    /// Forgets the values ignored after the first `len`.
+    pub(super) fn truncate(&self, len: usize) {
+        self.values.borrow_mut().truncate(len);
+    }
+
+    /// Runs `f` with `segment` appended to the current path.
+    pub(super) fn nested<T>(&self, segment: Segment, f: impl FnOnce() -> T) -> T {
+        self.path.borrow_mut().push(segment);
+        let result = f();
+        self.path.borrow_mut().pop();
+        result
+    }
+
+    /// Records a value ignored at the current path.
+    pub(super) fn record(&self, value: Value<'_>) {
+        let path = path::join(&self.path.borrow());
+        self.values.borrow_mut().push((path, value.into_owned()));
+    }
+}
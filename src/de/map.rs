@@ -1,5 +1,8 @@
 use crate::de::identifier::Identifier;
-use crate::Deserializer;
+use crate::de::missing::Missing;
+use crate::de::Options;
+use crate::de::ValueDeserializer;
+use crate::path::Segment;
 use crate::Error;
 use crate::Value;
 use alloc::borrow::Cow;
@@ -14,27 +17,42 @@ pub(super) enum Key<'de> {
     Value(Value<'de>),
 }
 
-pub(super) struct Map<'de> {
+pub(super) struct Map<'de, 'c> {
     iter: Peekable<IntoIter<(Key<'de>, Value<'de>)>>,
-    human_readable: bool,
-    coerce_numbers: bool,
+    missing: IntoIter<(usize, Missing<'c>)>,
+    field: Option<Missing<'c>>,
+    // The path segment of the value whose key was deserialized last.
+    segment: Option<Segment>,
+    options: Options<'c>,
 }
 
-impl<'de> Map<'de> {
-    pub(super) fn new(
-        vec: Vec<(Key<'de>, Value<'de>)>,
-        human_readable: bool,
-        coerce_numbers: bool,
-    ) -> Self {
+impl<'de, 'c> Map<'de, 'c> {
+    pub(super) fn new(vec: Vec<(Key<'de>, Value<'de>)>, options: Options<'c>) -> Self {
         Self {
-            human_readable,
-            coerce_numbers,
+            options,
             iter: vec.into_iter().peekable(),
+            missing: Vec::new().into_iter(),
+            field: None,
+            segment: None,
         }
     }
+
+    /// Returns the path segment of the value of the entry with `key`, if it's needed.
+    fn segment(&self, key: &Key<'_>) -> Option<Segment> {
+        self.options.segment(|| match key {
+            Key::Identifier(identifier) => identifier.segment(),
+            Key::Value(value) => Segment::key(value),
+        })
+    }
+
+    /// Adds the struct fields that are missing from the map after its entries.
+    pub(super) fn missing(mut self, fields: Vec<(usize, Missing<'c>)>) -> Self {
+        self.missing = fields.into_iter();
+        self
+    }
 }
 
-impl<'de> de::MapAccess<'de> for Map<'de> {
+impl<'de, 'c> de::MapAccess<'de> for Map<'de, 'c> {
     type Error = Error;
 
     fn next_key_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Error>
@@ -42,18 +60,28 @@ impl<'de> de::MapAccess<'de> for Map<'de> {
         T: de::DeserializeSeed<'de>,
     {
         match self.iter.peek_mut() {
-            Some((key, _)) => match mem::replace(key, Key::Value(Value::Unit)) {
-                Key::Value(value) => {
-                    let deserializer = Deserializer {
-                        value,
-                        human_readable: self.human_readable,
-                        coerce_numbers: self.coerce_numbers,
-                    };
-                    seed.deserialize(deserializer).map(Some)
+            Some((key, _)) => {
+                let key = mem::replace(key, Key::Value(Value::Unit));
+                self.segment = self.segment(&key);
+                match key {
+                    Key::Value(value) => {
+                        let deserializer = ValueDeserializer {
+                            value,
+                            options: self.options,
+                        };
+                        seed.deserialize(deserializer).map(Some)
+                    }
+                    Key::Identifier(identifier) => seed.deserialize(identifier).map(Some),
+                }
+            }
+            None => match self.missing.next() {
+                Some((index, missing)) => {
+                    self.field = Some(missing);
+                    let identifier = Identifier::new(Cow::Borrowed(missing.field()), index as u64);
+                    seed.deserialize(identifier).map(Some)
                 }
-                Key::Identifier(identifier) => seed.deserialize(identifier).map(Some),
+                None => Ok(None),
             },
-            None => Ok(None),
         }
     }
 
@@ -63,14 +91,18 @@ impl<'de> de::MapAccess<'de> for Map<'de> {
     {
         match self.iter.next() {
             Some((_, value)) => {
-                let deserializer = Deserializer {
+                let deserializer = ValueDeserializer {
                     value,
-                    human_readable: self.human_readable,
-                    coerce_numbers: self.coerce_numbers,
+                    options: self.options,
                 };
-                seed.deserialize(deserializer)
+                let segment = self.segment.take();
+                self.options
+                    .nested(segment, || seed.deserialize(deserializer))
             }
-            None => Err(de::Error::custom("[BUG] value is missing")),
+            None => match self.field.take() {
+                Some(missing) => seed.deserialize(missing),
+                None => Err(de::Error::custom("[BUG] value is missing")),
+            },
         }
     }
 
@@ -85,55 +117,64 @@ impl<'de> de::MapAccess<'de> for Map<'de> {
     {
         match self.iter.next() {
             Some((key, value)) => {
+                let segment = self.segment(&key);
                 let key = match key {
                     Key::Identifier(identifier) => kseed.deserialize(identifier)?,
                     Key::Value(value) => {
-                        let deserializer = Deserializer {
+                        let deserializer = ValueDeserializer {
                             value,
-                            human_readable: self.human_readable,
-                            coerce_numbers: self.coerce_numbers,
+                            options: self.options,
                         };
                         kseed.deserialize(deserializer)?
                     }
                 };
-                let deserializer = Deserializer {
+                let deserializer = ValueDeserializer {
                     value,
-                    human_readable: self.human_readable,
-                    coerce_numbers: self.coerce_numbers,
+                    options: self.options,
                 };
-                let value = vseed.deserialize(deserializer)?;
+                let value = self
+                    .options
+                    .nested(segment, || vseed.deserialize(deserializer))?;
                 Ok(Some((key, value)))
             }
-            None => Ok(None),
+            None => match self.missing.next() {
+                Some((index, missing)) => {
+                    let field = Cow::Borrowed(missing.field());
+                    let key = kseed.deserialize(Identifier::new(field, index as u64))?;
+                    let value = vseed.deserialize(missing)?;
+                    Ok(Some((key, value)))
+                }
+                None => Ok(None),
+            },
         }
     }
 
     fn size_hint(&self) -> Option<usize> {
         match self.iter.size_hint() {
-            (lower, Some(upper)) if lower == upper => Some(upper),
+            (lower, Some(upper)) if lower == upper => Some(upper + self.missing.len()),
             _ => None,
         }
     }
 }
 
-impl<'de> From<(Vec<(Cow<'static, str>, Value<'de>)>, bool, bool)> for Map<'de> {
-    fn from(fields: (Vec<(Cow<'static, str>, Value<'de>)>, bool, bool)) -> Self {
+impl<'de, 'c> From<(Vec<(Cow<'static, str>, Value<'de>)>, Options<'c>)> for Map<'de, 'c> {
+    fn from(fields: (Vec<(Cow<'static, str>, Value<'de>)>, Options<'c>)) -> Self {
         let mut vec = Vec::with_capacity(fields.0.len());
         for (index, (key, value)) in fields.0.into_iter().enumerate() {
             let key = Key::Identifier(Identifier::new(key, index as u64));
             vec.push((key, value));
         }
-        Self::new(vec, fields.1, fields.2)
+        Self::new(vec, fields.1)
     }
 }
 
-impl<'de> From<(Vec<(Value<'de>, Value<'de>)>, bool, bool)> for Map<'de> {
-    fn from(fields: (Vec<(Value<'de>, Value<'de>)>, bool, bool)) -> Self {
+impl<'de, 'c> From<(Vec<(Value<'de>, Value<'de>)>, Options<'c>)> for Map<'de, 'c> {
+    fn from(fields: (Vec<(Value<'de>, Value<'de>)>, Options<'c>)) -> Self {
         let mut vec = Vec::with_capacity(fields.0.len());
         for (key, value) in fields.0 {
             let key = Key::Value(key);
             vec.push((key, value));
         }
-        Self::new(vec, fields.1, fields.2)
+        Self::new(vec, fields.1)
     }
 }
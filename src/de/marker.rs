@@ -0,0 +1,129 @@
+use crate::Data;
+use crate::Enum;
+use crate::Number;
+use crate::Value;
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::mem;
+use serde::de;
+
+/// The key of the map a marker wraps the value in.
+const KEY: &str = "$serde_content::private::Value";
+
+/// Whether `value` is an enum or a 128-bit number, which serde can't buffer.
+///
+/// `serde` buffers values in its own internal representation for `#[serde(flatten)]`, untagged
+/// and internally tagged enums, which has no enums or 128-bit numbers. Inside maps, such values
+/// are handed to `deserialize_any` visitors as a newtype holding a marker instead, a map with a
+/// single entry describing the value, which [ValueVisitor](crate::ValueVisitor) turns back into
+/// the value. This is how [Rest](crate::Rest) keeps them.
+pub(super) fn needed(value: &Value<'_>) -> bool {
+    matches!(
+        value,
+        Value::Enum(_) | Value::Number(Number::I128(_) | Number::U128(_))
+    )
+}
+
+/// Describes `value` as a marker.
+pub(super) fn encode(value: Value<'_>) -> Value<'_> {
+    let parts = match value {
+        Value::Number(Number::I128(v)) => vec![string("i128"), bytes(v.to_le_bytes())],
+        Value::Number(Number::U128(v)) => vec![string("u128"), bytes(v.to_le_bytes())],
+        Value::Enum(v) => {
+            let v = *v;
+            let mut parts = vec![
+                string("enum"),
+                Value::String(v.name),
+                Value::Number(Number::U32(v.variant_index)),
+                Value::String(v.variant),
+            ];
+            match v.data {
+                Data::Unit => parts.push(string("unit")),
+                Data::NewType { value } => parts.extend([string("newtype"), value]),
+                Data::Tuple { values } => parts.extend([string("tuple"), Value::Seq(values)]),
+                Data::Struct { fields } => {
+                    let fields = fields
+                        .into_iter()
+                        .map(|(key, value)| (Value::String(key), value))
+                        .collect();
+                    parts.extend([string("struct"), Value::Map(fields)]);
+                }
+            }
+            parts
+        }
+        value => return value,
+    };
+    Value::Map(vec![(string(KEY), Value::Seq(parts))])
+}
+
+/// Turns a marker back into the value it describes, giving back any other value as it is.
+pub(super) fn decode<E>(value: Value<'_>) -> Result<Value<'_>, E>
+where
+    E: de::Error,
+{
+    let Value::Map(mut map) = value else {
+        return Ok(value);
+    };
+    let parts = match map.as_mut_slice() {
+        [(Value::String(key), Value::Seq(parts))] if key == KEY => mem::take(parts),
+        _ => return Ok(Value::Map(map)),
+    };
+    parts_into_value(parts).ok_or_else(|| E::custom("invalid serde-content value marker"))
+}
+
+fn parts_into_value(parts: Vec<Value<'_>>) -> Option<Value<'_>> {
+    let mut parts = parts.into_iter();
+    let value = match (parts.next()?, parts.next()?) {
+        (Value::String(kind), Value::Bytes(v)) if kind == "i128" => Value::Number(Number::I128(
+            i128::from_le_bytes(v.as_ref().try_into().ok()?),
+        )),
+        (Value::String(kind), Value::Bytes(v)) if kind == "u128" => Value::Number(Number::U128(
+            u128::from_le_bytes(v.as_ref().try_into().ok()?),
+        )),
+        (Value::String(kind), Value::String(name)) if kind == "enum" => {
+            let (Value::Number(Number::U32(variant_index)), Value::String(variant)) =
+                (parts.next()?, parts.next()?)
+            else {
+                return None;
+            };
+            let data = match (parts.next()?, parts.next()) {
+                (Value::String(kind), None) if kind == "unit" => Data::Unit,
+                (Value::String(kind), Some(value)) if kind == "newtype" => Data::NewType { value },
+                (Value::String(kind), Some(Value::Seq(values))) if kind == "tuple" => {
+                    Data::Tuple { values }
+                }
+                (Value::String(kind), Some(Value::Map(fields))) if kind == "struct" => {
+                    let fields = fields.into_iter().map(|(key, value)| match key {
+                        Value::String(key) => Some((Cow::Owned(key.into_owned()), value)),
+                        _ => None,
+                    });
+                    Data::Struct {
+                        fields: fields.collect::<Option<_>>()?,
+                    }
+                }
+                _ => return None,
+            };
+            Value::Enum(Box::new(Enum {
+                name: Cow::Owned(name.into_owned()),
+                variant_index,
+                variant: Cow::Owned(variant.into_owned()),
+                data,
+            }))
+        }
+        _ => return None,
+    };
+    match parts.next() {
+        Some(_) => None,
+        None => Some(value),
+    }
+}
+
+fn string(value: &'static str) -> Value<'static> {
+    Value::String(Cow::Borrowed(value))
+}
+
+fn bytes<'a, const N: usize>(value: [u8; N]) -> Value<'a> {
+    Value::Bytes(Cow::Owned(value.to_vec()))
+}
@@ -0,0 +1,179 @@
+use crate::de::fallback::Fallback;
+use crate::Error;
+use alloc::vec::Vec;
+use core::cell::Cell;
+use core::cell::RefCell;
+use serde::de;
+use serde::de::Deserializer;
+use serde::de::Visitor;
+
+/// Deserializes a struct field that is missing from the value.
+///
+/// Options are deserialized as `None` and the unit type as `()`. Anything else is recorded in
+/// [Skipped] so that the field can be left out next time, and while [Skipped] is probing, a
+/// default value is deserialized in its place to find the other fields to leave out. Otherwise
+/// it's reported as a missing field.
+#[derive(Clone, Copy)]
+pub(super) struct Missing<'c> {
+    field: &'static str,
+    // The fields of the struct or enum variant the field belongs to.
+    fields: &'static [&'static str],
+    skipped: Option<&'c Skipped>,
+}
+
+impl<'c> Missing<'c> {
+    pub(super) const fn new(
+        field: &'static str,
+        fields: &'static [&'static str],
+        skipped: Option<&'c Skipped>,
+    ) -> Self {
+        Self {
+            field,
+            fields,
+            skipped,
+        }
+    }
+
+    pub(super) const fn field(&self) -> &'static str {
+        self.field
+    }
+
+    /// Records the field as skipped, returning a fallback to deserialize in its place if
+    /// probing.
+    fn skip(self) -> Result<Fallback<'static>, Error> {
+        match self.skipped {
+            Some(skipped) => {
+                skipped.insert(self.fields, self.field);
+                match skipped.probing.get() {
+                    true => Ok(Fallback::new(None)),
+                    false => Err(de::Error::missing_field(self.field)),
+                }
+            }
+            None => Err(de::Error::missing_field(self.field)),
+        }
+    }
+}
+
+/// Implements the methods of `serde::Deserializer` by deserializing a fallback after
+/// [Missing::skip].
+macro_rules! skip {
+    ($($method:ident($($arg:ident: $ty:ty),*);)*) => {
+        $(
+            fn $method<V>(self, $($arg: $ty,)* visitor: V) -> Result<V::Value, Self::Error>
+            where
+                V: Visitor<'de>,
+            {
+                self.skip()?.$method($($arg,)* visitor)
+            }
+        )*
+    };
+}
+
+impl<'de> Deserializer<'de> for Missing<'_> {
+    type Error = Error;
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_none()
+    }
+
+    fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    skip! {
+        deserialize_any();
+        deserialize_bool();
+        deserialize_i8();
+        deserialize_i16();
+        deserialize_i32();
+        deserialize_i64();
+        deserialize_i128();
+        deserialize_u8();
+        deserialize_u16();
+        deserialize_u32();
+        deserialize_u64();
+        deserialize_u128();
+        deserialize_f32();
+        deserialize_f64();
+        deserialize_char();
+        deserialize_str();
+        deserialize_string();
+        deserialize_bytes();
+        deserialize_byte_buf();
+        deserialize_unit_struct(name: &'static str);
+        deserialize_newtype_struct(name: &'static str);
+        deserialize_seq();
+        deserialize_tuple(len: usize);
+        deserialize_tuple_struct(name: &'static str, len: usize);
+        deserialize_map();
+        deserialize_struct(name: &'static str, fields: &'static [&'static str]);
+        deserialize_enum(name: &'static str, variants: &'static [&'static str]);
+        deserialize_identifier();
+    }
+}
+
+/// The missing fields that turned out not to be options or the unit type.
+///
+/// There is no telling what type a field has before offering it to the `Deserialize`
+/// implementation, and once offered, a field with `#[serde(default)]` can't fall back to its
+/// default anymore. So [Missing] records such fields here while probing, and deserialization
+/// is retried once without them, leaving `Deserialize` implementations to fill them in as they
+/// would without [Deserializer::fill_missing](crate::Deserializer::fill_missing).
+#[derive(Debug, PartialEq, PartialOrd)]
+pub(super) struct Skipped {
+    fields: RefCell<Vec<(&'static [&'static str], &'static str)>>,
+    // Whether default values stand in for the fields recorded, so that the fields after them
+    // are found in the same pass.
+    probing: Cell<bool>,
+}
+
+impl Skipped {
+    pub(super) const fn new() -> Self {
+        Self {
+            fields: RefCell::new(Vec::new()),
+            probing: Cell::new(true),
+        }
+    }
+
+    /// Whether no fields have been recorded.
+    pub(super) fn is_empty(&self) -> bool {
+        self.fields.borrow().is_empty()
+    }
+
+    /// Stops standing in default values for the fields recorded, reporting them as missing
+    /// instead.
+    pub(super) fn stop_probing(&self) {
+        self.probing.set(false);
+    }
+
+    /// Whether `field` of the struct or enum variant with `fields` is to be left out.
+    ///
+    /// Structs and enum variants are told apart by their field names, as the constants serde
+    /// passes them in don't necessarily have a single address.
+    pub(super) fn contains(&self, fields: &'static [&'static str], field: &str) -> bool {
+        let skipped = self.fields.borrow();
+        skipped
+            .iter()
+            .any(|(f, name)| *f == fields && *name == field)
+    }
+
+    /// Records that `field` of the struct or enum variant with `fields` is to be left out.
+    fn insert(&self, fields: &'static [&'static str], field: &'static str) {
+        if !self.contains(fields, field) {
+            self.fields.borrow_mut().push((fields, field));
+        }
+    }
+}
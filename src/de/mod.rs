@@ -1,26 +1,42 @@
 #![cfg(feature = "serde")]
 
+mod bytes;
+mod collect;
 mod r#enum;
+mod fallback;
 mod identifier;
+mod ignored;
 mod map;
+mod marker;
+mod missing;
 mod number;
 mod seq;
 mod tests;
 
+use crate::limits;
+use crate::path::Segment;
+use crate::BytesEncoding;
 use crate::Data;
 use crate::DataType;
 use crate::Error;
+use crate::Errors;
 use crate::Expected;
+use crate::Limits;
 use crate::Number;
 use crate::Value;
 use alloc::borrow::Cow;
 use alloc::borrow::ToOwned;
 use alloc::boxed::Box;
 use alloc::string::String;
+use alloc::string::ToString;
 use alloc::vec::Vec;
+use collect::Collector;
 use core::fmt;
+use fallback::Fallback;
 use identifier::Identifier;
 use map::Map;
+use missing::Missing;
+use missing::Skipped;
 use seq::Seq;
 use serde::de;
 use serde::Deserialize;
@@ -31,13 +47,165 @@ use serde::de::SeqAccess;
 use serde::de::Visitor;
 
 pub use error::Unexpected;
+pub use ignored::Ignored;
 
 /// A structure that deserializes Rust values into [Value].
+///
+/// The value being deserialized lives for `'de`, which is how long the strings and byte arrays
+/// deserialized can borrow from it for.
 #[derive(Debug, Clone, PartialEq, PartialOrd)]
 pub struct Deserializer<'de> {
     value: Value<'de>,
+    config: Config,
+}
+
+/// The settings of a [Deserializer].
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+struct Config {
     human_readable: bool,
     coerce_numbers: bool,
+    coerce_strings: bool,
+    coerce_bytes: bool,
+    bytes_encoding: Option<BytesEncoding>,
+    adjacent_tags: Option<(&'static str, &'static str)>,
+    loose_identifiers: bool,
+    coerce_shapes: bool,
+    fill_missing: bool,
+    strict: bool,
+}
+
+/// Deserializes a value and the values nested in it on behalf of a [Deserializer].
+///
+/// Unlike [Deserializer], this also borrows the collectors that are only needed while
+/// deserializing, for `'c`.
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+struct ValueDeserializer<'de, 'c> {
+    value: Value<'de>,
+    options: Options<'c>,
+}
+
+/// Options shared by a deserializer and the deserializers of nested values.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+struct Options<'c> {
+    config: Config,
+    ignored: Option<&'c Ignored>,
+    errors: Option<&'c Collector>,
+    skipped: Option<&'c Skipped>,
+    // Whether values serde can't buffer are handed to `deserialize_any` visitors as markers, which
+    // is the case inside maps. See `marker::needed`.
+    markers: bool,
+}
+
+impl<'c> Options<'c> {
+    /// Resolves loosely matching struct field names into the names in `fields`.
+    fn fields<'v>(
+        self,
+        values: Vec<(Cow<'static, str>, Value<'v>)>,
+        fields: &'static [&'static str],
+    ) -> Vec<(Cow<'static, str>, Value<'v>)> {
+        if !self.config.loose_identifiers {
+            return values;
+        }
+        values
+            .into_iter()
+            .map(|(key, value)| (identifier::resolve(key, fields), value))
+            .collect()
+    }
+
+    /// Resolves loosely matching map keys into the names in `fields`.
+    fn keys<'v>(
+        self,
+        map: Vec<(Value<'v>, Value<'v>)>,
+        fields: &'static [&'static str],
+    ) -> Vec<(Value<'v>, Value<'v>)> {
+        if !self.config.loose_identifiers {
+            return map;
+        }
+        map.into_iter()
+            .map(|(key, value)| (self.identifier(key, fields), value))
+            .collect()
+    }
+
+    /// Lists the entries of `fields` that aren't in `keys`, if missing fields are to be filled.
+    ///
+    /// Fields that turned out not to be options or the unit type are left out.
+    fn missing<'a>(
+        self,
+        keys: impl Iterator<Item = &'a str>,
+        fields: &'static [&'static str],
+    ) -> Vec<(usize, Missing<'c>)> {
+        if !self.config.fill_missing {
+            return Vec::new();
+        }
+        let keys = keys.collect::<Vec<_>>();
+        let skipped = |field| self.skipped.is_some_and(|s| s.contains(fields, field));
+        fields
+            .iter()
+            .copied()
+            .enumerate()
+            .filter(|(_, field)| !keys.contains(field) && !skipped(field))
+            .map(|(index, field)| (index, Missing::new(field, fields, self.skipped)))
+            .collect()
+    }
+
+    /// Creates a path segment, if ignored values or errors are collected.
+    fn segment(self, segment: impl FnOnce() -> Segment) -> Option<Segment> {
+        match self.ignored.is_some() || self.errors.is_some() {
+            true => Some(segment()),
+            false => None,
+        }
+    }
+
+    /// Runs `f` with `segment` appended to the current path, if ignored values or errors are
+    /// collected.
+    fn nested<T>(
+        self,
+        segment: Option<Segment>,
+        f: impl FnOnce() -> Result<T, Error>,
+    ) -> Result<T, Error> {
+        let Some(segment) = segment else {
+            return f();
+        };
+        match (self.ignored, self.errors) {
+            (Some(ignored), Some(errors)) => {
+                ignored.nested(segment.clone(), || errors.nested(segment, f))
+            }
+            (Some(ignored), None) => ignored.nested(segment, f),
+            (None, Some(errors)) => errors.nested(segment, f),
+            (None, None) => f(),
+        }
+    }
+
+    /// Returns `error`, or records it and carries on if errors are collected.
+    fn report(self, error: Error) -> Result<(), Error> {
+        match self.errors {
+            Some(errors) => {
+                errors.record(error);
+                Ok(())
+            }
+            None => Err(error),
+        }
+    }
+
+    /// Returns `error`, or records it and runs `fallback` in its place if errors are collected.
+    fn recover<T>(
+        self,
+        error: Error,
+        fallback: impl FnOnce() -> Result<T, Error>,
+    ) -> Result<T, Error> {
+        self.report(error)?;
+        fallback()
+    }
+
+    /// Resolves a loosely matching string into a name in `names`.
+    fn identifier<'v>(self, value: Value<'v>, names: &'static [&'static str]) -> Value<'v> {
+        match value {
+            Value::String(v) if self.config.loose_identifiers => {
+                Value::String(identifier::resolve(v, names))
+            }
+            value => value,
+        }
+    }
 }
 
 impl<'de> Deserializer<'de> {
@@ -48,35 +216,256 @@ impl<'de> Deserializer<'de> {
     pub const fn new(value: Value<'de>) -> Self {
         Self {
             value,
-            human_readable: false,
-            coerce_numbers: false,
+            config: Config {
+                human_readable: false,
+                coerce_numbers: false,
+                coerce_strings: false,
+                coerce_bytes: false,
+                bytes_encoding: None,
+                adjacent_tags: None,
+                loose_identifiers: false,
+                coerce_shapes: false,
+                fill_missing: false,
+                strict: false,
+            },
         }
     }
 
     /// Make `Deserialize` implementations deserialize in human-readable form.
     pub const fn human_readable(mut self) -> Self {
-        self.human_readable = true;
+        self.config.human_readable = true;
         self
     }
 
     /// When deseriazing numbers try to coerce different number types into the expected type.
     pub const fn coerce_numbers(mut self) -> Self {
-        self.coerce_numbers = true;
+        self.config.coerce_numbers = true;
         self
     }
 
+    /// Parse scalars from strings and format scalars as strings where needed.
+    ///
+    /// Numbers, booleans, characters and the unit type can then be deserialized from
+    /// [Value::String], which is handy for values coming from environment variables, query
+    /// strings or CSV files. The unit type is parsed from an empty string. In the other
+    /// direction, strings can be deserialized from numbers, booleans and characters.
+    pub const fn coerce_strings(mut self) -> Self {
+        self.config.coerce_strings = true;
+        self
+    }
+
+    /// Convert between byte arrays and sequences of integers.
+    ///
+    /// Byte arrays can then be deserialized from sequences of integers between `0` and `255`,
+    /// which is how most self-describing formats without a native byte array type represent
+    /// them. In the other direction, sequences and tuples can be deserialized from byte arrays.
+    pub const fn coerce_bytes(mut self) -> Self {
+        self.config.coerce_bytes = true;
+        self
+    }
+
+    /// Decode strings with `encoding` when deserializing byte arrays in human-readable form.
+    ///
+    /// This has no effect unless [Deserializer::human_readable] is also set.
+    pub const fn bytes_encoding(mut self, encoding: BytesEncoding) -> Self {
+        self.config.bytes_encoding = Some(encoding);
+        self
+    }
+
+    /// Also accept adjacently tagged enums, like `{"t": "Variant", "c": data}`.
+    ///
+    /// The variant is read from the `tag` entry and its data from the `content` entry, which
+    /// unit variants may leave out. Both maps and object-like structs are accepted.
+    pub const fn adjacent_tags(mut self, tag: &'static str, content: &'static str) -> Self {
+        self.config.adjacent_tags = Some((tag, content));
+        self
+    }
+
+    /// Match struct fields and enum variants ignoring case and separators.
+    ///
+    /// With this, `user_id`, `userId` and `USER-ID` all match a field named `user_id`. Exact
+    /// matches take precedence. Only structs and enums, which list their fields and variants,
+    /// are affected.
+    pub const fn loose_identifiers(mut self) -> Self {
+        self.config.loose_identifiers = true;
+        self
+    }
+
+    /// Convert between maps, sequences, tuples and structs where needed.
+    ///
+    /// With this, maps can be deserialized from object-like structs, sequences from tuples and
+    /// tuple structs, tuples and tuple structs from any of those, and object-like structs from
+    /// sequences, tuples and tuple structs by the position of their fields.
+    pub const fn coerce_shapes(mut self) -> Self {
+        self.config.coerce_shapes = true;
+        self
+    }
+
+    /// Fill in missing struct fields that can be left empty.
+    ///
+    /// Missing fields of object-like structs and enum variants that deserialize as options are
+    /// set to `None` and those that deserialize as the unit type are set to `()`, even without
+    /// `#[serde(default)]`. Other missing fields are left to the `Deserialize` implementation, so
+    /// `#[serde(default)]` still applies to them and those without a default are reported as
+    /// [crate::ErrorKind::MissingField].
+    ///
+    /// The deserializer can't tell the type of a field it doesn't have, so it finds out by
+    /// offering it and deserializes once more without the fields that weren't options or the
+    /// unit type. This is done by [Deserializer::deserialize], [Deserializer::deserialize_all] and
+    /// their [Ignored] counterparts; when the deserializer is passed to `Deserialize`
+    /// implementations directly, such fields are reported as missing even if they have a default.
+    pub const fn fill_missing(mut self) -> Self {
+        self.config.fill_missing = true;
+        self
+    }
+
+    /// Only accept values in the exact shape [Serializer](crate::Serializer) would have produced.
+    ///
+    /// By default, the deserializer is lenient. For example, it accepts maps for structs,
+    /// sequences for tuples and plain strings for unit enum variants. In strict mode, structs and
+    /// enums must have the expected name and shape, tuples must have the expected length, options
+    /// must be [Value::Option] and object-like structs and enum variants can't have unknown fields.
+    pub const fn strict(mut self) -> Self {
+        self.config.strict = true;
+        self
+    }
+
+    /// Check that the value is within `limits` before deserializing it.
+    ///
+    /// Deserializing a value recurses as deeply as the value is nested, so values from untrusted
+    /// sources should be checked first. See [Limits] for details.
+    pub fn limits(self, limits: &Limits) -> Result<Self, Error> {
+        limits.check(&self.value)?;
+        Ok(self)
+    }
+
     /// Deserializes a value `T` from [`Value`]
     pub fn deserialize<T>(self) -> Result<T, Error>
     where
         T: de::Deserialize<'de>,
     {
-        T::deserialize(self)
+        self.fill(None, |deserializer| T::deserialize(deserializer))
+    }
+
+    /// Deserializes a value `T` from [`Value`], reporting every error found instead of the first.
+    ///
+    /// When a value has the wrong type, the error is recorded with its path and a default value
+    /// is used in its place, so that deserialization can carry on. Numbers default to zero,
+    /// strings and collections to being empty, options to `None` and enums to a variant, unit
+    /// variants and variants that don't recurse being preferred as they are found out.
+    /// Errors raised by `Deserialize` implementations themselves, like missing struct fields,
+    /// and default values nesting too deeply for recursive types still stop deserialization
+    /// and are reported last.
+    pub fn deserialize_all<T>(self) -> Result<T, Errors>
+    where
+        T: de::Deserialize<'de>,
+    {
+        self.collect(None)
+    }
+
+    /// Deserializes a value `T`, collecting every error found and the values ignored in
+    /// `ignored`, if given.
+    fn collect<T>(self, ignored: Option<&Ignored>) -> Result<T, Errors>
+    where
+        T: de::Deserialize<'de>,
+    {
+        self.fill(ignored, |deserializer| {
+            let collector = Collector::new();
+            let deserializer = ValueDeserializer {
+                value: deserializer.value,
+                options: Options {
+                    errors: Some(&collector),
+                    ..deserializer.options
+                },
+            };
+            let result = T::deserialize(deserializer);
+            collector.finish(result)
+        })
+    }
+
+    /// Runs `deserialize` on the value, recording ignored values in `ignored` if given.
+    ///
+    /// If [Deserializer::fill_missing] is set, a first run finds the missing fields that can't be
+    /// filled and, if there are any, a second run leaves them out. Only the first run needs a
+    /// copy of the value.
+    fn fill<R>(
+        self,
+        ignored: Option<&Ignored>,
+        deserialize: impl for<'s> Fn(ValueDeserializer<'de, 's>) -> R,
+    ) -> R {
+        let options = Options {
+            config: self.config,
+            ignored,
+            errors: None,
+            skipped: None,
+            markers: false,
+        };
+        if !self.config.fill_missing {
+            return deserialize(ValueDeserializer {
+                value: self.value,
+                options,
+            });
+        }
+        let skipped = Skipped::new();
+        let len = ignored.map(Ignored::len);
+        let options = Options {
+            skipped: Some(&skipped),
+            ..options
+        };
+        let result = deserialize(ValueDeserializer {
+            value: self.value.clone(),
+            options,
+        });
+        if skipped.is_empty() {
+            return result;
+        }
+        if let (Some(ignored), Some(len)) = (ignored, len) {
+            ignored.truncate(len);
+        }
+        skipped.stop_probing();
+        deserialize(ValueDeserializer {
+            value: self.value,
+            options,
+        })
+    }
+
+    /// Returns a deserializer of the value that doesn't collect anything.
+    fn into_value_deserializer(self) -> ValueDeserializer<'de, 'static> {
+        ValueDeserializer {
+            value: self.value,
+            options: Options {
+                config: self.config,
+                ignored: None,
+                errors: None,
+                skipped: None,
+                markers: false,
+            },
+        }
+    }
+}
+
+impl<'de> ValueDeserializer<'de, '_> {
+    /// Deserializes a number of the `expected` type.
+    fn deserialize_number<V>(self, expected: Expected, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        let zero = number::zero(&expected);
+        let number = match self.value {
+            Value::Number(n) => number::convert(n, expected, self.options.config.coerce_numbers),
+            Value::String(v) if self.options.config.coerce_strings => number::parse(v, expected),
+            value => Err(value.unexpected(expected)),
+        };
+        match number {
+            Ok(number) => number::visit(number, visitor),
+            Err(error) => self.options.recover(error, || number::visit(zero, visitor)),
+        }
     }
 }
 
 #[cfg(feature = "std")]
 impl<'de> serde::de::IntoDeserializer<'de, Error> for Deserializer<'de> {
-    type Deserializer = Deserializer<'de>;
+    type Deserializer = Self;
 
     fn into_deserializer(self) -> Self::Deserializer {
         self
@@ -92,13 +481,73 @@ impl<'de> serde::de::IntoDeserializer<'de, Error> for Value<'de> {
     }
 }
 
+/// Forwards the methods of `serde::Deserializer` to a [ValueDeserializer].
+macro_rules! forward {
+    ($($method:ident($($arg:ident: $ty:ty),*);)*) => {
+        $(
+            fn $method<V>(self, $($arg: $ty,)* visitor: V) -> Result<V::Value, Self::Error>
+            where
+                V: Visitor<'de>,
+            {
+                self.into_value_deserializer().$method($($arg,)* visitor)
+            }
+        )*
+    };
+}
+
 impl<'de> de::Deserializer<'de> for Deserializer<'de> {
     type Error = Error;
 
+    forward! {
+        deserialize_any();
+        deserialize_bool();
+        deserialize_i8();
+        deserialize_i16();
+        deserialize_i32();
+        deserialize_i64();
+        deserialize_i128();
+        deserialize_u8();
+        deserialize_u16();
+        deserialize_u32();
+        deserialize_u64();
+        deserialize_u128();
+        deserialize_f32();
+        deserialize_f64();
+        deserialize_char();
+        deserialize_str();
+        deserialize_string();
+        deserialize_bytes();
+        deserialize_byte_buf();
+        deserialize_option();
+        deserialize_unit();
+        deserialize_unit_struct(name: &'static str);
+        deserialize_newtype_struct(name: &'static str);
+        deserialize_seq();
+        deserialize_tuple(len: usize);
+        deserialize_tuple_struct(name: &'static str, len: usize);
+        deserialize_map();
+        deserialize_struct(name: &'static str, fields: &'static [&'static str]);
+        deserialize_enum(name: &'static str, variants: &'static [&'static str]);
+        deserialize_identifier();
+        deserialize_ignored_any();
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.config.human_readable
+    }
+}
+
+impl<'de, 'c> de::Deserializer<'de> for ValueDeserializer<'de, 'c> {
+    type Error = Error;
+
     fn deserialize_any<V>(mut self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
+        if self.options.markers && marker::needed(&self.value) {
+            self.value = marker::encode(self.value);
+            return visitor.visit_newtype_struct(self);
+        }
         match self.value {
             Value::Unit => visitor.visit_unit(),
             Value::Bool(v) => visitor.visit_bool(v),
@@ -125,12 +574,8 @@ impl<'de> de::Deserializer<'de> for Deserializer<'de> {
                 Cow::Borrowed(v) => visitor.visit_borrowed_bytes(v),
                 Cow::Owned(v) => visitor.visit_byte_buf(v),
             },
-            Value::Seq(v) => {
-                visitor.visit_seq(Seq::new(v, self.human_readable, self.coerce_numbers))
-            }
-            Value::Map(v) => {
-                visitor.visit_map(Map::from((v, self.human_readable, self.coerce_numbers)))
-            }
+            Value::Seq(v) => visitor.visit_seq(Seq::new(v, self.options)),
+            Value::Map(v) => visitor.visit_map(Map::from((v, self.options))),
             Value::Option(v) => match v {
                 Some(v) => {
                     self.value = *v;
@@ -145,7 +590,7 @@ impl<'de> de::Deserializer<'de> for Deserializer<'de> {
                     visitor.visit_newtype_struct(self)
                 }
                 Data::Tuple { values } => {
-                    let tuple = Seq::new(values, self.human_readable, self.coerce_numbers);
+                    let tuple = Seq::new(values, self.options);
                     visitor.visit_seq(tuple)
                 }
                 Data::Struct { fields } => {
@@ -155,20 +600,12 @@ impl<'de> de::Deserializer<'de> for Deserializer<'de> {
                         let key = Key::Identifier(Identifier::new(key, index as u64));
                         vec.push((key, value));
                     }
-                    let data = Map::new(vec, self.human_readable, self.coerce_numbers);
+                    let data = Map::new(vec, self.options);
                     visitor.visit_map(data)
                 }
             },
-            Value::Enum(v) => r#enum::visit_enum(
-                v.name.clone(),
-                v,
-                self.human_readable,
-                self.coerce_numbers,
-                visitor,
-            ),
-            Value::Tuple(v) => {
-                visitor.visit_seq(Seq::new(v, self.human_readable, self.coerce_numbers))
-            }
+            Value::Enum(v) => r#enum::visit_enum(v.name.clone(), v, self.options, visitor),
+            Value::Tuple(v) => visitor.visit_seq(Seq::new(v, self.options)),
         }
     }
 
@@ -178,7 +615,19 @@ impl<'de> de::Deserializer<'de> for Deserializer<'de> {
     {
         match self.value {
             Value::Bool(v) => visitor.visit_bool(v),
-            _ => Err(self.value.unexpected(Expected::Bool)),
+            Value::String(v) if self.options.config.coerce_strings => match v.parse() {
+                Ok(v) => visitor.visit_bool(v),
+                Err(_) => self
+                    .options
+                    .recover(Value::String(v).unexpected(Expected::Bool), || {
+                        Fallback::new(self.options.errors).deserialize_bool(visitor)
+                    }),
+            },
+            _ => self
+                .options
+                .recover(self.value.unexpected(Expected::Bool), || {
+                    Fallback::new(self.options.errors).deserialize_bool(visitor)
+                }),
         }
     }
 
@@ -186,120 +635,84 @@ impl<'de> de::Deserializer<'de> for Deserializer<'de> {
     where
         V: Visitor<'de>,
     {
-        match self.value {
-            Value::Number(n) => number::visit(n, Expected::I8, self.coerce_numbers, visitor),
-            _ => Err(self.value.unexpected(Expected::I8)),
-        }
+        self.deserialize_number(Expected::I8, visitor)
     }
 
     fn deserialize_i16<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        match self.value {
-            Value::Number(n) => number::visit(n, Expected::I16, self.coerce_numbers, visitor),
-            _ => Err(self.value.unexpected(Expected::I16)),
-        }
+        self.deserialize_number(Expected::I16, visitor)
     }
 
     fn deserialize_i32<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        match self.value {
-            Value::Number(n) => number::visit(n, Expected::I32, self.coerce_numbers, visitor),
-            _ => Err(self.value.unexpected(Expected::I32)),
-        }
+        self.deserialize_number(Expected::I32, visitor)
     }
 
     fn deserialize_i64<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        match self.value {
-            Value::Number(n) => number::visit(n, Expected::I64, self.coerce_numbers, visitor),
-            _ => Err(self.value.unexpected(Expected::I64)),
-        }
+        self.deserialize_number(Expected::I64, visitor)
     }
 
     fn deserialize_i128<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        match self.value {
-            Value::Number(n) => number::visit(n, Expected::I128, self.coerce_numbers, visitor),
-            _ => Err(self.value.unexpected(Expected::I128)),
-        }
+        self.deserialize_number(Expected::I128, visitor)
     }
 
     fn deserialize_u8<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        match self.value {
-            Value::Number(n) => number::visit(n, Expected::U8, self.coerce_numbers, visitor),
-            _ => Err(self.value.unexpected(Expected::U8)),
-        }
+        self.deserialize_number(Expected::U8, visitor)
     }
 
     fn deserialize_u16<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        match self.value {
-            Value::Number(n) => number::visit(n, Expected::U16, self.coerce_numbers, visitor),
-            _ => Err(self.value.unexpected(Expected::U16)),
-        }
+        self.deserialize_number(Expected::U16, visitor)
     }
 
     fn deserialize_u32<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        match self.value {
-            Value::Number(n) => number::visit(n, Expected::U32, self.coerce_numbers, visitor),
-            _ => Err(self.value.unexpected(Expected::U32)),
-        }
+        self.deserialize_number(Expected::U32, visitor)
     }
 
     fn deserialize_u64<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        match self.value {
-            Value::Number(n) => number::visit(n, Expected::U64, self.coerce_numbers, visitor),
-            _ => Err(self.value.unexpected(Expected::U64)),
-        }
+        self.deserialize_number(Expected::U64, visitor)
     }
 
     fn deserialize_u128<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        match self.value {
-            Value::Number(n) => number::visit(n, Expected::U128, self.coerce_numbers, visitor),
-            _ => Err(self.value.unexpected(Expected::U128)),
-        }
+        self.deserialize_number(Expected::U128, visitor)
     }
 
     fn deserialize_f32<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        match self.value {
-            Value::Number(n) => number::visit(n, Expected::F32, self.coerce_numbers, visitor),
-            _ => Err(self.value.unexpected(Expected::F32)),
-        }
+        self.deserialize_number(Expected::F32, visitor)
     }
 
     fn deserialize_f64<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        match self.value {
-            Value::Number(n) => number::visit(n, Expected::F64, self.coerce_numbers, visitor),
-            _ => Err(self.value.unexpected(Expected::F64)),
-        }
+        self.deserialize_number(Expected::F64, visitor)
     }
 
     fn deserialize_char<V>(self, visitor: V) -> Result<V::Value, Self::Error>
@@ -308,7 +721,19 @@ impl<'de> de::Deserializer<'de> for Deserializer<'de> {
     {
         match self.value {
             Value::Char(v) => visitor.visit_char(v),
-            _ => Err(self.value.unexpected(Expected::Char)),
+            Value::String(v) if self.options.config.coerce_strings => match v.parse() {
+                Ok(v) => visitor.visit_char(v),
+                Err(_) => self
+                    .options
+                    .recover(Value::String(v).unexpected(Expected::Char), || {
+                        Fallback::new(self.options.errors).deserialize_char(visitor)
+                    }),
+            },
+            _ => self
+                .options
+                .recover(self.value.unexpected(Expected::Char), || {
+                    Fallback::new(self.options.errors).deserialize_char(visitor)
+                }),
         }
     }
 
@@ -321,7 +746,20 @@ impl<'de> de::Deserializer<'de> for Deserializer<'de> {
                 Cow::Borrowed(v) => visitor.visit_borrowed_str(v),
                 Cow::Owned(v) => visitor.visit_string(v),
             },
-            _ => Err(self.value.unexpected(Expected::String)),
+            Value::Number(v) if self.options.config.coerce_strings => {
+                visitor.visit_string(v.to_plain_string())
+            }
+            Value::Bool(v) if self.options.config.coerce_strings => {
+                visitor.visit_string(v.to_string())
+            }
+            Value::Char(v) if self.options.config.coerce_strings => {
+                visitor.visit_string(v.to_string())
+            }
+            _ => self
+                .options
+                .recover(self.value.unexpected(Expected::String), || {
+                    Fallback::new(self.options.errors).deserialize_str(visitor)
+                }),
         }
     }
 
@@ -341,7 +779,34 @@ impl<'de> de::Deserializer<'de> for Deserializer<'de> {
                 Cow::Borrowed(v) => visitor.visit_borrowed_bytes(v),
                 Cow::Owned(v) => visitor.visit_byte_buf(v),
             },
-            _ => Err(self.value.unexpected(Expected::Bytes)),
+            Value::Seq(v) if self.options.config.coerce_bytes => match bytes::from_seq(&v) {
+                Some(v) => visitor.visit_byte_buf(v),
+                None => self
+                    .options
+                    .recover(Value::Seq(v).unexpected(Expected::Bytes), || {
+                        Fallback::new(self.options.errors).deserialize_bytes(visitor)
+                    }),
+            },
+            Value::String(v) if self.options.config.human_readable => {
+                match self
+                    .options
+                    .config
+                    .bytes_encoding
+                    .and_then(|e| e.decode(&v))
+                {
+                    Some(v) => visitor.visit_byte_buf(v),
+                    None => self
+                        .options
+                        .recover(Value::String(v).unexpected(Expected::Bytes), || {
+                            Fallback::new(self.options.errors).deserialize_bytes(visitor)
+                        }),
+                }
+            }
+            _ => self
+                .options
+                .recover(self.value.unexpected(Expected::Bytes), || {
+                    Fallback::new(self.options.errors).deserialize_bytes(visitor)
+                }),
         }
     }
 
@@ -364,6 +829,11 @@ impl<'de> de::Deserializer<'de> for Deserializer<'de> {
                 }
                 None => visitor.visit_none(),
             },
+            _ if self.options.config.strict => self
+                .options
+                .recover(self.value.unexpected(Expected::Option), || {
+                    Fallback::new(self.options.errors).deserialize_option(visitor)
+                }),
             _ => visitor.visit_some(self),
         }
     }
@@ -374,7 +844,14 @@ impl<'de> de::Deserializer<'de> for Deserializer<'de> {
     {
         match self.value {
             Value::Unit => visitor.visit_unit(),
-            _ => Err(self.value.unexpected(Expected::Unit)),
+            Value::String(v) if self.options.config.coerce_strings && v.is_empty() => {
+                visitor.visit_unit()
+            }
+            _ => self
+                .options
+                .recover(self.value.unexpected(Expected::Unit), || {
+                    Fallback::new(self.options.errors).deserialize_unit(visitor)
+                }),
         }
     }
 
@@ -386,30 +863,57 @@ impl<'de> de::Deserializer<'de> for Deserializer<'de> {
     where
         V: Visitor<'de>,
     {
+        let expected = || Expected::Struct {
+            name: Some(name.to_owned()),
+            typ: Some(DataType::Unit),
+        };
         match self.value {
+            Value::Struct(v) if self.options.config.strict && v.name != name => {
+                self.options.recover(v.unexpected(expected()), || {
+                    Fallback::new(self.options.errors).deserialize_unit_struct(name, visitor)
+                })
+            }
             Value::Struct(v) => match v.data {
                 Data::Unit => visitor.visit_unit(),
-                _ => Err(v.unexpected(Expected::Struct {
-                    name: Some(name.to_owned()),
-                    typ: Some(DataType::Unit),
-                })),
+                _ => self.options.recover(v.unexpected(expected()), || {
+                    Fallback::new(self.options.errors).deserialize_unit_struct(name, visitor)
+                }),
             },
-            Value::Unit => self.deserialize_unit(visitor),
-            _ => Err(self.value.unexpected(Expected::Struct {
-                name: Some(name.to_owned()),
-                typ: Some(DataType::Unit),
-            })),
+            Value::Unit if !self.options.config.strict => self.deserialize_unit(visitor),
+            _ => self.options.recover(self.value.unexpected(expected()), || {
+                Fallback::new(self.options.errors).deserialize_unit_struct(name, visitor)
+            }),
         }
     }
 
     fn deserialize_newtype_struct<V>(
         mut self,
-        _name: &'static str,
+        name: &'static str,
         visitor: V,
     ) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
+        if self.options.config.strict {
+            let expected = || Expected::Struct {
+                name: Some(name.to_owned()),
+                typ: Some(DataType::NewType),
+            };
+            return match self.value {
+                Value::Struct(v) if v.name == name => match v.data {
+                    Data::NewType { value } => {
+                        self.value = value;
+                        visitor.visit_newtype_struct(self)
+                    }
+                    _ => self.options.recover(v.unexpected(expected()), || {
+                        Fallback::new(self.options.errors).deserialize_newtype_struct(name, visitor)
+                    }),
+                },
+                _ => self.options.recover(self.value.unexpected(expected()), || {
+                    Fallback::new(self.options.errors).deserialize_newtype_struct(name, visitor)
+                }),
+            };
+        }
         match self.value {
             Value::Struct(v) => match v.data {
                 Data::NewType { value } => {
@@ -430,10 +934,23 @@ impl<'de> de::Deserializer<'de> for Deserializer<'de> {
         V: Visitor<'de>,
     {
         match self.value {
-            Value::Seq(v) => {
-                visitor.visit_seq(Seq::new(v, self.human_readable, self.coerce_numbers))
+            Value::Seq(v) => visitor.visit_seq(Seq::new(v, self.options)),
+            Value::Bytes(v) if self.options.config.coerce_bytes => {
+                visitor.visit_seq(Seq::new(bytes::to_seq(&v), self.options))
             }
-            _ => Err(self.value.unexpected(Expected::Seq)),
+            Value::Tuple(_) | Value::Struct(_) if self.options.config.coerce_shapes => {
+                match elements(self.value) {
+                    Ok(v) => visitor.visit_seq(Seq::new(v, self.options)),
+                    Err(value) => self.options.recover(value.unexpected(Expected::Seq), || {
+                        Fallback::new(self.options.errors).deserialize_seq(visitor)
+                    }),
+                }
+            }
+            _ => self
+                .options
+                .recover(self.value.unexpected(Expected::Seq), || {
+                    Fallback::new(self.options.errors).deserialize_seq(visitor)
+                }),
         }
     }
 
@@ -442,38 +959,78 @@ impl<'de> de::Deserializer<'de> for Deserializer<'de> {
         V: Visitor<'de>,
     {
         match self.value {
-            Value::Tuple(v) => {
-                visitor.visit_seq(Seq::new(v, self.human_readable, self.coerce_numbers))
+            Value::Tuple(v) if self.options.config.strict && v.len() != len => self
+                .options
+                .recover(Value::Tuple(v).unexpected(Expected::Tuple(len)), || {
+                    Fallback::new(self.options.errors).deserialize_tuple(len, visitor)
+                }),
+            Value::Tuple(v) => visitor.visit_seq(Seq::new(v, self.options)),
+            Value::Seq(_) if !self.options.config.strict => self.deserialize_seq(visitor),
+            Value::Bytes(v)
+                if self.options.config.coerce_bytes
+                    && (!self.options.config.strict || v.len() == len) =>
+            {
+                visitor.visit_seq(Seq::new(bytes::to_seq(&v), self.options))
+            }
+            Value::Seq(_) | Value::Struct(_) if self.options.config.coerce_shapes => {
+                match elements(self.value) {
+                    Ok(v) => visitor.visit_seq(Seq::new(v, self.options)),
+                    Err(value) => self
+                        .options
+                        .recover(value.unexpected(Expected::Tuple(len)), || {
+                            Fallback::new(self.options.errors).deserialize_tuple(len, visitor)
+                        }),
+                }
             }
-            Value::Seq(_) => self.deserialize_seq(visitor),
-            _ => Err(self.value.unexpected(Expected::Tuple(len))),
+            _ => self
+                .options
+                .recover(self.value.unexpected(Expected::Tuple(len)), || {
+                    Fallback::new(self.options.errors).deserialize_tuple(len, visitor)
+                }),
         }
     }
 
     fn deserialize_tuple_struct<V>(
         self,
         name: &'static str,
-        _len: usize,
+        len: usize,
         visitor: V,
     ) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
+        let expected = || Expected::Struct {
+            name: Some(name.to_owned()),
+            typ: Some(DataType::Tuple),
+        };
         match self.value {
+            Value::Struct(v) if self.options.config.strict && v.name != name => {
+                self.options.recover(v.unexpected(expected()), || {
+                    Fallback::new(self.options.errors).deserialize_tuple_struct(name, len, visitor)
+                })
+            }
             Value::Struct(v) => match v.data {
-                Data::Tuple { values } => {
-                    visitor.visit_seq(Seq::new(values, self.human_readable, self.coerce_numbers))
+                Data::Tuple { values } if self.options.config.strict && values.len() != len => {
+                    self.options.recover(
+                        Value::Tuple(values).unexpected(Expected::Tuple(len)),
+                        || {
+                            Fallback::new(self.options.errors)
+                                .deserialize_tuple_struct(name, len, visitor)
+                        },
+                    )
                 }
-                _ => Err(v.unexpected(Expected::Struct {
-                    name: Some(name.to_owned()),
-                    typ: Some(DataType::Tuple),
-                })),
+                Data::Tuple { values } => visitor.visit_seq(Seq::new(values, self.options)),
+                _ => self.options.recover(v.unexpected(expected()), || {
+                    Fallback::new(self.options.errors).deserialize_tuple_struct(name, len, visitor)
+                }),
             },
-            Value::Seq(_) => self.deserialize_seq(visitor),
-            _ => Err(self.value.unexpected(Expected::Struct {
-                name: Some(name.to_owned()),
-                typ: Some(DataType::Tuple),
-            })),
+            Value::Seq(_) if !self.options.config.strict => self.deserialize_seq(visitor),
+            Value::Seq(v) | Value::Tuple(v) if self.options.config.coerce_shapes => {
+                visitor.visit_seq(Seq::new(v, self.options))
+            }
+            _ => self.options.recover(self.value.unexpected(expected()), || {
+                Fallback::new(self.options.errors).deserialize_tuple_struct(name, len, visitor)
+            }),
         }
     }
 
@@ -481,81 +1038,162 @@ impl<'de> de::Deserializer<'de> for Deserializer<'de> {
     where
         V: Visitor<'de>,
     {
+        let options = Options {
+            markers: true,
+            ..self.options
+        };
         match self.value {
-            Value::Map(v) => {
-                visitor.visit_map(Map::from((v, self.human_readable, self.coerce_numbers)))
-            }
-            _ => Err(self.value.unexpected(Expected::Map)),
+            Value::Map(v) => visitor.visit_map(Map::from((v, options))),
+            Value::Struct(v) if self.options.config.coerce_shapes => match v.data {
+                Data::Struct { fields } => visitor.visit_map(Map::from((fields, options))),
+                _ => self.options.recover(v.unexpected(Expected::Map), || {
+                    Fallback::new(self.options.errors).deserialize_map(visitor)
+                }),
+            },
+            _ => self
+                .options
+                .recover(self.value.unexpected(Expected::Map), || {
+                    Fallback::new(self.options.errors).deserialize_map(visitor)
+                }),
         }
     }
 
     fn deserialize_struct<V>(
         self,
         name: &'static str,
-        _fields: &'static [&'static str],
+        fields: &'static [&'static str],
         visitor: V,
     ) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
+        let expected = || Expected::Struct {
+            name: Some(name.to_owned()),
+            typ: Some(DataType::Struct),
+        };
         match self.value {
+            Value::Struct(v) if self.options.config.strict && v.name != name => {
+                self.options.recover(v.unexpected(expected()), || {
+                    Fallback::new(self.options.errors).deserialize_struct(name, fields, visitor)
+                })
+            }
             Value::Struct(v) => match v.data {
-                Data::Struct { fields } => visitor.visit_map(Map::from((
-                    fields,
-                    self.human_readable,
-                    self.coerce_numbers,
-                ))),
-                _ => Err(v.unexpected(Expected::Struct {
-                    name: Some(name.to_owned()),
-                    typ: Some(DataType::Struct),
-                })),
+                Data::Struct { fields: values } => {
+                    let values = self.options.fields(values, fields);
+                    if self.options.config.strict {
+                        if let Err(error) = check_fields(&values, fields) {
+                            self.options.report(error)?;
+                        }
+                    }
+                    let missing = self
+                        .options
+                        .missing(values.iter().filter_map(field), fields);
+                    let map = Map::from((values, self.options)).missing(missing);
+                    visitor
+                        .visit_map(map)
+                        .map_err(|error| error.in_struct(name))
+                }
+                Data::Tuple { values } if self.options.config.coerce_shapes => {
+                    visitor.visit_seq(Seq::new(values, self.options))
+                }
+                _ => self.options.recover(v.unexpected(expected()), || {
+                    Fallback::new(self.options.errors).deserialize_struct(name, fields, visitor)
+                }),
             },
-            Value::Map(_) => self.deserialize_map(visitor),
-            _ => Err(self.value.unexpected(Expected::Struct {
-                name: Some(name.to_owned()),
-                typ: Some(DataType::Struct),
-            })),
+            Value::Map(map) if !self.options.config.strict => {
+                let map = self.options.keys(map, fields);
+                let missing = self.options.missing(map.iter().filter_map(key), fields);
+                let map = Map::from((map, self.options)).missing(missing);
+                visitor
+                    .visit_map(map)
+                    .map_err(|error| error.in_struct(name))
+            }
+            Value::Seq(v) | Value::Tuple(v) if self.options.config.coerce_shapes => {
+                visitor.visit_seq(Seq::new(v, self.options))
+            }
+            _ => self.options.recover(self.value.unexpected(expected()), || {
+                Fallback::new(self.options.errors).deserialize_struct(name, fields, visitor)
+            }),
         }
     }
 
     fn deserialize_enum<V>(
         self,
         name: &'static str,
-        _variants: &'static [&'static str],
+        variants: &'static [&'static str],
         visitor: V,
     ) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        match self.value {
-            Value::Enum(v) => r#enum::visit_enum(
-                Cow::Borrowed(name),
-                v,
-                self.human_readable,
-                self.coerce_numbers,
-                visitor,
-            ),
-            Value::String(string) => visitor.visit_enum(r#enum::Access {
+        let expected = || Expected::Enum {
+            name: Some(name.to_owned()),
+            typ: None,
+        };
+        let access = |variant, data| -> Result<_, Error> {
+            Ok(r#enum::Access {
                 expected: name,
-                name: Value::String(string),
-                data: None,
-                human_readable: self.human_readable,
-                coerce_numbers: self.coerce_numbers,
-            }),
-            Value::Map(mut map) if map.len() == 1 => {
-                let (variant, data) = map.pop().unwrap();
-                visitor.visit_enum(r#enum::Access {
-                    expected: name,
-                    name: variant,
-                    data: Some(data),
-                    human_readable: self.human_readable,
-                    coerce_numbers: self.coerce_numbers,
+                name: self
+                    .options
+                    .identifier(r#enum::variant_name(variant, name, variants)?, variants),
+                data,
+                options: self.options,
+            })
+        };
+        match self.value {
+            Value::Enum(v) if self.options.config.strict && v.name != name => {
+                self.options.recover(v.unexpected(expected()), || {
+                    Fallback::new(self.options.errors).deserialize_enum(name, variants, visitor)
                 })
             }
-            _ => Err(self.value.unexpected(Expected::Enum {
-                name: Some(name.to_owned()),
-                typ: None,
-            })),
+            Value::Enum(mut v) => {
+                if self.options.config.loose_identifiers {
+                    v.variant = identifier::resolve(v.variant, variants);
+                }
+                r#enum::visit_enum(Cow::Borrowed(name), v, self.options, visitor)
+            }
+            _ if self.options.config.strict => {
+                self.options.recover(self.value.unexpected(expected()), || {
+                    Fallback::new(self.options.errors).deserialize_enum(name, variants, visitor)
+                })
+            }
+            Value::String(_) | Value::Number(_) => visitor.visit_enum(access(self.value, None)?),
+            Value::Map(map) => match self.options.config.adjacent_tags {
+                Some((tag, content)) if r#enum::is_adjacent(map.iter().map(key), tag, content) => {
+                    let entries = map
+                        .into_iter()
+                        .map(|(k, v)| (matches!(k, Value::String(k) if k == tag), v));
+                    let (variant, data) = r#enum::split_adjacent(entries);
+                    visitor.visit_enum(access(variant, data)?)
+                }
+                _ if map.len() == 1 => {
+                    let (variant, data) = map.into_iter().next().unwrap();
+                    visitor.visit_enum(access(variant, Some(data))?)
+                }
+                _ => self
+                    .options
+                    .recover(Value::Map(map).unexpected(expected()), || {
+                        Fallback::new(self.options.errors).deserialize_enum(name, variants, visitor)
+                    }),
+            },
+            Value::Struct(v) => match (self.options.config.adjacent_tags, v.data) {
+                (Some((tag, content)), Data::Struct { fields })
+                    if r#enum::is_adjacent(fields.iter().map(field), tag, content) =>
+                {
+                    let entries = fields.into_iter().map(|(k, v)| (k == tag, v));
+                    let (variant, data) = r#enum::split_adjacent(entries);
+                    visitor.visit_enum(access(variant, data)?)
+                }
+                (_, data) => {
+                    let value = Value::Struct(Box::new(crate::Struct { name: v.name, data }));
+                    self.options.recover(value.unexpected(expected()), || {
+                        Fallback::new(self.options.errors).deserialize_enum(name, variants, visitor)
+                    })
+                }
+            },
+            _ => self.options.recover(self.value.unexpected(expected()), || {
+                Fallback::new(self.options.errors).deserialize_enum(name, variants, visitor)
+            }),
         }
     }
 
@@ -572,7 +1210,19 @@ impl<'de> de::Deserializer<'de> for Deserializer<'de> {
                 Cow::Borrowed(v) => visitor.visit_borrowed_str(v),
                 Cow::Owned(v) => visitor.visit_string(v),
             },
-            _ => Err(self.value.unexpected(Expected::Identifier)),
+            Value::Number(n) => match r#enum::index(n) {
+                Some(index) => visitor.visit_u64(index as u64),
+                None => self
+                    .options
+                    .recover(self.value.unexpected(Expected::Identifier), || {
+                        Fallback::new(self.options.errors).deserialize_identifier(visitor)
+                    }),
+            },
+            _ => self
+                .options
+                .recover(self.value.unexpected(Expected::Identifier), || {
+                    Fallback::new(self.options.errors).deserialize_identifier(visitor)
+                }),
         }
     }
 
@@ -580,11 +1230,55 @@ impl<'de> de::Deserializer<'de> for Deserializer<'de> {
     where
         V: Visitor<'de>,
     {
+        if let Some(ignored) = self.options.ignored {
+            ignored.record(self.value);
+        }
         visitor.visit_unit()
     }
 
     fn is_human_readable(&self) -> bool {
-        self.human_readable
+        self.options.config.human_readable
+    }
+}
+
+/// Returns the elements of sequences, tuples and tuple structs.
+///
+/// Any other value is given back as the error.
+fn elements(value: Value<'_>) -> Result<Vec<Value<'_>>, Value<'_>> {
+    match value {
+        Value::Seq(v) | Value::Tuple(v) => Ok(v),
+        Value::Struct(v) => match v.data {
+            Data::Tuple { values } => Ok(values),
+            data => Err(Value::Struct(Box::new(crate::Struct { data, ..*v }))),
+        },
+        value => Err(value),
+    }
+}
+
+/// Returns the key of a map entry if it's a string.
+fn key<'a>(entry: &'a (Value<'_>, Value<'_>)) -> Option<&'a str> {
+    match &entry.0 {
+        Value::String(key) => Some(key),
+        _ => None,
+    }
+}
+
+/// Returns the name of a struct field.
+fn field<'a>(entry: &'a (Cow<'static, str>, Value<'_>)) -> Option<&'a str> {
+    Some(&entry.0)
+}
+
+/// Checks that object-like struct or enum variant data only has the expected fields.
+fn check_fields(
+    values: &[(Cow<'static, str>, Value<'_>)],
+    fields: &'static [&'static str],
+) -> Result<(), Error> {
+    match values
+        .iter()
+        .find(|(key, _)| !fields.contains(&key.as_ref()))
+    {
+        Some((key, _)) => Err(de::Error::unknown_field(key, fields)),
+        None => Ok(()),
     }
 }
 
@@ -600,6 +1294,9 @@ impl<'de> de::Deserialize<'de> for Value<'static> {
 
 /// A Serde visitor that deserializes any value into [crate::Value].
 ///
+/// To share equal strings between the values deserialized, use [crate::Interner::deserialize]
+/// instead.
+///
 /// ## Example
 ///
 /// ```
@@ -770,15 +1467,14 @@ impl<'de> Visitor<'de> for ValueVisitor {
     where
         D: de::Deserializer<'de>,
     {
-        Deserialize::deserialize(deserializer)
+        marker::decode(Deserialize::deserialize(deserializer)?)
     }
 
     fn visit_seq<V>(self, mut visitor: V) -> Result<Self::Value, V::Error>
     where
         V: SeqAccess<'de>,
     {
-        let len = visitor.size_hint().unwrap_or_default();
-        let mut vec = Vec::with_capacity(len);
+        let mut vec = Vec::with_capacity(limits::capacity::<Value>(visitor.size_hint()));
         while let Some(e) = visitor.next_element()? {
             vec.push(e);
         }
@@ -789,8 +1485,7 @@ impl<'de> Visitor<'de> for ValueVisitor {
     where
         V: MapAccess<'de>,
     {
-        let len = visitor.size_hint().unwrap_or_default();
-        let mut vec = Vec::with_capacity(len);
+        let mut vec = Vec::with_capacity(limits::capacity::<(Value, Value)>(visitor.size_hint()));
         while let Some(kv) = visitor.next_entry()? {
             vec.push(kv);
         }
@@ -1,7 +1,9 @@
 use crate::Error;
 use crate::Expected;
+use crate::Found;
 use crate::Number;
 use crate::Unexpected;
+use alloc::borrow::Cow;
 use serde::de;
 use serde::Deserialize;
 
@@ -130,50 +132,50 @@ macro_rules! range {
 }
 
 macro_rules! coerce_int {
-    ($number:ident, $v:ident, $expected:ident, $visitor:ident) => {{
+    ($number:ident, $v:ident, $expected:ident) => {{
         match $expected {
-            Expected::I8 => {
-                $visitor.visit_i8($v.try_into().map_err(|_| $number.unexpected($expected))?)
-            }
-            Expected::U8 => {
-                $visitor.visit_u8($v.try_into().map_err(|_| $number.unexpected($expected))?)
-            }
-            Expected::I16 => {
-                $visitor.visit_i16($v.try_into().map_err(|_| $number.unexpected($expected))?)
-            }
-            Expected::U16 => {
-                $visitor.visit_u16($v.try_into().map_err(|_| $number.unexpected($expected))?)
-            }
-            Expected::I32 => {
-                $visitor.visit_i32($v.try_into().map_err(|_| $number.unexpected($expected))?)
-            }
+            Expected::I8 => Ok(Number::I8(
+                $v.try_into().map_err(|_| $number.unexpected($expected))?,
+            )),
+            Expected::U8 => Ok(Number::U8(
+                $v.try_into().map_err(|_| $number.unexpected($expected))?,
+            )),
+            Expected::I16 => Ok(Number::I16(
+                $v.try_into().map_err(|_| $number.unexpected($expected))?,
+            )),
+            Expected::U16 => Ok(Number::U16(
+                $v.try_into().map_err(|_| $number.unexpected($expected))?,
+            )),
+            Expected::I32 => Ok(Number::I32(
+                $v.try_into().map_err(|_| $number.unexpected($expected))?,
+            )),
             Expected::F32
                 if i32::try_from($v).is_ok()
                     && range!(i16, u16 as i32).contains(&i32::try_from($v).unwrap()) =>
             {
-                $visitor.visit_f32($v as f32)
-            }
-            Expected::U32 => {
-                $visitor.visit_u32($v.try_into().map_err(|_| $number.unexpected($expected))?)
-            }
-            Expected::I64 => {
-                $visitor.visit_i64($v.try_into().map_err(|_| $number.unexpected($expected))?)
+                Ok(Number::F32($v as f32))
             }
+            Expected::U32 => Ok(Number::U32(
+                $v.try_into().map_err(|_| $number.unexpected($expected))?,
+            )),
+            Expected::I64 => Ok(Number::I64(
+                $v.try_into().map_err(|_| $number.unexpected($expected))?,
+            )),
             Expected::F64
                 if i64::try_from($v).is_ok()
                     && range!(i32, u32 as i64).contains(&i64::try_from($v).unwrap()) =>
             {
-                $visitor.visit_f64($v as f64)
-            }
-            Expected::U64 => {
-                $visitor.visit_u64($v.try_into().map_err(|_| $number.unexpected($expected))?)
-            }
-            Expected::I128 => {
-                $visitor.visit_i128($v.try_into().map_err(|_| $number.unexpected($expected))?)
-            }
-            Expected::U128 => {
-                $visitor.visit_u128($v.try_into().map_err(|_| $number.unexpected($expected))?)
+                Ok(Number::F64($v as f64))
             }
+            Expected::U64 => Ok(Number::U64(
+                $v.try_into().map_err(|_| $number.unexpected($expected))?,
+            )),
+            Expected::I128 => Ok(Number::I128(
+                $v.try_into().map_err(|_| $number.unexpected($expected))?,
+            )),
+            Expected::U128 => Ok(Number::U128(
+                $v.try_into().map_err(|_| $number.unexpected($expected))?,
+            )),
             _ => Err($number.unexpected($expected)),
         }
     }};
@@ -195,70 +197,117 @@ impl Expected {
     }
 }
 
-pub(super) fn visit<'de, V>(
+/// Converts `number` into the `expected` number type.
+///
+/// Numbers of other types are only converted if `coerce_numbers` is set and the value fits.
+pub(super) fn convert(
     number: Number,
     expected: Expected,
     coerce_numbers: bool,
-    visitor: V,
-) -> Result<V::Value, Error>
+) -> Result<Number, Error> {
+    match (number, &expected) {
+        (Number::I8(_), Expected::I8)
+        | (Number::U8(_), Expected::U8)
+        | (Number::I16(_), Expected::I16)
+        | (Number::U16(_), Expected::U16)
+        | (Number::I32(_), Expected::I32)
+        | (Number::U32(_), Expected::U32)
+        | (Number::F32(_), Expected::F32)
+        | (Number::I64(_), Expected::I64)
+        | (Number::U64(_), Expected::U64)
+        | (Number::F64(_), Expected::F64)
+        | (Number::I128(_), Expected::I128)
+        | (Number::U128(_), Expected::U128) => Ok(number),
+        _ if !coerce_numbers => Err(number.unexpected(expected)),
+        _ => match (number, expected.number_type()) {
+            (Number::I8(v), _) => coerce_int!(number, v, expected),
+            (Number::U8(v), _) => coerce_int!(number, v, expected),
+            (Number::I16(v), _) => coerce_int!(number, v, expected),
+            (Number::U16(v), _) => coerce_int!(number, v, expected),
+            (Number::I32(v), _) => coerce_int!(number, v, expected),
+            (Number::U32(v), _) => coerce_int!(number, v, expected),
+            (Number::F32(v), NumberType::F64) => Ok(Number::F64(v as f64)),
+            (Number::I64(v), _) => coerce_int!(number, v, expected),
+            (Number::U64(v), _) => coerce_int!(number, v, expected),
+            (Number::F64(v), NumberType::F32) if range!(f32 as f64).contains(&v) => {
+                Ok(Number::F32(v as f32))
+            }
+            (Number::I128(v), _) => coerce_int!(number, v, expected),
+            (Number::U128(v), _) => coerce_int!(number, v, expected),
+            _ => Err(number.unexpected(expected)),
+        },
+    }
+}
+
+/// Passes `number` to the visitor method of its type.
+pub(super) fn visit<'de, V>(number: Number, visitor: V) -> Result<V::Value, Error>
 where
     V: de::Visitor<'de>,
 {
-    match (number, expected) {
-        (Number::I8(v), Expected::I8) => visitor.visit_i8(v),
-        (Number::U8(v), Expected::U8) => visitor.visit_u8(v),
-        (Number::I16(v), Expected::I16) => visitor.visit_i16(v),
-        (Number::U16(v), Expected::U16) => visitor.visit_u16(v),
-        (Number::I32(v), Expected::I32) => visitor.visit_i32(v),
-        (Number::U32(v), Expected::U32) => visitor.visit_u32(v),
-        (Number::F32(v), Expected::F32) => visitor.visit_f32(v),
-        (Number::I64(v), Expected::I64) => visitor.visit_i64(v),
-        (Number::U64(v), Expected::U64) => visitor.visit_u64(v),
-        (Number::F64(v), Expected::F64) => visitor.visit_f64(v),
-        (Number::I128(v), Expected::I128) => visitor.visit_i128(v),
-        (Number::U128(v), Expected::U128) => visitor.visit_u128(v),
-        (number, expected) => {
-            if !coerce_numbers {
-                return Err(number.unexpected(expected));
-            }
+    match number {
+        Number::I8(v) => visitor.visit_i8(v),
+        Number::U8(v) => visitor.visit_u8(v),
+        Number::I16(v) => visitor.visit_i16(v),
+        Number::U16(v) => visitor.visit_u16(v),
+        Number::I32(v) => visitor.visit_i32(v),
+        Number::U32(v) => visitor.visit_u32(v),
+        Number::F32(v) => visitor.visit_f32(v),
+        Number::I64(v) => visitor.visit_i64(v),
+        Number::U64(v) => visitor.visit_u64(v),
+        Number::F64(v) => visitor.visit_f64(v),
+        Number::I128(v) => visitor.visit_i128(v),
+        Number::U128(v) => visitor.visit_u128(v),
+    }
+}
 
-            match (number, expected.number_type()) {
-                (Number::I8(v), _) => {
-                    coerce_int!(number, v, expected, visitor)
-                }
-                (Number::U8(v), _) => {
-                    coerce_int!(number, v, expected, visitor)
-                }
-                (Number::I16(v), _) => {
-                    coerce_int!(number, v, expected, visitor)
-                }
-                (Number::U16(v), _) => {
-                    coerce_int!(number, v, expected, visitor)
-                }
-                (Number::I32(v), _) => {
-                    coerce_int!(number, v, expected, visitor)
-                }
-                (Number::U32(v), _) => {
-                    coerce_int!(number, v, expected, visitor)
-                }
-                (Number::F32(v), NumberType::F64) => visitor.visit_f64(v as f64),
-                (Number::I64(v), _) => {
-                    coerce_int!(number, v, expected, visitor)
-                }
-                (Number::U64(v), _) => {
-                    coerce_int!(number, v, expected, visitor)
-                }
-                (Number::F64(v), NumberType::F32) if range!(f32 as f64).contains(&v) => {
-                    visitor.visit_f32(v as f32)
-                }
-                (Number::I128(v), _) => {
-                    coerce_int!(number, v, expected, visitor)
-                }
-                (Number::U128(v), _) => {
-                    coerce_int!(number, v, expected, visitor)
-                }
-                _ => Err(number.unexpected(expected)),
+/// Returns zero as the `expected` number type.
+pub(super) const fn zero(expected: &Expected) -> Number {
+    match expected {
+        Expected::I8 => Number::I8(0),
+        Expected::U8 => Number::U8(0),
+        Expected::I16 => Number::I16(0),
+        Expected::U16 => Number::U16(0),
+        Expected::I32 => Number::I32(0),
+        Expected::U32 => Number::U32(0),
+        Expected::F32 => Number::F32(0.0),
+        Expected::I64 => Number::I64(0),
+        Expected::U64 => Number::U64(0),
+        Expected::F64 => Number::F64(0.0),
+        Expected::I128 => Number::I128(0),
+        _ => Number::U128(0),
+    }
+}
+
+/// Parses a number of the `expected` type from a string.
+pub(super) fn parse(string: Cow<'_, str>, expected: Expected) -> Result<Number, Error> {
+    macro_rules! parse {
+        ($ty:ty, $variant:ident) => {
+            match string.parse::<$ty>() {
+                Ok(v) => Ok(Number::$variant(v)),
+                Err(_) => Err(Error::unexpected(
+                    Found::String(string.into_owned()),
+                    expected,
+                )),
             }
-        }
+        };
+    }
+
+    match expected {
+        Expected::I8 => parse!(i8, I8),
+        Expected::U8 => parse!(u8, U8),
+        Expected::I16 => parse!(i16, I16),
+        Expected::U16 => parse!(u16, U16),
+        Expected::I32 => parse!(i32, I32),
+        Expected::U32 => parse!(u32, U32),
+        Expected::F32 => parse!(f32, F32),
+        Expected::I64 => parse!(i64, I64),
+        Expected::U64 => parse!(u64, U64),
+        Expected::F64 => parse!(f64, F64),
+        Expected::I128 => parse!(i128, I128),
+        Expected::U128 => parse!(u128, U128),
+        _ => Err(Error::unexpected(
+            Found::String(string.into_owned()),
+            expected,
+        )),
     }
 }
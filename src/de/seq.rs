@@ -1,27 +1,29 @@
-use crate::Deserializer;
+use crate::de::Options;
+use crate::de::ValueDeserializer;
+use crate::path::Segment;
 use crate::Error;
 use crate::Value;
 use alloc::vec::IntoIter;
 use alloc::vec::Vec;
 use serde::de;
 
-pub(super) struct Seq<'de> {
+pub(super) struct Seq<'de, 'c> {
     iter: IntoIter<Value<'de>>,
-    human_readable: bool,
-    coerce_numbers: bool,
+    index: usize,
+    options: Options<'c>,
 }
 
-impl<'de> Seq<'de> {
-    pub(super) fn new(vec: Vec<Value<'de>>, human_readable: bool, coerce_numbers: bool) -> Self {
+impl<'de, 'c> Seq<'de, 'c> {
+    pub(super) fn new(vec: Vec<Value<'de>>, options: Options<'c>) -> Self {
         Self {
-            human_readable,
-            coerce_numbers,
+            options,
             iter: vec.into_iter(),
+            index: 0,
         }
     }
 }
 
-impl<'de> de::SeqAccess<'de> for Seq<'de> {
+impl<'de, 'c> de::SeqAccess<'de> for Seq<'de, 'c> {
     type Error = Error;
 
     fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
@@ -30,12 +32,15 @@ impl<'de> de::SeqAccess<'de> for Seq<'de> {
     {
         match self.iter.next() {
             Some(value) => {
-                let deserializer = Deserializer {
+                let deserializer = ValueDeserializer {
                     value,
-                    human_readable: self.human_readable,
-                    coerce_numbers: self.coerce_numbers,
+                    options: self.options,
                 };
-                seed.deserialize(deserializer).map(Some)
+                let segment = self.options.segment(|| Segment::Index(self.index));
+                self.index += 1;
+                self.options
+                    .nested(segment, || seed.deserialize(deserializer))
+                    .map(Some)
             }
             None => Ok(None),
         }
@@ -32,13 +32,13 @@ fn deserialize_i8() {
     assert_eq!(
         0i8,
         Deserializer::new(Value::Number(Number::I8(0)))
-            .deserialize()
+            .deserialize::<i8>()
             .unwrap()
     );
     assert_eq!(
         1i8,
         Deserializer::new(Value::Number(Number::I8(1)))
-            .deserialize()
+            .deserialize::<i8>()
             .unwrap()
     );
 }
@@ -48,13 +48,13 @@ fn deserialize_i16() {
     assert_eq!(
         0i16,
         Deserializer::new(Value::Number(Number::I16(0)))
-            .deserialize()
+            .deserialize::<i16>()
             .unwrap()
     );
     assert_eq!(
         1i16,
         Deserializer::new(Value::Number(Number::I16(1)))
-            .deserialize()
+            .deserialize::<i16>()
             .unwrap()
     );
 }
@@ -64,13 +64,13 @@ fn deserialize_i32() {
     assert_eq!(
         0i32,
         Deserializer::new(Value::Number(Number::I32(0)))
-            .deserialize()
+            .deserialize::<i32>()
             .unwrap()
     );
     assert_eq!(
         1i32,
         Deserializer::new(Value::Number(Number::I32(1)))
-            .deserialize()
+            .deserialize::<i32>()
             .unwrap()
     );
 }
@@ -80,13 +80,13 @@ fn deserialize_i64() {
     assert_eq!(
         0i64,
         Deserializer::new(Value::Number(Number::I64(0)))
-            .deserialize()
+            .deserialize::<i64>()
             .unwrap()
     );
     assert_eq!(
         1i64,
         Deserializer::new(Value::Number(Number::I64(1)))
-            .deserialize()
+            .deserialize::<i64>()
             .unwrap()
     );
 }
@@ -112,13 +112,13 @@ fn deserialize_u8() {
     assert_eq!(
         0u8,
         Deserializer::new(Value::Number(Number::U8(0)))
-            .deserialize()
+            .deserialize::<u8>()
             .unwrap()
     );
     assert_eq!(
         1u8,
         Deserializer::new(Value::Number(Number::U8(1)))
-            .deserialize()
+            .deserialize::<u8>()
             .unwrap()
     );
 }
@@ -128,13 +128,13 @@ fn deserialize_u16() {
     assert_eq!(
         0u16,
         Deserializer::new(Value::Number(Number::U16(0)))
-            .deserialize()
+            .deserialize::<u16>()
             .unwrap()
     );
     assert_eq!(
         1u16,
         Deserializer::new(Value::Number(Number::U16(1)))
-            .deserialize()
+            .deserialize::<u16>()
             .unwrap()
     );
 }
@@ -144,13 +144,13 @@ fn deserialize_u32() {
     assert_eq!(
         0u32,
         Deserializer::new(Value::Number(Number::U32(0)))
-            .deserialize()
+            .deserialize::<u32>()
             .unwrap()
     );
     assert_eq!(
         1u32,
         Deserializer::new(Value::Number(Number::U32(1)))
-            .deserialize()
+            .deserialize::<u32>()
             .unwrap()
     );
 }
@@ -160,13 +160,13 @@ fn deserialize_u64() {
     assert_eq!(
         0u64,
         Deserializer::new(Value::Number(Number::U64(0)))
-            .deserialize()
+            .deserialize::<u64>()
             .unwrap()
     );
     assert_eq!(
         1u64,
         Deserializer::new(Value::Number(Number::U64(1)))
-            .deserialize()
+            .deserialize::<u64>()
             .unwrap()
     );
 }
@@ -192,13 +192,13 @@ fn deserialize_f32() {
     assert_eq!(
         0f32,
         Deserializer::new(Value::Number(Number::F32(0.0)))
-            .deserialize()
+            .deserialize::<f32>()
             .unwrap()
     );
     assert_eq!(
         1f32,
         Deserializer::new(Value::Number(Number::F32(1.0)))
-            .deserialize()
+            .deserialize::<f32>()
             .unwrap()
     );
 }
@@ -208,13 +208,13 @@ fn deserialize_f64() {
     assert_eq!(
         0f64,
         Deserializer::new(Value::Number(Number::F64(0.0)))
-            .deserialize()
+            .deserialize::<f64>()
             .unwrap()
     );
     assert_eq!(
         1f64,
         Deserializer::new(Value::Number(Number::F64(1.0)))
-            .deserialize()
+            .deserialize::<f64>()
             .unwrap()
     );
 }
@@ -430,13 +430,13 @@ fn deserialize_seq() {
     assert_eq!(
         Vec::<bool>::new(),
         Deserializer::new(Value::Seq(Vec::new()))
-            .deserialize::<Vec<_>>()
+            .deserialize::<Vec<bool>>()
             .unwrap()
     );
     assert_eq!(
         vec![true, false],
         Deserializer::new(Value::Seq(vec![Value::Bool(true), Value::Bool(false)]))
-            .deserialize::<Vec<_>>()
+            .deserialize::<Vec<bool>>()
             .unwrap()
     );
 }
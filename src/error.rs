@@ -1,5 +1,6 @@
 use crate::DataType;
 use crate::Number;
+use crate::Value;
 use alloc::boxed::Box;
 use alloc::format;
 use alloc::string::String;
@@ -25,6 +26,51 @@ impl Error {
         }
     }
 
+    /// Creates a new duplicate field error
+    pub(crate) fn duplicate_field(field: &str) -> Self {
+        Self {
+            kind: Box::new(ErrorKind::DuplicateField(field.to_string())),
+        }
+    }
+
+    /// Creates a new custom error
+    pub(crate) fn custom(msg: impl fmt::Display) -> Self {
+        Self {
+            kind: Box::new(ErrorKind::Custom(msg.to_string())),
+        }
+    }
+
+    /// Creates a new syntax error
+    pub(crate) fn syntax(message: &str, line: usize, column: usize) -> Self {
+        Self {
+            kind: Box::new(ErrorKind::Syntax {
+                message: message.to_string(),
+                line,
+                column,
+            }),
+        }
+    }
+
+    /// Creates a new limit exceeded error
+    pub(crate) fn limit_exceeded(limit: Limit) -> Self {
+        Self {
+            kind: Box::new(ErrorKind::LimitExceeded(limit)),
+        }
+    }
+
+    /// Names the struct of a missing field error that doesn't have one yet.
+    #[cfg(feature = "serde")]
+    pub(crate) fn in_struct(mut self, name: &str) -> Self {
+        if let ErrorKind::MissingField {
+            struct_name: struct_name @ None,
+            ..
+        } = &mut *self.kind
+        {
+            *struct_name = Some(name.to_string());
+        }
+        self
+    }
+
     /// Borrows the underlying error kind
     pub fn kind(&self) -> &ErrorKind {
         &self.kind
@@ -50,12 +96,91 @@ pub enum ErrorKind {
     },
     /// A custom error message from `serde`.
     Custom(String),
+    /// A struct or enum variant was built with the same field name more than once.
+    DuplicateField(String),
+    /// A struct or enum variant was missing a required field when deserialising.
+    MissingField {
+        /// The name of the struct, or of the enum for enum variants, if known.
+        struct_name: Option<String>,
+        /// The name of the missing field.
+        field: String,
+    },
+    /// A struct or enum variant had a field it doesn't know about when deserialising.
+    UnknownField {
+        /// The name of the unknown field.
+        field: String,
+        /// The names of the fields that were expected.
+        expected: Vec<String>,
+    },
+    /// A value went over one of the [Limits](crate::Limits) set on a serializer or deserializer.
+    LimitExceeded(Limit),
+    /// Text being parsed, like [JSON](crate::json::from_str), was malformed.
+    Syntax {
+        /// What was wrong with the text.
+        message: String,
+        /// The line of the text the error was found on, starting from 1.
+        line: usize,
+        /// The column of the line the error was found at, in characters, starting from 1.
+        column: usize,
+    },
+}
+
+/// The limit that was exceeded, along with its configured maximum.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "derive", derive(serde::Serialize, serde::Deserialize))]
+pub enum Limit {
+    /// The maximum nesting depth.
+    Depth(usize),
+    /// The maximum number of elements, map entries and fields in total.
+    Elements(usize),
+    /// The maximum length in bytes of a single string or byte array.
+    StringLength(usize),
+    /// The maximum number of bytes in strings, byte arrays and field names in total.
+    TotalBytes(usize),
+}
+
+impl fmt::Display for Limit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Limit::Depth(max) => write!(f, "the maximum depth of {max}"),
+            Limit::Elements(max) => write!(f, "the maximum of {max} elements"),
+            Limit::StringLength(max) => write!(f, "the maximum string length of {max} bytes"),
+            Limit::TotalBytes(max) => write!(f, "the maximum of {max} bytes in total"),
+        }
+    }
 }
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match &*self.kind {
             ErrorKind::Custom(msg) => write!(f, "{msg}"),
+            ErrorKind::DuplicateField(field) => write!(f, "duplicate field `{field}`"),
+            ErrorKind::MissingField {
+                struct_name: Some(name),
+                field,
+            } => write!(f, "missing field `{field}` in `{name}`"),
+            ErrorKind::MissingField {
+                struct_name: None,
+                field,
+            } => write!(f, "missing field `{field}`"),
+            ErrorKind::UnknownField { field, expected } => match expected.as_slice() {
+                [] => write!(f, "unknown field `{field}`, there are no fields"),
+                [a] => write!(f, "unknown field `{field}`, expected `{a}`"),
+                [a, b] => write!(f, "unknown field `{field}`, expected `{a}` or `{b}`"),
+                [first, rest @ ..] => {
+                    write!(f, "unknown field `{field}`, expected one of `{first}`")?;
+                    for name in rest {
+                        write!(f, ", `{name}`")?;
+                    }
+                    Ok(())
+                }
+            },
+            ErrorKind::LimitExceeded(limit) => write!(f, "exceeded {limit}"),
+            ErrorKind::Syntax {
+                message,
+                line,
+                column,
+            } => write!(f, "{message} at line {line} column {column}"),
             ErrorKind::Unexpected { found, expected } => write!(
                 f,
                 "failed to deserialize; expected {expected}, found {found}"
@@ -67,15 +192,77 @@ impl fmt::Display for Error {
 #[cfg(feature = "std")]
 impl std::error::Error for Error {}
 
+/// All the errors found by [Deserializer::deserialize_all](crate::Deserializer::deserialize_all).
+///
+/// Each error comes with the path of the value it was found in, like `users[0].name`. The path of
+/// an error in the value being deserialized itself is empty.
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "derive", derive(serde::Serialize, serde::Deserialize))]
+pub struct Errors {
+    errors: Vec<(String, Error)>,
+}
+
+impl Errors {
+    #[cfg(feature = "serde")]
+    pub(crate) const fn new(errors: Vec<(String, Error)>) -> Self {
+        Self { errors }
+    }
+
+    /// Returns the number of errors.
+    pub fn len(&self) -> usize {
+        self.errors.len()
+    }
+
+    /// Whether there are no errors.
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Iterates over the errors and their paths, in the order they were found.
+    pub fn iter(&self) -> core::slice::Iter<'_, (String, Error)> {
+        self.errors.iter()
+    }
+
+    /// Consumes the errors and returns them with their paths.
+    pub fn into_vec(self) -> Vec<(String, Error)> {
+        self.errors
+    }
+}
+
+impl IntoIterator for Errors {
+    type Item = (String, Error);
+    type IntoIter = alloc::vec::IntoIter<(String, Error)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.errors.into_iter()
+    }
+}
+
+impl fmt::Display for Errors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, (path, error)) in self.errors.iter().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            match path.is_empty() {
+                true => write!(f, "{error}")?,
+                false => write!(f, "{path}: {error}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Errors {}
+
 #[cfg(feature = "serde")]
 impl serde::ser::Error for Error {
     fn custom<T>(msg: T) -> Self
     where
         T: fmt::Display,
     {
-        Self {
-            kind: Box::new(ErrorKind::Custom(msg.to_string())),
-        }
+        Error::custom(msg)
     }
 }
 
@@ -85,8 +272,24 @@ impl serde::de::Error for Error {
     where
         T: fmt::Display,
     {
+        Error::custom(msg)
+    }
+
+    fn missing_field(field: &'static str) -> Self {
         Self {
-            kind: Box::new(ErrorKind::Custom(msg.to_string())),
+            kind: Box::new(ErrorKind::MissingField {
+                struct_name: None,
+                field: field.to_string(),
+            }),
+        }
+    }
+
+    fn unknown_field(field: &str, expected: &'static [&'static str]) -> Self {
+        Self {
+            kind: Box::new(ErrorKind::UnknownField {
+                field: field.to_string(),
+                expected: expected.iter().map(|name| name.to_string()).collect(),
+            }),
         }
     }
 }
@@ -108,6 +311,10 @@ pub enum Expected {
     StaticStr,
     /// Expected a Rust byte array.
     Bytes,
+    /// Expected any Rust number.
+    Number,
+    /// Expected an optional Rust value.
+    Option,
     /// Expected an array of Rust values.
     Seq,
     /// Expected a map of Rust values.
@@ -178,6 +385,8 @@ impl fmt::Display for Expected {
             Expected::String => write!(f, "a string"),
             Expected::StaticStr => write!(f, "a static string slice"),
             Expected::Bytes => write!(f, "a byte array"),
+            Expected::Number => write!(f, "a number"),
+            Expected::Option => write!(f, "an optional value"),
             Expected::Seq => write!(f, "a sequence"),
             Expected::Map => write!(f, "a map"),
             Expected::Struct { name, typ } => match (name.as_deref(), typ) {
@@ -362,3 +571,65 @@ impl fmt::Display for Found {
         }
     }
 }
+
+impl Value<'_> {
+    pub(crate) fn into_found(self) -> Found {
+        match self {
+            Value::Unit => Found::Unit,
+            Value::Bool(v) => Found::Bool(v),
+            Value::Number(v) => Found::Number(v),
+            Value::Char(v) => Found::Char(v),
+            Value::String(v) => Found::String(v.into_owned()),
+            Value::Bytes(v) => Found::Bytes(v.into_owned()),
+            Value::Seq(v) => {
+                let mut vec = Vec::with_capacity(v.len());
+                for value in v {
+                    vec.push(value.into_found());
+                }
+                Found::Seq(vec)
+            }
+            Value::Map(v) => {
+                let mut vec = Vec::with_capacity(v.len());
+                for (key, value) in v {
+                    vec.push((key.into_found(), value.into_found()));
+                }
+                Found::Map(vec)
+            }
+            Value::Option(v) => Found::Option(v.map(|x| Box::new(x.into_found()))),
+            Value::Struct(v) => Found::Struct {
+                name: v.name.into_owned(),
+                data: Box::new(v.data.into_found()),
+            },
+            Value::Enum(v) => Found::Enum {
+                name: v.name.into_owned(),
+                variant: v.variant.into_owned(),
+                data: Box::new(v.data.into_found()),
+            },
+            Value::Tuple(v) => {
+                let mut vec = Vec::with_capacity(v.len());
+                for value in v {
+                    vec.push(value.into_found());
+                }
+                Found::Tuple(vec)
+            }
+        }
+    }
+}
+
+impl crate::Data<'_> {
+    pub(crate) fn into_found(self) -> Data {
+        match self {
+            crate::Data::Unit => Data::Unit,
+            crate::Data::NewType { value } => Data::NewType(value.into_found()),
+            crate::Data::Tuple { values } => {
+                Data::Tuple(values.into_iter().map(Value::into_found).collect())
+            }
+            crate::Data::Struct { fields } => Data::Struct(
+                fields
+                    .into_iter()
+                    .map(|(k, v)| (k.into_owned(), v.into_found()))
+                    .collect(),
+            ),
+        }
+    }
+}
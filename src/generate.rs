@@ -0,0 +1,31 @@
+use crate::Value;
+
+/// The names random structs, enums, variants and fields are picked from.
+///
+/// They are all `'static`, so random values can be serialized with [Serializer](crate::Serializer).
+pub(crate) const NAMES: &[&str] = &["", "a", "Point", "type", "$__private", "名前"];
+
+/// How deeply random values nest, counting the outermost value.
+pub(crate) const MAX_DEPTH: usize = 4;
+
+/// How many elements, entries or fields random collections and structs have at most.
+pub(crate) const MAX_LEN: usize = 8;
+
+/// A random [Value] that round-trips.
+///
+/// Serializing the value with [Serializer](crate::Serializer), or deserializing it into a
+/// [Value] with [Deserializer](crate::Deserializer), gives back an equal value. This keeps it to
+/// the types of values that survive `deserialize_any`: the unit type, booleans, numbers that
+/// aren't NaN, characters, strings, bytes, sequences, maps and options. Structs, enums and tuples
+/// can only be told apart by the type being deserialized.
+///
+/// A random [Value] itself covers every variant, with struct and enum names from a fixed set of
+/// `'static` names and nesting at most four levels deep.
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct Roundtrip<'a>(pub Value<'a>);
+
+impl<'a> From<Roundtrip<'a>> for Value<'a> {
+    fn from(roundtrip: Roundtrip<'a>) -> Self {
+        roundtrip.0
+    }
+}
@@ -0,0 +1,323 @@
+use alloc::boxed::Box;
+use core::fmt;
+use core::hash::BuildHasher;
+use core::sync::atomic;
+use core::sync::atomic::AtomicUsize;
+use std::collections::hash_map::RandomState;
+use std::sync::OnceLock;
+#[cfg(feature = "serde")]
+use {
+    crate::limits::capacity, crate::Value, crate::ValueVisitor, alloc::borrow::Cow,
+    alloc::string::String, alloc::vec::Vec, core::cmp::Ordering, core::ptr, serde::de,
+};
+
+/// A pool of strings for sharing equal strings between values.
+///
+/// Serializing or deserializing many values with the same strings in them, like the keys of
+/// maps, normally allocates each of those strings again and again. Interning stores every
+/// distinct string once and hands out borrows of it, so the values hold [Cow::Borrowed]
+/// strings pointing into the pool instead, and live as long as the pool does.
+///
+/// Strings are interned while serializing by [crate::Serializer::intern] and while
+/// deserializing by [Interner::deserialize]. The pool only grows, so it can be shared between
+/// any number of values and threads, and dropped along with all of its strings once they are
+/// done. Interning needs the `std` feature.
+///
+/// Strings are looked up by hash. The hash is randomly keyed for each pool, so input from
+/// untrusted sources can't pick strings that collide.
+///
+/// [Cow::Borrowed]: alloc::borrow::Cow::Borrowed
+///
+/// ## Example
+///
+/// ```
+/// # use serde_content::{Interner, Serializer};
+/// # use std::collections::BTreeMap;
+/// let interner = Interner::new();
+/// let serializer = Serializer::new().intern(&interner);
+///
+/// let rows = [
+///     BTreeMap::from([("id", 1), ("size", 2)]),
+///     BTreeMap::from([("id", 3), ("size", 4)]),
+/// ];
+/// let values = rows.map(|row| serializer.serialize(row).unwrap());
+/// assert_eq!(values.len(), 2);
+/// assert_eq!(interner.len(), 2);
+/// ```
+#[derive(Default)]
+pub struct Interner {
+    root: OnceLock<Box<Node>>,
+    len: AtomicUsize,
+    // The randomly keyed hasher of the pool, so that colliding strings can't be picked.
+    state: OnceLock<RandomState>,
+}
+
+// A node of a trie over the hashes of the strings, taking two bits of the hash at each level.
+// Below the levels of the hash, the trie goes on over the bytes of the strings, so that even
+// strings with the same hash are only as many levels apart as it takes to tell them apart.
+struct Node {
+    hash: u64,
+    value: Box<str>,
+    children: [OnceLock<Box<Node>>; 4],
+}
+
+impl Interner {
+    /// Creates an empty pool.
+    pub const fn new() -> Self {
+        Self {
+            root: OnceLock::new(),
+            len: AtomicUsize::new(0),
+            state: OnceLock::new(),
+        }
+    }
+
+    /// Returns the string in the pool equal to `value`, adding it first if there is none.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use serde_content::Interner;
+    /// let interner = Interner::new();
+    /// let a = interner.intern("key");
+    /// let b = interner.intern(&String::from("key"));
+    /// assert!(std::ptr::eq(a, b));
+    /// ```
+    pub fn intern(&self, value: &str) -> &str {
+        let hash = self.hash(value);
+        let mut slot = &self.root;
+        let mut level = 0;
+        loop {
+            match slot.get() {
+                Some(node) if node.hash == hash && *node.value == *value => return &node.value,
+                Some(node) => {
+                    slot = &node.children[branch(hash, value, level)];
+                    level += 1;
+                }
+                // Another thread may fill the slot first, with this string or another one, in
+                // which case the slot is looked at again.
+                None => {
+                    let node = Box::new(Node {
+                        hash,
+                        value: value.into(),
+                        children: Default::default(),
+                    });
+                    if slot.set(node).is_ok() {
+                        self.len.fetch_add(1, atomic::Ordering::Relaxed);
+                    }
+                }
+            }
+        }
+    }
+
+    fn hash(&self, value: &str) -> u64 {
+        self.state.get_or_init(RandomState::new).hash_one(value)
+    }
+
+    /// Returns the number of distinct strings in the pool.
+    pub fn len(&self) -> usize {
+        self.len.load(atomic::Ordering::Relaxed)
+    }
+
+    /// Returns `true` if the pool doesn't hold any strings.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Deserializes a [Value] from any self-describing format, interning all of its strings.
+    ///
+    /// Unlike [ValueVisitor], the value doesn't borrow from the input, so byte arrays are
+    /// always copied.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use serde_content::{value, Deserializer, Interner};
+    /// let interner = Interner::new();
+    /// let rows = value!([{ "id": 1u8 }, { "id": 2u8 }]);
+    ///
+    /// let value = interner.deserialize(Deserializer::new(rows.clone())).unwrap();
+    /// assert_eq!(value, rows);
+    /// assert_eq!(interner.len(), 1);
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn deserialize<'de, D>(&self, deserializer: D) -> Result<Value<'_>, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_any(Visitor { interner: self })
+    }
+}
+
+/// Returns which child to go to from a node at `level` of the trie when looking for `value`.
+pub(crate) fn branch(hash: u64, value: &str, level: usize) -> usize {
+    let bits = match level.checked_sub(32) {
+        None => hash >> (2 * level),
+        Some(level) => match value.as_bytes().get(level / 4) {
+            Some(byte) => u64::from(*byte) >> (2 * (level % 4)),
+            None => 0,
+        },
+    };
+    (bits & 3) as usize
+}
+
+impl fmt::Debug for Interner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Interner")
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+/// A pool shared by a serializer, compared by identity.
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Shared<'i>(pub(crate) &'i Interner);
+
+#[cfg(feature = "serde")]
+impl PartialEq for Shared<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        ptr::eq(self.0, other.0)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl PartialOrd for Shared<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        (self == other).then_some(Ordering::Equal)
+    }
+}
+
+/// Deserializes a [Value] with its strings interned in `interner`.
+#[cfg(feature = "serde")]
+#[derive(Clone, Copy)]
+struct Visitor<'i> {
+    interner: &'i Interner,
+}
+
+#[cfg(feature = "serde")]
+impl<'i> Visitor<'i> {
+    fn string<E>(self, value: &str) -> Result<Value<'i>, E> {
+        Ok(Value::String(Cow::Borrowed(self.interner.intern(value))))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, 'i> de::DeserializeSeed<'de> for Visitor<'i> {
+    type Value = Value<'i>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_any(self)
+    }
+}
+
+// Values other than strings are the same as without interning.
+#[cfg(feature = "serde")]
+macro_rules! forward {
+    ($($method:ident($ty:ty),)*) => {
+        $(
+            fn $method<E>(self, value: $ty) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                ValueVisitor.$method(value)
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "serde")]
+impl<'de, 'i> de::Visitor<'de> for Visitor<'i> {
+    type Value = Value<'i>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("any value")
+    }
+
+    forward! {
+        visit_bool(bool),
+        visit_i8(i8),
+        visit_i16(i16),
+        visit_i32(i32),
+        visit_i64(i64),
+        visit_i128(i128),
+        visit_u8(u8),
+        visit_u16(u16),
+        visit_u32(u32),
+        visit_u64(u64),
+        visit_u128(u128),
+        visit_f32(f32),
+        visit_f64(f64),
+        visit_char(char),
+        visit_bytes(&[u8]),
+        visit_byte_buf(Vec<u8>),
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        self.string(value)
+    }
+
+    fn visit_string<E>(self, value: String) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        self.string(&value)
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Value::Unit)
+    }
+
+    fn visit_none<E>(self) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Value::Option(None))
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        let value = de::DeserializeSeed::deserialize(self, deserializer)?;
+        Ok(Value::Option(Some(Box::new(value))))
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        de::DeserializeSeed::deserialize(self, deserializer)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::SeqAccess<'de>,
+    {
+        let mut vec = Vec::with_capacity(capacity::<Value>(seq.size_hint()));
+        while let Some(value) = seq.next_element_seed(self)? {
+            vec.push(value);
+        }
+        Ok(Value::Seq(vec))
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::MapAccess<'de>,
+    {
+        let mut vec = Vec::with_capacity(capacity::<(Value, Value)>(map.size_hint()));
+        while let Some(key) = map.next_key_seed(self)? {
+            let value = map.next_value_seed(self)?;
+            vec.push((key, value));
+        }
+        Ok(Value::Map(vec))
+    }
+}
@@ -0,0 +1,32 @@
+//! Conversions between [Value](crate::Value) and JSON.
+//!
+//! [from_str] parses JSON text into a [Value](crate::Value), borrowing strings from the text
+//! where it can, and [to_string] writes a [Value](crate::Value) as JSON text. Both work without
+//! the standard library.
+//!
+//! With the `json` feature, a `serde_json::Value` converts into a [Value](crate::Value) with
+//! `From`. The other way around, JSON can't represent everything a [Value](crate::Value) can, so
+//! `TryFrom` converts with the default [Policy], and `Policy::convert` with any other. The same
+//! policies apply to [Policy::to_string].
+//!
+//! `serde_json` implements `PartialEq` between its `Value` and primitive types, so with the
+//! `json` feature enabled, comparisons that relied on inference to pick the type to
+//! deserialize, like `assert_eq!(1usize, deserializer.deserialize().unwrap())`, may need to
+//! name it, as in `deserializer.deserialize::<usize>()`.
+
+mod policy;
+mod read;
+#[cfg(feature = "json")]
+mod value;
+mod write;
+
+pub use policy::Chars;
+pub use policy::Keys;
+pub use policy::Names;
+pub use policy::NonFinite;
+pub use policy::Policy;
+pub use policy::Tuples;
+pub use policy::WideIntegers;
+pub use read::from_str;
+pub use read::Reader;
+pub use write::to_string;
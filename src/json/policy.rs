@@ -0,0 +1,240 @@
+use crate::BytesEncoding;
+use crate::Data;
+use crate::Error;
+use crate::Found;
+use crate::Number;
+use crate::Value;
+use alloc::string::String;
+use alloc::string::ToString;
+
+/// The key [Names::Tagged] stores the names of structs and enum variants under.
+pub(super) const TYPE: &str = "$type";
+/// The key [Names::Tagged] stores the data of structs and enum variants under, unless they are
+/// object-like.
+pub(super) const VALUE: &str = "$value";
+/// The key [Tuples::Tagged] stores the elements of tuples under.
+pub(super) const TUPLE: &str = "$tuple";
+
+/// How to convert a [Value] into JSON where JSON has no equivalent.
+///
+/// By default, the conversion follows what `serde_json` does when serializing the same data:
+///
+/// - Byte arrays become arrays of numbers. See [Policy::bytes_encoding].
+/// - Characters become strings. See [Chars].
+/// - 128-bit integers become strings. See [WideIntegers].
+/// - `NaN` and infinite floats become `null`. See [NonFinite].
+/// - Numbers, booleans, characters and unit enum variants used as map keys become strings. See
+///   [Keys].
+/// - Tuples become arrays, like sequences. See [Tuples].
+/// - The names of structs and enums are dropped, and enums are externally tagged by their
+///   variant. See [Names].
+/// - The unit type and `None` become `null`, and `Some` becomes its value.
+///
+/// ## Example
+///
+/// ```
+/// # use serde_content::{json, value, BytesEncoding};
+/// let value = value!({ "data": (&b"hi"[..]), "id": 1u128 });
+///
+/// let json = json::to_string(&value).unwrap();
+/// assert_eq!(json, r#"{"data":[104,105],"id":"1"}"#);
+///
+/// let policy = json::Policy::new()
+///     .bytes_encoding(BytesEncoding::Hex)
+///     .wide_integers(json::WideIntegers::Number);
+/// let json = policy.to_string(&value).unwrap();
+/// assert_eq!(json, r#"{"data":"6869","id":1}"#);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Policy {
+    pub(super) bytes_encoding: Option<BytesEncoding>,
+    pub(super) chars: Chars,
+    pub(super) wide_integers: WideIntegers,
+    pub(super) non_finite: NonFinite,
+    pub(super) keys: Keys,
+    pub(super) tuples: Tuples,
+    pub(super) names: Names,
+}
+
+/// How [Value::Char] is converted into JSON.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Chars {
+    /// A string holding the character.
+    #[default]
+    String,
+    /// The Unicode code point of the character, as a number.
+    CodePoint,
+}
+
+/// How [Number::I128] and [Number::U128] are converted into JSON.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum WideIntegers {
+    /// A string holding the integer in decimal.
+    #[default]
+    String,
+    /// A number. When converting into a `serde_json::Value`, integers that don't fit in 64 bits
+    /// are an error, unless the `arbitrary_precision` feature of `serde_json` is enabled.
+    Number,
+}
+
+/// How `NaN` and infinite floats are converted into JSON.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum NonFinite {
+    /// `null`.
+    #[default]
+    Null,
+    /// The strings `"NaN"`, `"Infinity"` and `"-Infinity"`.
+    String,
+    /// An error.
+    Reject,
+}
+
+/// How maps with keys that aren't strings are converted into JSON.
+///
+/// Keys that end up as the same string, like `1u8` and `"1"`, are an error, since a JSON object
+/// can only hold one of them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Keys {
+    /// Numbers, booleans, characters and unit enum variants become strings, and any other key
+    /// is an error.
+    #[default]
+    Stringify,
+    /// Maps with any key that isn't a string become arrays of `[key, value]` pairs.
+    Entries,
+    /// Any key that isn't a string is an error.
+    Reject,
+}
+
+/// How [Value::Tuple] is converted into JSON.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Tuples {
+    /// An array, indistinguishable from a sequence.
+    #[default]
+    Array,
+    /// An object with the array under a `"$tuple"` key.
+    Tagged,
+}
+
+/// How the names of structs and enums are converted into JSON.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Names {
+    /// The names of structs and enums are dropped. Structs become their data, while enum
+    /// variants become their name, or an object with their data under their name.
+    #[default]
+    Drop,
+    /// Structs and enum variants become objects with their name under a `"$type"` key, like
+    /// `Point` or `Shape::Circle`. Object-like data is kept alongside it, while any other data
+    /// goes under a `"$value"` key.
+    Tagged,
+}
+
+impl Default for Policy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Policy {
+    /// Creates the default policy.
+    pub const fn new() -> Self {
+        Self {
+            bytes_encoding: None,
+            chars: Chars::String,
+            wide_integers: WideIntegers::String,
+            non_finite: NonFinite::Null,
+            keys: Keys::Stringify,
+            tuples: Tuples::Array,
+            names: Names::Drop,
+        }
+    }
+
+    /// Encode byte arrays as strings with `encoding`, instead of arrays of numbers.
+    ///
+    /// [Deserializer::bytes_encoding](crate::Deserializer::bytes_encoding) decodes them again.
+    pub const fn bytes_encoding(mut self, encoding: BytesEncoding) -> Self {
+        self.bytes_encoding = Some(encoding);
+        self
+    }
+
+    /// Set how characters are converted.
+    pub const fn chars(mut self, chars: Chars) -> Self {
+        self.chars = chars;
+        self
+    }
+
+    /// Set how 128-bit integers are converted.
+    pub const fn wide_integers(mut self, wide_integers: WideIntegers) -> Self {
+        self.wide_integers = wide_integers;
+        self
+    }
+
+    /// Set how `NaN` and infinite floats are converted.
+    pub const fn non_finite(mut self, non_finite: NonFinite) -> Self {
+        self.non_finite = non_finite;
+        self
+    }
+
+    /// Set how maps with keys that aren't strings are converted.
+    pub const fn keys(mut self, keys: Keys) -> Self {
+        self.keys = keys;
+        self
+    }
+
+    /// Set how tuples are converted.
+    pub const fn tuples(mut self, tuples: Tuples) -> Self {
+        self.tuples = tuples;
+        self
+    }
+
+    /// Set how the names of structs and enums are converted.
+    pub const fn names(mut self, names: Names) -> Self {
+        self.names = names;
+        self
+    }
+
+    /// Returns the string a `NaN` or infinite `float` is converted into, or `None` for `null`.
+    pub(super) fn non_finite_float(
+        self,
+        float: f64,
+        number: Number,
+    ) -> Result<Option<&'static str>, Error> {
+        match self.non_finite {
+            NonFinite::Null => Ok(None),
+            NonFinite::String if float.is_nan() => Ok(Some("NaN")),
+            NonFinite::String if float > 0.0 => Ok(Some("Infinity")),
+            NonFinite::String => Ok(Some("-Infinity")),
+            NonFinite::Reject => Err(unrepresentable(Found::Number(number))),
+        }
+    }
+
+    /// Whether a map with `entries` is converted into an array of `[key, value]` pairs.
+    pub(super) fn entries(self, entries: &[(Value<'_>, Value<'_>)]) -> bool {
+        self.keys == Keys::Entries
+            && entries
+                .iter()
+                .any(|(key, _)| !matches!(key, Value::String(_)))
+    }
+
+    /// Converts a map key into a string.
+    pub(super) fn key(self, key: &Value<'_>) -> Result<String, Error> {
+        match key {
+            Value::String(v) => Ok(v.to_string()),
+            key if self.keys == Keys::Reject => Err(unrepresentable(key.clone().into_found())),
+            Value::Bool(v) => Ok(v.to_string()),
+            Value::Char(v) => Ok(v.to_string()),
+            Value::Number(v) => Ok(v.to_plain_string()),
+            Value::Enum(v) if v.data == Data::Unit => Ok(v.variant.to_string()),
+            key => Err(unrepresentable(key.clone().into_found())),
+        }
+    }
+}
+
+/// Creates the error for a map with more than one key converted into the string `key`.
+pub(super) fn duplicate_key(key: &str) -> Error {
+    Error::custom(format_args!("duplicate key `{key}` in JSON object"))
+}
+
+/// Creates the error for a value that can't be converted into JSON under a policy.
+pub(super) fn unrepresentable(found: Found) -> Error {
+    Error::custom(format_args!("{found} can't be represented in JSON"))
+}
@@ -0,0 +1,422 @@
+use crate::limits::Counts;
+use crate::Error;
+use crate::Limit;
+use crate::Limits;
+use crate::Number;
+use crate::Value;
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// How deeply arrays and objects can nest when no depth limit is set, like `serde_json`.
+pub(super) const MAX_DEPTH: usize = 128;
+
+/// Parses JSON text into a [Value] with the default [Reader].
+///
+/// ## Example
+///
+/// ```
+/// # use serde_content::{json, value};
+/// let value = json::from_str(r#"{ "name": "Ferris", "age": 9, "tags": [] }"#).unwrap();
+/// assert_eq!(value, value!({ "name": "Ferris", "age": 9u8, "tags": [] }));
+/// ```
+pub fn from_str(text: &str) -> Result<Value<'_>, Error> {
+    Reader::new(text).read()
+}
+
+/// Parses JSON text into a [Value].
+///
+/// - `null` becomes [Value::Unit], arrays become [Value::Seq] and objects become [Value::Map]
+///   with string keys, in the order they appear in the text.
+/// - Strings without escape sequences are borrowed from the text.
+/// - Integers become the narrowest [Number] that holds them, unsigned unless they are negative,
+///   up to [Number::U128] and [Number::I128]. Larger integers and any number with a fraction or
+///   an exponent become [Number::F64].
+///
+/// Arrays and objects can nest 128 levels deep, unless [Limits] with a depth limit are set, in
+/// which case that limit applies instead.
+///
+/// ## Example
+///
+/// ```
+/// # use serde_content::{json, value, ErrorKind, Limit, Limits};
+/// let value = json::Reader::new("[[1]]").read().unwrap();
+/// assert_eq!(value, value!([[1u8]]));
+///
+/// let limits = Limits::new().max_depth(1);
+/// let error = json::Reader::new("[[1]]").limits(&limits).read().unwrap_err();
+/// assert_eq!(error.kind(), &ErrorKind::LimitExceeded(Limit::Depth(1)));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Reader<'a, 'l> {
+    text: &'a str,
+    limits: Option<&'l Limits>,
+}
+
+impl<'a, 'l> Reader<'a, 'l> {
+    /// Creates a reader for `text`.
+    pub const fn new(text: &'a str) -> Self {
+        Self { text, limits: None }
+    }
+
+    /// Enforce `limits` while reading.
+    pub const fn limits(mut self, limits: &'l Limits) -> Self {
+        self.limits = Some(limits);
+        self
+    }
+
+    /// Parses the text into a [Value].
+    pub fn read(self) -> Result<Value<'a>, Error> {
+        let mut parser = Parser {
+            text: self.text,
+            pos: 0,
+            limits: self.limits,
+            counts: Counts::new(),
+        };
+        let value = parser.value(0)?;
+        parser.whitespace();
+        match parser.pos < parser.text.len() {
+            true => Err(parser.error("trailing characters")),
+            false => Ok(value),
+        }
+    }
+}
+
+struct Parser<'a, 'l> {
+    text: &'a str,
+    pos: usize,
+    limits: Option<&'l Limits>,
+    counts: Counts,
+}
+
+impl<'a> Parser<'a, '_> {
+    fn peek(&self) -> Option<u8> {
+        self.text.as_bytes().get(self.pos).copied()
+    }
+
+    fn next(&mut self) -> Option<u8> {
+        let byte = self.peek()?;
+        self.pos += 1;
+        Some(byte)
+    }
+
+    fn whitespace(&mut self) {
+        while let Some(b' ' | b'\t' | b'\n' | b'\r') = self.peek() {
+            self.pos += 1;
+        }
+    }
+
+    /// Creates a syntax error at the current position.
+    fn error(&self, message: &str) -> Error {
+        let before = &self.text.as_bytes()[..self.pos];
+        let line_start = before
+            .iter()
+            .rposition(|&b| b == b'\n')
+            .map_or(0, |i| i + 1);
+        let line = before.iter().filter(|&&b| b == b'\n').count() + 1;
+        // Count characters rather than bytes, by skipping UTF-8 continuation bytes.
+        let column = before[line_start..]
+            .iter()
+            .filter(|&&b| b & 0xc0 != 0x80)
+            .count()
+            + 1;
+        Error::syntax(message, line, column)
+    }
+
+    /// Returns the depth of the values nested inside an array or object at `depth`.
+    fn enter(&self, depth: usize) -> Result<usize, Error> {
+        let max = self.limits.and_then(Limits::depth).unwrap_or(MAX_DEPTH);
+        match depth < max {
+            true => Ok(depth + 1),
+            false => Err(Error::limit_exceeded(Limit::Depth(max))),
+        }
+    }
+
+    fn elements(&self, count: usize) -> Result<(), Error> {
+        match self.limits {
+            Some(limits) => limits.elements(&self.counts, count),
+            None => Ok(()),
+        }
+    }
+
+    fn bytes(&self, len: usize) -> Result<(), Error> {
+        match self.limits {
+            Some(limits) => limits.bytes(&self.counts, len),
+            None => Ok(()),
+        }
+    }
+
+    fn value(&mut self, depth: usize) -> Result<Value<'a>, Error> {
+        self.whitespace();
+        match self.peek() {
+            None => Err(self.error("unexpected end of input")),
+            Some(b'n') => self.literal("null", Value::Unit),
+            Some(b't') => self.literal("true", Value::Bool(true)),
+            Some(b'f') => self.literal("false", Value::Bool(false)),
+            Some(b'"') => {
+                let string = self.string()?;
+                self.bytes(string.len())?;
+                Ok(Value::String(string))
+            }
+            Some(b'[') => self.array(depth),
+            Some(b'{') => self.object(depth),
+            Some(b'-' | b'0'..=b'9') => self.number().map(Value::Number),
+            Some(_) => Err(self.error("expected value")),
+        }
+    }
+
+    fn literal(&mut self, literal: &str, value: Value<'a>) -> Result<Value<'a>, Error> {
+        match self.text[self.pos..].starts_with(literal) {
+            true => {
+                self.pos += literal.len();
+                Ok(value)
+            }
+            false => Err(self.error("expected value")),
+        }
+    }
+
+    fn array(&mut self, depth: usize) -> Result<Value<'a>, Error> {
+        self.pos += 1;
+        let depth = self.enter(depth)?;
+        let mut values = Vec::new();
+        self.whitespace();
+        if self.peek() == Some(b']') {
+            self.pos += 1;
+            return Ok(Value::Seq(values));
+        }
+        loop {
+            self.elements(1)?;
+            values.push(self.value(depth)?);
+            self.whitespace();
+            match self.next() {
+                Some(b',') => {}
+                Some(b']') => return Ok(Value::Seq(values)),
+                None => return Err(self.error("unexpected end of input")),
+                Some(_) => {
+                    self.pos -= 1;
+                    return Err(self.error("expected `,` or `]`"));
+                }
+            }
+        }
+    }
+
+    fn object(&mut self, depth: usize) -> Result<Value<'a>, Error> {
+        self.pos += 1;
+        let depth = self.enter(depth)?;
+        let mut entries = Vec::new();
+        self.whitespace();
+        if self.peek() == Some(b'}') {
+            self.pos += 1;
+            return Ok(Value::Map(entries));
+        }
+        loop {
+            self.elements(1)?;
+            self.whitespace();
+            if self.peek() != Some(b'"') {
+                return Err(self.error("expected string key"));
+            }
+            let key = self.string()?;
+            self.bytes(key.len())?;
+            self.whitespace();
+            if self.peek() != Some(b':') {
+                return Err(self.error("expected `:`"));
+            }
+            self.pos += 1;
+            entries.push((Value::String(key), self.value(depth)?));
+            self.whitespace();
+            match self.next() {
+                Some(b',') => {}
+                Some(b'}') => return Ok(Value::Map(entries)),
+                None => return Err(self.error("unexpected end of input")),
+                Some(_) => {
+                    self.pos -= 1;
+                    return Err(self.error("expected `,` or `}`"));
+                }
+            }
+        }
+    }
+
+    /// Parses a string, borrowing it from the text unless it has escape sequences.
+    fn string(&mut self) -> Result<Cow<'a, str>, Error> {
+        let text = self.text;
+        self.pos += 1;
+        let mut owned: Option<String> = None;
+        loop {
+            let start = self.pos;
+            while let Some(byte) = self.peek() {
+                if byte == b'"' || byte == b'\\' || byte < 0x20 {
+                    break;
+                }
+                self.pos += 1;
+            }
+            let run = &text[start..self.pos];
+            match self.next() {
+                Some(b'"') => {
+                    return Ok(match owned {
+                        Some(mut owned) => {
+                            owned.push_str(run);
+                            Cow::Owned(owned)
+                        }
+                        None => Cow::Borrowed(run),
+                    })
+                }
+                Some(b'\\') => {
+                    let owned = owned.get_or_insert_with(String::new);
+                    owned.push_str(run);
+                    owned.push(self.escape()?);
+                }
+                None => return Err(self.error("unexpected end of input")),
+                Some(_) => {
+                    self.pos -= 1;
+                    return Err(self.error("control character in string"));
+                }
+            }
+        }
+    }
+
+    /// Parses the rest of an escape sequence after its backslash.
+    fn escape(&mut self) -> Result<char, Error> {
+        match self.next() {
+            Some(b'"') => Ok('"'),
+            Some(b'\\') => Ok('\\'),
+            Some(b'/') => Ok('/'),
+            Some(b'b') => Ok('\u{8}'),
+            Some(b'f') => Ok('\u{c}'),
+            Some(b'n') => Ok('\n'),
+            Some(b'r') => Ok('\r'),
+            Some(b't') => Ok('\t'),
+            Some(b'u') => self.unicode(),
+            None => Err(self.error("unexpected end of input")),
+            Some(_) => {
+                self.pos -= 1;
+                Err(self.error("invalid escape"))
+            }
+        }
+    }
+
+    /// Parses the hex digits of a `\u` escape, and a second one for a surrogate pair.
+    fn unicode(&mut self) -> Result<char, Error> {
+        let start = self.pos - 2;
+        let high = self.hex()?;
+        let code = match high {
+            0xd800..=0xdbff => {
+                if !self.text[self.pos..].starts_with("\\u") {
+                    self.pos = start;
+                    return Err(self.error("lone surrogate in string"));
+                }
+                self.pos += 2;
+                let low = self.hex()?;
+                if !(0xdc00..=0xdfff).contains(&low) {
+                    self.pos = start;
+                    return Err(self.error("lone surrogate in string"));
+                }
+                0x10000 + ((high as u32 - 0xd800) << 10) + (low as u32 - 0xdc00)
+            }
+            0xdc00..=0xdfff => {
+                self.pos = start;
+                return Err(self.error("lone surrogate in string"));
+            }
+            code => code as u32,
+        };
+        // Surrogates are handled above, so every other code is a valid character.
+        char::from_u32(code).ok_or_else(|| self.error("invalid unicode escape"))
+    }
+
+    fn hex(&mut self) -> Result<u16, Error> {
+        let digits = self.text.as_bytes().get(self.pos..self.pos + 4);
+        match digits {
+            Some(digits) if digits.iter().all(u8::is_ascii_hexdigit) => {
+                let digits = &self.text[self.pos..self.pos + 4];
+                self.pos += 4;
+                u16::from_str_radix(digits, 16).map_err(|_| self.error("invalid unicode escape"))
+            }
+            _ => Err(self.error("invalid unicode escape")),
+        }
+    }
+
+    fn digits(&mut self) -> usize {
+        let start = self.pos;
+        while let Some(b'0'..=b'9') = self.peek() {
+            self.pos += 1;
+        }
+        self.pos - start
+    }
+
+    fn number(&mut self) -> Result<Number, Error> {
+        let start = self.pos;
+        let negative = self.peek() == Some(b'-');
+        if negative {
+            self.pos += 1;
+        }
+        match self.peek() {
+            Some(b'0') => self.pos += 1,
+            Some(b'1'..=b'9') => {
+                self.digits();
+            }
+            _ => return Err(self.error("invalid number")),
+        }
+        let mut integer = true;
+        if self.peek() == Some(b'.') {
+            self.pos += 1;
+            integer = false;
+            if self.digits() == 0 {
+                return Err(self.error("invalid number"));
+            }
+        }
+        if let Some(b'e' | b'E') = self.peek() {
+            self.pos += 1;
+            integer = false;
+            if let Some(b'+' | b'-') = self.peek() {
+                self.pos += 1;
+            }
+            if self.digits() == 0 {
+                return Err(self.error("invalid number"));
+            }
+        }
+        let text = &self.text[start..self.pos];
+        if integer {
+            let number = match negative {
+                true => text.parse().ok().map(signed),
+                false => text.parse().ok().map(unsigned),
+            };
+            if let Some(number) = number {
+                return Ok(number);
+            }
+        }
+        match text.parse::<f64>() {
+            Ok(v) if v.is_finite() => Ok(Number::F64(v)),
+            _ => {
+                self.pos = start;
+                Err(self.error("number out of range"))
+            }
+        }
+    }
+}
+
+fn unsigned(v: u128) -> Number {
+    if let Ok(v) = u8::try_from(v) {
+        Number::U8(v)
+    } else if let Ok(v) = u16::try_from(v) {
+        Number::U16(v)
+    } else if let Ok(v) = u32::try_from(v) {
+        Number::U32(v)
+    } else if let Ok(v) = u64::try_from(v) {
+        Number::U64(v)
+    } else {
+        Number::U128(v)
+    }
+}
+
+fn signed(v: i128) -> Number {
+    if let Ok(v) = i8::try_from(v) {
+        Number::I8(v)
+    } else if let Ok(v) = i16::try_from(v) {
+        Number::I16(v)
+    } else if let Ok(v) = i32::try_from(v) {
+        Number::I32(v)
+    } else if let Ok(v) = i64::try_from(v) {
+        Number::I64(v)
+    } else {
+        Number::I128(v)
+    }
+}
@@ -0,0 +1,220 @@
+use super::policy::duplicate_key;
+use super::policy::unrepresentable;
+use super::policy::TUPLE;
+use super::policy::TYPE;
+use super::policy::VALUE;
+use super::Chars;
+use super::Names;
+use super::Policy;
+use super::Tuples;
+use super::WideIntegers;
+use crate::Data;
+use crate::Error;
+use crate::Found;
+use crate::Number;
+use crate::Value;
+use alloc::borrow::Cow;
+use alloc::format;
+use alloc::string::String;
+use alloc::string::ToString;
+use alloc::vec;
+use alloc::vec::Vec;
+use serde_json::map::Entry;
+use serde_json::Map;
+use serde_json::Value as Json;
+
+impl Policy {
+    /// Converts `value` into JSON following this policy.
+    pub fn convert(self, value: Value<'_>) -> Result<Json, Error> {
+        match value {
+            Value::Unit | Value::Option(None) => Ok(Json::Null),
+            Value::Bool(v) => Ok(Json::Bool(v)),
+            Value::Number(v) => self.number(v),
+            Value::Char(v) => match self.chars {
+                Chars::String => Ok(Json::String(v.to_string())),
+                Chars::CodePoint => Ok(Json::from(v as u32)),
+            },
+            Value::String(v) => Ok(Json::String(v.into_owned())),
+            Value::Bytes(v) => match self.bytes_encoding {
+                Some(encoding) => Ok(Json::String(encoding.encode(&v))),
+                None => Ok(v.iter().copied().map(Json::from).collect()),
+            },
+            Value::Seq(v) => self.array(v),
+            Value::Tuple(v) => match self.tuples {
+                Tuples::Array => self.array(v),
+                Tuples::Tagged => {
+                    let mut object = Map::new();
+                    object.insert(TUPLE.to_string(), self.array(v)?);
+                    Ok(Json::Object(object))
+                }
+            },
+            Value::Map(v) => self.map(v),
+            Value::Option(Some(v)) => self.convert(*v),
+            Value::Struct(v) => match self.names {
+                Names::Drop => self.data(v.data),
+                Names::Tagged => self.tagged(v.name.into_owned(), v.data),
+            },
+            Value::Enum(v) => match (self.names, v.data) {
+                (Names::Drop, Data::Unit) => Ok(Json::String(v.variant.into_owned())),
+                (Names::Drop, data) => {
+                    let mut object = Map::new();
+                    object.insert(v.variant.into_owned(), self.data(data)?);
+                    Ok(Json::Object(object))
+                }
+                (Names::Tagged, data) => {
+                    let name = format!("{}::{}", v.name, v.variant);
+                    self.tagged(name, data)
+                }
+            },
+        }
+    }
+
+    fn number(self, number: Number) -> Result<Json, Error> {
+        match number {
+            Number::I8(v) => Ok(Json::from(v)),
+            Number::U8(v) => Ok(Json::from(v)),
+            Number::I16(v) => Ok(Json::from(v)),
+            Number::U16(v) => Ok(Json::from(v)),
+            Number::I32(v) => Ok(Json::from(v)),
+            Number::U32(v) => Ok(Json::from(v)),
+            Number::I64(v) => Ok(Json::from(v)),
+            Number::U64(v) => Ok(Json::from(v)),
+            Number::F32(v) => self.float(v as f64, number),
+            Number::F64(v) => self.float(v, number),
+            Number::I128(v) => match self.wide_integers {
+                WideIntegers::String => Ok(Json::String(v.to_string())),
+                WideIntegers::Number => serde_json::Number::from_i128(v)
+                    .map(Json::Number)
+                    .ok_or_else(|| unrepresentable(Found::Number(number))),
+            },
+            Number::U128(v) => match self.wide_integers {
+                WideIntegers::String => Ok(Json::String(v.to_string())),
+                WideIntegers::Number => serde_json::Number::from_u128(v)
+                    .map(Json::Number)
+                    .ok_or_else(|| unrepresentable(Found::Number(number))),
+            },
+        }
+    }
+
+    fn float(self, float: f64, number: Number) -> Result<Json, Error> {
+        match serde_json::Number::from_f64(float) {
+            Some(v) => Ok(Json::Number(v)),
+            None => Ok(self
+                .non_finite_float(float, number)?
+                .map_or(Json::Null, Json::from)),
+        }
+    }
+
+    fn array(self, values: Vec<Value<'_>>) -> Result<Json, Error> {
+        values
+            .into_iter()
+            .map(|value| self.convert(value))
+            .collect()
+    }
+
+    fn map(self, entries: Vec<(Value<'_>, Value<'_>)>) -> Result<Json, Error> {
+        if self.entries(&entries) {
+            return entries
+                .into_iter()
+                .map(|(key, value)| Ok(Json::Array(vec![self.convert(key)?, self.convert(value)?])))
+                .collect();
+        }
+        let mut object = Map::new();
+        for (key, value) in entries {
+            match object.entry(self.key(&key)?) {
+                Entry::Occupied(entry) => return Err(duplicate_key(entry.key())),
+                Entry::Vacant(entry) => entry.insert(self.convert(value)?),
+            };
+        }
+        Ok(Json::Object(object))
+    }
+
+    /// Converts the data of a struct or enum variant, without its name.
+    fn data(self, data: Data<'_>) -> Result<Json, Error> {
+        match data {
+            Data::Unit => Ok(Json::Null),
+            Data::NewType { value } => self.convert(value),
+            Data::Tuple { values } => self.array(values),
+            Data::Struct { fields } => self.fields(Map::new(), fields),
+        }
+    }
+
+    /// Converts the data of a struct or enum variant, tagged with `name`.
+    fn tagged(self, name: String, data: Data<'_>) -> Result<Json, Error> {
+        let mut object = Map::new();
+        object.insert(TYPE.to_string(), Json::String(name));
+        match data {
+            Data::Unit => Ok(Json::Object(object)),
+            Data::Struct { fields } => self.fields(object, fields),
+            data => {
+                object.insert(VALUE.to_string(), self.data(data)?);
+                Ok(Json::Object(object))
+            }
+        }
+    }
+
+    fn fields(
+        self,
+        mut object: Map<String, Json>,
+        fields: Vec<(Cow<'static, str>, Value<'_>)>,
+    ) -> Result<Json, Error> {
+        let tagged = object.contains_key(TYPE);
+        for (key, value) in fields {
+            match object.entry(key.into_owned()) {
+                // The tag takes precedence over a field with the same name.
+                Entry::Occupied(entry) if tagged && entry.key() == TYPE => {}
+                Entry::Occupied(entry) => return Err(duplicate_key(entry.key())),
+                Entry::Vacant(entry) => {
+                    entry.insert(self.convert(value)?);
+                }
+            }
+        }
+        Ok(Json::Object(object))
+    }
+}
+
+impl TryFrom<Value<'_>> for Json {
+    type Error = Error;
+
+    /// Converts a [Value] into JSON with the default [Policy].
+    fn try_from(value: Value<'_>) -> Result<Self, Self::Error> {
+        Policy::new().convert(value)
+    }
+}
+
+impl From<Json> for Value<'static> {
+    /// Converts JSON into a [Value]. `null` becomes [Value::Unit].
+    fn from(json: Json) -> Self {
+        match json {
+            Json::Null => Value::Unit,
+            Json::Bool(v) => Value::Bool(v),
+            Json::Number(v) => Value::Number(v.into()),
+            Json::String(v) => Value::String(Cow::Owned(v)),
+            Json::Array(v) => Value::Seq(v.into_iter().map(Value::from).collect()),
+            Json::Object(v) => Value::Map(
+                v.into_iter()
+                    .map(|(key, value)| (Value::String(Cow::Owned(key)), value.into()))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+impl From<serde_json::Number> for Number {
+    /// Converts a JSON number into the narrowest of `U64`, `I64`, `U128`, `I128` and `F64` that
+    /// holds it. Integers only need 128 bits with the `arbitrary_precision` feature of
+    /// `serde_json`.
+    fn from(number: serde_json::Number) -> Self {
+        if let Some(v) = number.as_u64() {
+            Number::U64(v)
+        } else if let Some(v) = number.as_i64() {
+            Number::I64(v)
+        } else if let Some(v) = number.as_u128() {
+            Number::U128(v)
+        } else if let Some(v) = number.as_i128() {
+            Number::I128(v)
+        } else {
+            Number::F64(number.as_f64().unwrap_or(f64::NAN))
+        }
+    }
+}
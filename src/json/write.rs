@@ -0,0 +1,308 @@
+use super::policy::duplicate_key;
+use super::policy::TUPLE;
+use super::policy::TYPE;
+use super::policy::VALUE;
+use super::read::MAX_DEPTH;
+use super::Chars;
+use super::Names;
+use super::Policy;
+use super::Tuples;
+use super::WideIntegers;
+use crate::Data;
+use crate::Error;
+use crate::Limit;
+use crate::Number;
+use crate::Value;
+use alloc::borrow::Cow;
+use alloc::collections::BTreeSet;
+use alloc::string::String;
+use core::fmt;
+use core::fmt::Write as _;
+
+/// Writes `value` as compact JSON text with the default [Policy].
+///
+/// ## Example
+///
+/// ```
+/// # use serde_content::{json, value};
+/// let value = value!({ "name": "Ferris", "age": 9u8, "tags": [] });
+/// let json = json::to_string(&value).unwrap();
+/// assert_eq!(json, r#"{"name":"Ferris","age":9,"tags":[]}"#);
+/// ```
+pub fn to_string(value: &Value<'_>) -> Result<String, Error> {
+    Policy::new().to_string(value)
+}
+
+impl Policy {
+    /// Writes `value` as compact JSON text following this policy.
+    ///
+    /// Object keys are written in the order of the map or struct they come from.
+    ///
+    /// Fails if an object would have the same key twice, or if arrays and objects would nest
+    /// more than 128 levels deep, which is as deep as [Reader](super::Reader) reads by default.
+    pub fn to_string(self, value: &Value<'_>) -> Result<String, Error> {
+        let mut out = String::new();
+        self.write(value, 0, &mut out)?;
+        Ok(out)
+    }
+
+    // `depth` is the number of arrays and objects the value is nested in.
+    fn write(self, value: &Value<'_>, depth: usize, out: &mut String) -> Result<(), Error> {
+        match value {
+            Value::Unit | Value::Option(None) => out.push_str("null"),
+            Value::Bool(v) => out.push_str(if *v { "true" } else { "false" }),
+            Value::Number(v) => self.write_number(*v, out)?,
+            Value::Char(v) => match self.chars {
+                Chars::String => write_str(v.encode_utf8(&mut [0; 4]), out),
+                Chars::CodePoint => display(*v as u32, out),
+            },
+            Value::String(v) => write_str(v, out),
+            Value::Bytes(v) => match self.bytes_encoding {
+                Some(encoding) => write_str(&encoding.encode(v), out),
+                None => {
+                    enter(depth)?;
+                    out.push('[');
+                    for (index, byte) in v.iter().enumerate() {
+                        if index > 0 {
+                            out.push(',');
+                        }
+                        display(byte, out);
+                    }
+                    out.push(']');
+                }
+            },
+            Value::Seq(v) => self.write_array(v, depth, out)?,
+            Value::Tuple(v) => match self.tuples {
+                Tuples::Array => self.write_array(v, depth, out)?,
+                Tuples::Tagged => {
+                    let depth = enter(depth)?;
+                    out.push('{');
+                    write_str(TUPLE, out);
+                    out.push(':');
+                    self.write_array(v, depth, out)?;
+                    out.push('}');
+                }
+            },
+            Value::Map(v) => self.write_map(v, depth, out)?,
+            Value::Option(Some(v)) => self.write(v, depth, out)?,
+            Value::Struct(v) => match self.names {
+                Names::Drop => self.write_data(&v.data, depth, out)?,
+                Names::Tagged => self.write_tagged(&v.name, &v.data, depth, out)?,
+            },
+            Value::Enum(v) => match (self.names, &v.data) {
+                (Names::Drop, Data::Unit) => write_str(&v.variant, out),
+                (Names::Drop, data) => {
+                    let depth = enter(depth)?;
+                    out.push('{');
+                    write_str(&v.variant, out);
+                    out.push(':');
+                    self.write_data(data, depth, out)?;
+                    out.push('}');
+                }
+                (Names::Tagged, data) => {
+                    let name = [&*v.name, "::", &*v.variant].concat();
+                    self.write_tagged(&name, data, depth, out)?;
+                }
+            },
+        }
+        Ok(())
+    }
+
+    fn write_number(self, number: Number, out: &mut String) -> Result<(), Error> {
+        match number {
+            Number::I8(v) => display(v, out),
+            Number::U8(v) => display(v, out),
+            Number::I16(v) => display(v, out),
+            Number::U16(v) => display(v, out),
+            Number::I32(v) => display(v, out),
+            Number::U32(v) => display(v, out),
+            Number::I64(v) => display(v, out),
+            Number::U64(v) => display(v, out),
+            // `Debug` keeps the fraction of whole floats, so that they are read back as floats.
+            Number::F32(v) if v.is_finite() => display(format_args!("{v:?}"), out),
+            Number::F64(v) if v.is_finite() => display(format_args!("{v:?}"), out),
+            Number::F32(v) => self.write_non_finite(v as f64, number, out)?,
+            Number::F64(v) => self.write_non_finite(v, number, out)?,
+            Number::I128(v) => match self.wide_integers {
+                WideIntegers::String => display(format_args!("\"{v}\""), out),
+                WideIntegers::Number => display(v, out),
+            },
+            Number::U128(v) => match self.wide_integers {
+                WideIntegers::String => display(format_args!("\"{v}\""), out),
+                WideIntegers::Number => display(v, out),
+            },
+        }
+        Ok(())
+    }
+
+    fn write_non_finite(self, float: f64, number: Number, out: &mut String) -> Result<(), Error> {
+        match self.non_finite_float(float, number)? {
+            Some(v) => write_str(v, out),
+            None => out.push_str("null"),
+        }
+        Ok(())
+    }
+
+    fn write_array(
+        self,
+        values: &[Value<'_>],
+        depth: usize,
+        out: &mut String,
+    ) -> Result<(), Error> {
+        let depth = enter(depth)?;
+        out.push('[');
+        for (index, value) in values.iter().enumerate() {
+            if index > 0 {
+                out.push(',');
+            }
+            self.write(value, depth, out)?;
+        }
+        out.push(']');
+        Ok(())
+    }
+
+    fn write_map(
+        self,
+        entries: &[(Value<'_>, Value<'_>)],
+        depth: usize,
+        out: &mut String,
+    ) -> Result<(), Error> {
+        let depth = enter(depth)?;
+        let entries_as_arrays = self.entries(entries);
+        let mut keys = BTreeSet::new();
+        out.push(if entries_as_arrays { '[' } else { '{' });
+        for (index, (key, value)) in entries.iter().enumerate() {
+            if index > 0 {
+                out.push(',');
+            }
+            if entries_as_arrays {
+                let depth = enter(depth)?;
+                out.push('[');
+                self.write(key, depth, out)?;
+                out.push(',');
+                self.write(value, depth, out)?;
+                out.push(']');
+            } else {
+                let key = self.key(key)?;
+                if keys.contains(&key) {
+                    return Err(duplicate_key(&key));
+                }
+                write_str(&key, out);
+                keys.insert(key);
+                out.push(':');
+                self.write(value, depth, out)?;
+            }
+        }
+        out.push(if entries_as_arrays { ']' } else { '}' });
+        Ok(())
+    }
+
+    /// Writes the data of a struct or enum variant, without its name.
+    fn write_data(self, data: &Data<'_>, depth: usize, out: &mut String) -> Result<(), Error> {
+        match data {
+            Data::Unit => out.push_str("null"),
+            Data::NewType { value } => self.write(value, depth, out)?,
+            Data::Tuple { values } => self.write_array(values, depth, out)?,
+            Data::Struct { fields } => {
+                let depth = enter(depth)?;
+                out.push('{');
+                self.write_fields(fields, false, depth, out)?;
+                out.push('}');
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes the data of a struct or enum variant, tagged with `name`.
+    fn write_tagged(
+        self,
+        name: &str,
+        data: &Data<'_>,
+        depth: usize,
+        out: &mut String,
+    ) -> Result<(), Error> {
+        let depth = enter(depth)?;
+        out.push('{');
+        write_str(TYPE, out);
+        out.push(':');
+        write_str(name, out);
+        match data {
+            Data::Unit => {}
+            Data::Struct { fields } => self.write_fields(fields, true, depth, out)?,
+            data => {
+                out.push(',');
+                write_str(VALUE, out);
+                out.push(':');
+                self.write_data(data, depth, out)?;
+            }
+        }
+        out.push('}');
+        Ok(())
+    }
+
+    fn write_fields(
+        self,
+        fields: &[(Cow<'static, str>, Value<'_>)],
+        tagged: bool,
+        depth: usize,
+        out: &mut String,
+    ) -> Result<(), Error> {
+        // The tag takes precedence over a field with the same name.
+        let fields = fields.iter().filter(|(key, _)| !tagged || key != TYPE);
+        let mut keys = BTreeSet::new();
+        for (index, (key, value)) in fields.enumerate() {
+            if !keys.insert(&**key) {
+                return Err(duplicate_key(key));
+            }
+            if tagged || index > 0 {
+                out.push(',');
+            }
+            write_str(key, out);
+            out.push(':');
+            self.write(value, depth, out)?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the depth of the values nested inside an array or object at `depth`.
+///
+/// The depth is capped like it is when reading, so that everything written can be read back.
+fn enter(depth: usize) -> Result<usize, Error> {
+    match depth < MAX_DEPTH {
+        true => Ok(depth + 1),
+        false => Err(Error::limit_exceeded(Limit::Depth(MAX_DEPTH))),
+    }
+}
+
+fn display(value: impl fmt::Display, out: &mut String) {
+    // Writing to a `String` never fails.
+    let _ = write!(out, "{value}");
+}
+
+/// Writes `text` as a JSON string, escaping quotes, backslashes and control characters.
+fn write_str(text: &str, out: &mut String) {
+    out.push('"');
+    let mut start = 0;
+    for (index, byte) in text.bytes().enumerate() {
+        let escape = match byte {
+            b'"' => "\\\"",
+            b'\\' => "\\\\",
+            b'\n' => "\\n",
+            b'\r' => "\\r",
+            b'\t' => "\\t",
+            0x08 => "\\b",
+            0x0c => "\\f",
+            0x00..=0x1f => "",
+            _ => continue,
+        };
+        out.push_str(&text[start..index]);
+        match escape {
+            "" => display(format_args!("\\u{byte:04x}"), out),
+            escape => out.push_str(escape),
+        }
+        start = index + 1;
+    }
+    out.push_str(&text[start..]);
+    out.push('"');
+}
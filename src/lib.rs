@@ -7,26 +7,87 @@
 
 extern crate alloc;
 
+mod macros;
+
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+#[cfg(feature = "arena")]
+pub mod arena;
+mod builder;
+mod bytes;
+#[cfg(feature = "cbor")]
+mod cbor;
+#[cfg(feature = "derive")]
+pub mod conformance;
+mod convert;
 mod de;
 mod error;
+#[cfg(any(feature = "arbitrary", feature = "proptest"))]
+mod generate;
+mod hash;
+#[cfg(feature = "std")]
+mod intern;
+pub mod json;
+mod limits;
+#[cfg(feature = "msgpack")]
+mod msgpack;
 mod number;
+mod path;
+#[cfg(feature = "proptest")]
+mod proptest;
+mod redact;
+#[cfg(feature = "serde")]
+mod rest;
+#[cfg(feature = "ron")]
+mod ron;
 mod ser;
 mod tests;
+pub mod token;
+#[cfg(feature = "toml")]
+mod toml;
+#[cfg(feature = "yaml")]
+mod yaml;
 
 use alloc::borrow::Cow;
 use alloc::boxed::Box;
 use alloc::string::String;
 use alloc::vec::Vec;
 
+pub use builder::ObjectBuilder;
+pub use builder::TupleBuilder;
+pub use bytes::BytesEncoding;
 pub use error::Data as FoundData;
 pub use error::Error;
 pub use error::ErrorKind;
+pub use error::Errors;
 pub use error::Expected;
 pub use error::Found;
+pub use error::Limit;
 pub use error::Result;
+#[cfg(any(feature = "arbitrary", feature = "proptest"))]
+pub use generate::Roundtrip;
+#[cfg(feature = "std")]
+pub use intern::Interner;
+pub use limits::Limits;
 pub use number::Number;
+pub use redact::HashKey;
+pub use redact::RedactPolicy;
+pub use redact::Redactor;
+pub use redact::Replacement;
+pub use redact::Selector;
 #[cfg(feature = "serde")]
-pub use {de::Deserializer, de::Unexpected, de::ValueVisitor, ser::Serializer};
+pub use {
+    de::Deserializer, de::Ignored, de::Unexpected, de::ValueVisitor, rest::Rest,
+    ser::SerializeBorrowed, ser::Serializer,
+};
+
+#[doc(hidden)] // Not public API. Used by the `value!` macro.
+pub mod __private {
+    pub use alloc::borrow::Cow;
+    pub use alloc::boxed::Box;
+    pub use alloc::vec;
+    pub use core::option::Option::{None, Some};
+}
 
 /// Represents struct and enum data.
 #[derive(Debug, Clone, PartialEq, PartialOrd)]
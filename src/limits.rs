@@ -0,0 +1,465 @@
+use crate::Data;
+use crate::Error;
+use crate::Limit;
+use crate::Value;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp;
+use core::sync::atomic::AtomicUsize;
+use core::sync::atomic::Ordering;
+#[cfg(feature = "serde")]
+use {
+    crate::ValueVisitor, alloc::boxed::Box, alloc::string::String, core::fmt, core::mem, serde::de,
+};
+
+/// Limits on the size of values, for handling data from untrusted sources.
+///
+/// Every limit is unset by default.
+///
+/// - The depth of a value is how deeply it nests other values. Options with a value, newtypes,
+///   sequences, tuples, maps, and structs and enum variants with data nest their contents one
+///   level deeper, so `1u8` has a depth of `0` and `[[1u8]]` has a depth of `2`.
+/// - Elements are the elements of sequences and tuples, the entries of maps and the fields of
+///   structs and enum variants, counted across the whole value.
+/// - The string length limit applies to each string and byte array on its own, while the total
+///   bytes limit applies to all strings, byte arrays and struct field names together.
+///
+/// Limits can be enforced by [crate::Serializer::limits] while serializing, checked against a
+/// value before deserializing it with [crate::Deserializer::limits], and enforced while
+/// deserializing a [Value] from another format with [Limits::deserialize]. Limits are only
+/// configuration, so they can be shared between threads and used any number of times. The
+/// elements and bytes are counted afresh each time a value is serialized, checked or
+/// deserialized.
+///
+/// ## Example
+///
+/// ```
+/// # use serde_content::{value, Deserializer, ErrorKind, Limit, Limits};
+/// let limits = Limits::new().max_depth(1).max_elements(100);
+///
+/// let error = Deserializer::new(value!([[1u8]])).limits(&limits).unwrap_err();
+/// assert_eq!(error.kind(), &ErrorKind::LimitExceeded(Limit::Depth(1)));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, PartialOrd)]
+pub struct Limits {
+    max_depth: Option<usize>,
+    max_elements: Option<usize>,
+    max_string_len: Option<usize>,
+    max_total_bytes: Option<usize>,
+}
+
+/// The elements and bytes counted so far against the totals of [Limits].
+///
+/// The counts are atomic so that the [Serializer](crate::Serializer) borrowing them can be
+/// shared between threads. They are only ever updated by the call that owns them, so loads and
+/// stores are enough.
+#[derive(Debug, Default)]
+pub(crate) struct Counts {
+    elements: AtomicUsize,
+    bytes: AtomicUsize,
+}
+
+impl Counts {
+    pub(crate) const fn new() -> Self {
+        Self {
+            elements: AtomicUsize::new(0),
+            bytes: AtomicUsize::new(0),
+        }
+    }
+
+    fn get(&self) -> (usize, usize) {
+        (
+            self.elements.load(Ordering::Relaxed),
+            self.bytes.load(Ordering::Relaxed),
+        )
+    }
+}
+
+impl PartialEq for Counts {
+    fn eq(&self, other: &Self) -> bool {
+        self.get() == other.get()
+    }
+}
+
+impl PartialOrd for Counts {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        self.get().partial_cmp(&other.get())
+    }
+}
+
+impl Limits {
+    /// Creates a set of limits with none of them set.
+    pub const fn new() -> Self {
+        Self {
+            max_depth: None,
+            max_elements: None,
+            max_string_len: None,
+            max_total_bytes: None,
+        }
+    }
+
+    /// Limit how deeply values can be nested.
+    pub const fn max_depth(mut self, max: usize) -> Self {
+        self.max_depth = Some(max);
+        self
+    }
+
+    /// Limit the number of elements, map entries and fields in total.
+    pub const fn max_elements(mut self, max: usize) -> Self {
+        self.max_elements = Some(max);
+        self
+    }
+
+    /// Limit the length in bytes of each string and byte array.
+    pub const fn max_string_len(mut self, max: usize) -> Self {
+        self.max_string_len = Some(max);
+        self
+    }
+
+    /// Limit the number of bytes in strings, byte arrays and field names in total.
+    pub const fn max_total_bytes(mut self, max: usize) -> Self {
+        self.max_total_bytes = Some(max);
+        self
+    }
+
+    /// Checks that `value` is within the limits.
+    ///
+    /// The value is walked without recursing, so even values that are nested too deeply to be
+    /// deserialized can be checked.
+    pub fn check(&self, value: &Value) -> Result<(), Error> {
+        let counts = Counts::new();
+        let mut stack = vec![(value, 0)];
+        while let Some((value, depth)) = stack.pop() {
+            match value {
+                Value::String(v) => self.bytes(&counts, v.len())?,
+                Value::Bytes(v) => self.bytes(&counts, v.len())?,
+                Value::Seq(v) | Value::Tuple(v) => {
+                    let depth = self.enter(depth)?;
+                    self.elements(&counts, v.len())?;
+                    stack.extend(v.iter().map(|v| (v, depth)));
+                }
+                Value::Map(v) => {
+                    let depth = self.enter(depth)?;
+                    self.elements(&counts, v.len())?;
+                    for (key, value) in v {
+                        stack.push((key, depth));
+                        stack.push((value, depth));
+                    }
+                }
+                Value::Option(Some(v)) => stack.push((v, self.enter(depth)?)),
+                Value::Struct(v) => self.data(&counts, &v.data, depth, &mut stack)?,
+                Value::Enum(v) => self.data(&counts, &v.data, depth, &mut stack)?,
+                Value::Unit
+                | Value::Bool(_)
+                | Value::Number(_)
+                | Value::Char(_)
+                | Value::Option(None) => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Deserializes a [Value] from any self-describing format, within the limits.
+    ///
+    /// Unlike [ValueVisitor], this doesn't trust the size hints of the format to allocate
+    /// memory up front. Since the error type is the format's own, limits that are exceeded are
+    /// reported with the message of [crate::ErrorKind::LimitExceeded].
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use serde_content::{value, Deserializer, Limits};
+    /// let limits = Limits::new().max_string_len(8);
+    ///
+    /// let value = limits.deserialize(Deserializer::new(value!(["short"]))).unwrap();
+    /// assert_eq!(value, value!(["short"]));
+    ///
+    /// let error = limits.deserialize(Deserializer::new(value!(["too long to fit"]))).unwrap_err();
+    /// assert_eq!(error.to_string(), "exceeded the maximum string length of 8 bytes");
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn deserialize<'de, D>(&self, deserializer: D) -> Result<Value<'de>, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_any(Visitor {
+            limits: self,
+            counts: &Counts::new(),
+            depth: 0,
+        })
+    }
+
+    /// Returns the depth limit, if one is set.
+    pub(crate) const fn depth(&self) -> Option<usize> {
+        self.max_depth
+    }
+
+    /// Returns the depth of the values nested inside a value at `depth`.
+    pub(crate) fn enter(&self, depth: usize) -> Result<usize, Error> {
+        let depth = depth + 1;
+        match self.max_depth {
+            Some(max) if depth > max => Err(Error::limit_exceeded(Limit::Depth(max))),
+            _ => Ok(depth),
+        }
+    }
+
+    /// Counts `count` more elements, map entries or fields in `counts`.
+    pub(crate) fn elements(&self, counts: &Counts, count: usize) -> Result<(), Error> {
+        let total = counts
+            .elements
+            .load(Ordering::Relaxed)
+            .saturating_add(count);
+        counts.elements.store(total, Ordering::Relaxed);
+        match self.max_elements {
+            Some(max) if total > max => Err(Error::limit_exceeded(Limit::Elements(max))),
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks the length of a string or byte array of `len` bytes on its own.
+    pub(crate) fn string(&self, len: usize) -> Result<(), Error> {
+        match self.max_string_len {
+            Some(max) if len > max => Err(Error::limit_exceeded(Limit::StringLength(max))),
+            _ => Ok(()),
+        }
+    }
+
+    /// Counts a string, byte array or field name of `len` bytes in `counts`.
+    pub(crate) fn bytes(&self, counts: &Counts, len: usize) -> Result<(), Error> {
+        self.string(len)?;
+        let total = counts.bytes.load(Ordering::Relaxed).saturating_add(len);
+        counts.bytes.store(total, Ordering::Relaxed);
+        match self.max_total_bytes {
+            Some(max) if total > max => Err(Error::limit_exceeded(Limit::TotalBytes(max))),
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks the data of a struct or enum at `depth` and queues its values.
+    fn data<'v, 'a>(
+        &self,
+        counts: &Counts,
+        data: &'v Data<'a>,
+        depth: usize,
+        stack: &mut Vec<(&'v Value<'a>, usize)>,
+    ) -> Result<(), Error> {
+        match data {
+            Data::Unit => {}
+            Data::NewType { value } => stack.push((value, self.enter(depth)?)),
+            Data::Tuple { values } => {
+                let depth = self.enter(depth)?;
+                self.elements(counts, values.len())?;
+                stack.extend(values.iter().map(|v| (v, depth)));
+            }
+            Data::Struct { fields } => {
+                let depth = self.enter(depth)?;
+                self.elements(counts, fields.len())?;
+                for (key, value) in fields {
+                    self.bytes(counts, key.len())?;
+                    stack.push((value, depth));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Caps an untrusted size hint so that it can't make us allocate too much memory up front.
+#[cfg(feature = "serde")]
+pub(crate) fn capacity<T>(hint: Option<usize>) -> usize {
+    const MAX_BYTES: usize = 1024 * 1024;
+    let max = MAX_BYTES / mem::size_of::<T>().max(1);
+    hint.unwrap_or_default().min(max)
+}
+
+/// Deserializes a [Value] at `depth` within `limits`.
+#[cfg(feature = "serde")]
+#[derive(Clone, Copy)]
+struct Visitor<'l> {
+    limits: &'l Limits,
+    counts: &'l Counts,
+    depth: usize,
+}
+
+#[cfg(feature = "serde")]
+impl Visitor<'_> {
+    /// Returns the visitor for the values nested inside this one.
+    fn enter<E>(self) -> Result<Self, E>
+    where
+        E: de::Error,
+    {
+        match self.limits.enter(self.depth) {
+            Ok(depth) => Ok(Self { depth, ..self }),
+            Err(error) => Err(E::custom(error)),
+        }
+    }
+
+    /// Counts a string or byte array of `len` bytes.
+    fn bytes<E>(self, len: usize) -> Result<(), E>
+    where
+        E: de::Error,
+    {
+        self.limits.bytes(self.counts, len).map_err(E::custom)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> de::DeserializeSeed<'de> for Visitor<'_> {
+    type Value = Value<'de>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_any(self)
+    }
+}
+
+// Scalars other than strings and byte arrays can't exceed any limit.
+#[cfg(feature = "serde")]
+macro_rules! forward {
+    ($($method:ident($ty:ty),)*) => {
+        $(
+            fn $method<E>(self, value: $ty) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                ValueVisitor.$method(value)
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "serde")]
+impl<'de> de::Visitor<'de> for Visitor<'_> {
+    type Value = Value<'de>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("any value")
+    }
+
+    forward! {
+        visit_bool(bool),
+        visit_i8(i8),
+        visit_i16(i16),
+        visit_i32(i32),
+        visit_i64(i64),
+        visit_i128(i128),
+        visit_u8(u8),
+        visit_u16(u16),
+        visit_u32(u32),
+        visit_u64(u64),
+        visit_u128(u128),
+        visit_f32(f32),
+        visit_f64(f64),
+        visit_char(char),
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        self.bytes(value.len())?;
+        ValueVisitor.visit_str(value)
+    }
+
+    fn visit_borrowed_str<E>(self, value: &'de str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        self.bytes(value.len())?;
+        ValueVisitor.visit_borrowed_str(value)
+    }
+
+    fn visit_string<E>(self, value: String) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        self.bytes(value.len())?;
+        ValueVisitor.visit_string(value)
+    }
+
+    fn visit_bytes<E>(self, value: &[u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        self.bytes(value.len())?;
+        ValueVisitor.visit_bytes(value)
+    }
+
+    fn visit_borrowed_bytes<E>(self, value: &'de [u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        self.bytes(value.len())?;
+        ValueVisitor.visit_borrowed_bytes(value)
+    }
+
+    fn visit_byte_buf<E>(self, value: Vec<u8>) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        self.bytes(value.len())?;
+        ValueVisitor.visit_byte_buf(value)
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Value::Unit)
+    }
+
+    fn visit_none<E>(self) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Value::Option(None))
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        let value = de::DeserializeSeed::deserialize(self.enter()?, deserializer)?;
+        Ok(Value::Option(Some(Box::new(value))))
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        de::DeserializeSeed::deserialize(self.enter()?, deserializer)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::SeqAccess<'de>,
+    {
+        let visitor = self.enter()?;
+        let mut vec = Vec::with_capacity(capacity::<Value>(seq.size_hint()));
+        while let Some(value) = seq.next_element_seed(visitor)? {
+            self.limits
+                .elements(self.counts, 1)
+                .map_err(de::Error::custom)?;
+            vec.push(value);
+        }
+        Ok(Value::Seq(vec))
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::MapAccess<'de>,
+    {
+        let visitor = self.enter()?;
+        let mut vec = Vec::with_capacity(capacity::<(Value, Value)>(map.size_hint()));
+        while let Some(key) = map.next_key_seed(visitor)? {
+            let value = map.next_value_seed(visitor)?;
+            self.limits
+                .elements(self.counts, 1)
+                .map_err(de::Error::custom)?;
+            vec.push((key, value));
+        }
+        Ok(Value::Map(vec))
+    }
+}
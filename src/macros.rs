@@ -0,0 +1,277 @@
+/// Constructs a [Value](crate::Value) using Rust-like literal syntax.
+///
+/// The resulting value is the same as the one [Serializer](crate::Serializer) produces for the
+/// equivalent Rust value. Struct, enum, variant and field names are `&'static str`s, so the value
+/// serializes back into real structs and enums.
+///
+/// | Syntax                         | Value                                          |
+/// |--------------------------------|------------------------------------------------|
+/// | `()`                           | `Value::Unit`                                  |
+/// | `true`, `1u8`, `'c'`, `"str"`  | `Value::from(literal)`                         |
+/// | `None`, `Some(v)`              | `Value::Option`                                |
+/// | `[a, b]`                       | `Value::Seq`                                   |
+/// | `(a, b)`, `(a,)`               | `Value::Tuple`                                 |
+/// | `{ k: v }`                     | `Value::Map`                                   |
+/// | `Name`                         | unit struct                                    |
+/// | `Name(v)`                      | newtype struct                                 |
+/// | `Name(a, b)`                   | tuple struct                                   |
+/// | `Name { x: v }`                | object-like struct                             |
+/// | `Name::Variant @ 0`, `Name::Variant @ 1 (v)`, ... | enum variants of the same shapes as structs |
+/// | `(expr)`                       | `Value::from(expr)` for any Rust expression    |
+///
+/// ## Enum variant indices
+///
+/// The macro can't know where a variant is declared in its enum, so **enum variants must give
+/// their `variant_index` after an `@`**, like `Name::Variant @ 2 (v)`. [Value]s compare their
+/// variant indices, so `value!(Shape::Circle @ 1 (1.0f64))` is only equal to what
+/// [Serializer](crate::Serializer) produces for `Shape::Circle(1.0)` if `Circle` is the second
+/// variant of `Shape`. Leaving the index out is a compile error.
+///
+/// For the same reason, any path of two names is an enum variant, so constants like `u8::MAX`
+/// must be wrapped in parentheses, as `(u8::MAX)`, to be used as expressions.
+///
+/// ```compile_fail
+/// # use serde_content::value;
+/// let value = value!(Shape::Circle(1.0f64));
+/// ```
+///
+/// [Value]: crate::Value
+///
+/// ## Example
+///
+/// ```
+/// # use serde_content::{value, Value};
+/// let radius = 3.0f64;
+/// let value = value!({
+///     "point": Point { x: 1i32, y: 2i32 },
+///     "shape": Shape::Circle @ 1 ((radius)),
+///     "bytes": [1u8, 2u8],
+///     "nothing": Some(()),
+/// });
+/// assert!(matches!(value, Value::Map(_)));
+/// ```
+#[macro_export]
+macro_rules! value {
+    ($($value:tt)+) => {
+        $crate::__value!(@value $($value)+)
+    };
+}
+
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __value {
+    // Values.
+
+    (@value ()) => {
+        $crate::Value::Unit
+    };
+
+    (@value $value:literal) => {
+        $crate::Value::from($value)
+    };
+
+    (@value None) => {
+        $crate::Value::Option($crate::__private::None)
+    };
+
+    (@value Some($($value:tt)+)) => {
+        $crate::Value::Option($crate::__private::Some($crate::__private::Box::new(
+            $crate::__value!(@value $($value)+),
+        )))
+    };
+
+    (@value [$($values:tt)*]) => {
+        $crate::Value::Seq($crate::__value!(@seq [] [] $($values)*))
+    };
+
+    (@value {$($entries:tt)*}) => {
+        $crate::Value::Map($crate::__value!(@map [] [] $($entries)*))
+    };
+
+    (@value ($($values:tt)+)) => {
+        $crate::__value!(@tuple [] [] $($values)+)
+    };
+
+    (@value $name:ident :: $variant:ident @ $index:literal $($data:tt)?) => {
+        $crate::Value::Enum($crate::__private::Box::new($crate::Enum {
+            name: $crate::__private::Cow::Borrowed(stringify!($name)),
+            variant_index: $index,
+            variant: $crate::__private::Cow::Borrowed(stringify!($variant)),
+            data: $crate::__value!(@data $($data)?),
+        }))
+    };
+
+    (@value $name:ident :: $variant:ident $($data:tt)?) => {
+        ::core::compile_error!(::core::concat!(
+            "give the index of enum variant `",
+            ::core::stringify!($name),
+            "::",
+            ::core::stringify!($variant),
+            "` after an `@`, like `",
+            ::core::stringify!($name),
+            "::",
+            ::core::stringify!($variant),
+            " @ 0`, or wrap an expression in parentheses, like `(",
+            ::core::stringify!($name),
+            "::",
+            ::core::stringify!($variant),
+            ")`",
+        ))
+    };
+
+    (@value $name:ident $($data:tt)?) => {
+        $crate::Value::Struct($crate::__private::Box::new($crate::Struct {
+            name: $crate::__private::Cow::Borrowed(stringify!($name)),
+            data: $crate::__value!(@data $($data)?),
+        }))
+    };
+
+    // Struct and enum data.
+
+    (@data) => {
+        $crate::Data::Unit
+    };
+
+    (@data {$($fields:tt)*}) => {
+        $crate::Data::Struct {
+            fields: $crate::__value!(@fields [] $($fields)*),
+        }
+    };
+
+    (@data ()) => {
+        $crate::Data::Tuple {
+            values: $crate::__private::vec![],
+        }
+    };
+
+    (@data ($($values:tt)+)) => {
+        $crate::__value!(@data_tuple [] [] $($values)+)
+    };
+
+    // Sequences: `[a, b]`.
+
+    (@seq [$($done:expr,)*] []) => {
+        $crate::__private::vec![$($done,)*]
+    };
+
+    (@seq [$($done:expr,)*] [$($value:tt)+]) => {
+        $crate::__private::vec![$($done,)* $crate::__value!(@value $($value)+)]
+    };
+
+    (@seq [$($done:expr,)*] [$($value:tt)+] , $($rest:tt)*) => {
+        $crate::__value!(@seq [$($done,)* $crate::__value!(@value $($value)+),] [] $($rest)*)
+    };
+
+    (@seq [$($done:expr,)*] [$($value:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::__value!(@seq [$($done,)*] [$($value)* $next] $($rest)*)
+    };
+
+    // Tuples: `(a, b)`, or a Rust expression: `(expr)`.
+
+    (@tuple [] [$($expr:tt)+]) => {
+        $crate::Value::from($($expr)+)
+    };
+
+    (@tuple [$($done:expr,)+] []) => {
+        $crate::Value::Tuple($crate::__private::vec![$($done,)+])
+    };
+
+    (@tuple [$($done:expr,)+] [$($value:tt)+]) => {
+        $crate::Value::Tuple($crate::__private::vec![
+            $($done,)+
+            $crate::__value!(@value $($value)+)
+        ])
+    };
+
+    (@tuple [$($done:expr,)*] [$($value:tt)+] , $($rest:tt)*) => {
+        $crate::__value!(@tuple [$($done,)* $crate::__value!(@value $($value)+),] [] $($rest)*)
+    };
+
+    (@tuple [$($done:expr,)*] [$($value:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::__value!(@tuple [$($done,)*] [$($value)* $next] $($rest)*)
+    };
+
+    // Tuple data: `Name(a, b)`, or newtype data: `Name(a)`.
+
+    (@data_tuple [] [$($value:tt)+]) => {
+        $crate::Data::NewType {
+            value: $crate::__value!(@value $($value)+),
+        }
+    };
+
+    (@data_tuple [$($done:expr,)+] [$($value:tt)*]) => {
+        $crate::Data::Tuple {
+            values: $crate::__value!(@seq [$($done,)+] [$($value)*]),
+        }
+    };
+
+    (@data_tuple [$($done:expr,)*] [$($value:tt)+] , $($rest:tt)*) => {
+        $crate::__value!(
+            @data_tuple [$($done,)* $crate::__value!(@value $($value)+),] [] $($rest)*
+        )
+    };
+
+    (@data_tuple [$($done:expr,)*] [$($value:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::__value!(@data_tuple [$($done,)*] [$($value)* $next] $($rest)*)
+    };
+
+    // Maps: `{ k: v }`.
+
+    (@map [$($done:expr,)*] []) => {
+        $crate::__private::vec![$($done,)*]
+    };
+
+    (@map [$($done:expr,)*] [$($key:tt)+] : $($rest:tt)*) => {
+        $crate::__value!(@map_value [$($done,)*] [$($key)+] [] $($rest)*)
+    };
+
+    (@map [$($done:expr,)*] [$($key:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::__value!(@map [$($done,)*] [$($key)* $next] $($rest)*)
+    };
+
+    (@map_value [$($done:expr,)*] [$($key:tt)+] [$($value:tt)+]) => {
+        $crate::__value!(@map [$($done,)* (
+            $crate::__value!(@value $($key)+),
+            $crate::__value!(@value $($value)+),
+        ),] [])
+    };
+
+    (@map_value [$($done:expr,)*] [$($key:tt)+] [$($value:tt)+] , $($rest:tt)*) => {
+        $crate::__value!(@map [$($done,)* (
+            $crate::__value!(@value $($key)+),
+            $crate::__value!(@value $($value)+),
+        ),] [] $($rest)*)
+    };
+
+    (@map_value [$($done:expr,)*] [$($key:tt)+] [$($value:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::__value!(@map_value [$($done,)*] [$($key)+] [$($value)* $next] $($rest)*)
+    };
+
+    // Struct fields: `{ x: v }`.
+
+    (@fields [$($done:expr,)*]) => {
+        $crate::__private::vec![$($done,)*]
+    };
+
+    (@fields [$($done:expr,)*] $field:ident : $($rest:tt)*) => {
+        $crate::__value!(@field_value [$($done,)*] $field [] $($rest)*)
+    };
+
+    (@field_value [$($done:expr,)*] $field:ident [$($value:tt)+]) => {
+        $crate::__value!(@fields [$($done,)* (
+            $crate::__private::Cow::Borrowed(stringify!($field)),
+            $crate::__value!(@value $($value)+),
+        ),])
+    };
+
+    (@field_value [$($done:expr,)*] $field:ident [$($value:tt)+] , $($rest:tt)*) => {
+        $crate::__value!(@fields [$($done,)* (
+            $crate::__private::Cow::Borrowed(stringify!($field)),
+            $crate::__value!(@value $($value)+),
+        ),] $($rest)*)
+    };
+
+    (@field_value [$($done:expr,)*] $field:ident [$($value:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::__value!(@field_value [$($done,)*] $field [$($value)* $next] $($rest)*)
+    };
+}
@@ -0,0 +1,183 @@
+use crate::Data;
+use crate::Error;
+use crate::Found;
+use crate::Number;
+use crate::Struct;
+use crate::Value;
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::string::ToString;
+use alloc::vec;
+use alloc::vec::Vec;
+use rmpv::Integer;
+use rmpv::Value as MsgPack;
+use rmpv::MSGPACK_EXT_STRUCT_NAME as EXT;
+
+impl TryFrom<Value<'_>> for MsgPack {
+    type Error = Error;
+
+    /// Converts a [Value] into MessagePack, the way `rmpv` serializes the same data.
+    ///
+    /// Byte arrays and map keys of any type convert natively. A newtype struct named
+    /// `_ExtStruct`, described on the conversion from MessagePack, becomes an extension. The
+    /// unit type and `None` become nil, and characters become strings. Struct names are
+    /// dropped, while enums are externally tagged by their variant.
+    ///
+    /// MessagePack integers are at most 64 bits wide, so a [Number::I128] or [Number::U128]
+    /// that doesn't fit in an `i64` or a `u64` is an error, just like it is when `rmpv`
+    /// serializes it.
+    fn try_from(value: Value<'_>) -> Result<Self, Self::Error> {
+        match value {
+            Value::Unit | Value::Option(None) => Ok(MsgPack::Nil),
+            Value::Bool(v) => Ok(MsgPack::Boolean(v)),
+            Value::Number(v) => number(v),
+            Value::Char(v) => Ok(MsgPack::String(v.to_string().into())),
+            Value::String(v) => Ok(MsgPack::String(v.into())),
+            Value::Bytes(v) => Ok(MsgPack::Binary(v.into_owned())),
+            Value::Seq(v) | Value::Tuple(v) => array(v),
+            Value::Map(v) => v
+                .into_iter()
+                .map(|(key, value)| Ok((key.try_into()?, value.try_into()?)))
+                .collect::<Result<_, Error>>()
+                .map(MsgPack::Map),
+            Value::Option(Some(v)) => MsgPack::try_from(*v),
+            Value::Struct(v) => match extension(*v) {
+                Ok(ext) => Ok(ext),
+                Err(v) => data(v.data),
+            },
+            Value::Enum(v) => match v.data {
+                Data::Unit => Ok(MsgPack::String(v.variant.into())),
+                data => Ok(MsgPack::Map(vec![(
+                    MsgPack::String(v.variant.into()),
+                    self::data(data)?,
+                )])),
+            },
+        }
+    }
+}
+
+/// Converts a struct of the `_ExtStruct` convention into an extension.
+fn extension(value: Struct<'_>) -> Result<MsgPack, Struct<'_>> {
+    if value.name != EXT {
+        return Err(value);
+    }
+    match value.data {
+        Data::NewType {
+            value: Value::Tuple(values),
+        } => match <[Value; 2]>::try_from(values) {
+            Ok([Value::Number(Number::I8(tag)), Value::Bytes(bytes)]) => {
+                Ok(MsgPack::Ext(tag, bytes.into_owned()))
+            }
+            Ok(values) => Err(Struct {
+                data: Data::NewType {
+                    value: Value::Tuple(values.into()),
+                },
+                ..value
+            }),
+            Err(values) => Err(Struct {
+                data: Data::NewType {
+                    value: Value::Tuple(values),
+                },
+                ..value
+            }),
+        },
+        data => Err(Struct { data, ..value }),
+    }
+}
+
+fn number(number: Number) -> Result<MsgPack, Error> {
+    match number {
+        Number::I8(v) => Ok(MsgPack::Integer(v.into())),
+        Number::U8(v) => Ok(MsgPack::Integer(v.into())),
+        Number::I16(v) => Ok(MsgPack::Integer(v.into())),
+        Number::U16(v) => Ok(MsgPack::Integer(v.into())),
+        Number::I32(v) => Ok(MsgPack::Integer(v.into())),
+        Number::U32(v) => Ok(MsgPack::Integer(v.into())),
+        Number::I64(v) => Ok(MsgPack::Integer(v.into())),
+        Number::U64(v) => Ok(MsgPack::Integer(v.into())),
+        Number::F32(v) => Ok(MsgPack::F32(v)),
+        Number::F64(v) => Ok(MsgPack::F64(v)),
+        Number::I128(v) => match (u64::try_from(v), i64::try_from(v)) {
+            (Ok(v), _) => Ok(MsgPack::Integer(v.into())),
+            (_, Ok(v)) => Ok(MsgPack::Integer(v.into())),
+            _ => Err(unrepresentable(Found::Number(number))),
+        },
+        Number::U128(v) => u64::try_from(v)
+            .map(|v| MsgPack::Integer(v.into()))
+            .map_err(|_| unrepresentable(Found::Number(number))),
+    }
+}
+
+fn array(values: Vec<Value<'_>>) -> Result<MsgPack, Error> {
+    values
+        .into_iter()
+        .map(MsgPack::try_from)
+        .collect::<Result<_, _>>()
+        .map(MsgPack::Array)
+}
+
+/// Converts the data of a struct or enum variant, without its name.
+fn data(data: Data<'_>) -> Result<MsgPack, Error> {
+    match data {
+        Data::Unit => Ok(MsgPack::Nil),
+        Data::NewType { value } => value.try_into(),
+        Data::Tuple { values } => array(values),
+        Data::Struct { fields } => fields
+            .into_iter()
+            .map(|(key, value)| Ok((MsgPack::String(key.into()), value.try_into()?)))
+            .collect::<Result<_, Error>>()
+            .map(MsgPack::Map),
+    }
+}
+
+fn unrepresentable(found: Found) -> Error {
+    Error::custom(format_args!("{found} can't be represented in MessagePack"))
+}
+
+impl From<MsgPack> for Value<'static> {
+    /// Converts MessagePack into a [Value].
+    ///
+    /// Nil becomes [Value::Unit], and integers [Number::U64] unless they are negative. Strings
+    /// that aren't valid UTF-8 become byte arrays. Maps keep keys of any type.
+    ///
+    /// An extension becomes a newtype struct named `_ExtStruct` holding a tuple of its type as
+    /// a [Number::I8] and its data as bytes, which is how `rmpv` serializes it.
+    fn from(msgpack: MsgPack) -> Self {
+        match msgpack {
+            MsgPack::Nil => Value::Unit,
+            MsgPack::Boolean(v) => Value::Bool(v),
+            MsgPack::Integer(v) => Value::Number(integer(v)),
+            MsgPack::F32(v) => Value::Number(Number::F32(v)),
+            MsgPack::F64(v) => Value::Number(Number::F64(v)),
+            MsgPack::String(v) if v.is_str() => {
+                Value::String(Cow::Owned(v.into_str().unwrap_or_default()))
+            }
+            MsgPack::String(v) => Value::Bytes(Cow::Owned(v.into_bytes())),
+            MsgPack::Binary(v) => Value::Bytes(Cow::Owned(v)),
+            MsgPack::Array(v) => Value::Seq(v.into_iter().map(Value::from).collect()),
+            MsgPack::Map(v) => Value::Map(
+                v.into_iter()
+                    .map(|(key, value)| (key.into(), value.into()))
+                    .collect(),
+            ),
+            MsgPack::Ext(tag, bytes) => Value::Struct(Box::new(Struct {
+                name: Cow::Borrowed(EXT),
+                data: Data::NewType {
+                    value: Value::Tuple(vec![
+                        Value::Number(Number::I8(tag)),
+                        Value::Bytes(Cow::Owned(bytes)),
+                    ]),
+                },
+            })),
+        }
+    }
+}
+
+fn integer(v: Integer) -> Number {
+    match (v.as_u64(), v.as_i64()) {
+        (Some(v), _) => Number::U64(v),
+        (_, Some(v)) => Number::I64(v),
+        // Every MessagePack integer fits in a `u64` or an `i64`.
+        (None, None) => Number::F64(v.as_f64().unwrap_or(f64::NAN)),
+    }
+}
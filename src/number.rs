@@ -1,3 +1,6 @@
+use alloc::string::String;
+use alloc::string::ToString;
+
 /// A containter for all Rust number types.
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
 #[non_exhaustive] // In case Rust introduces new number types.
@@ -103,3 +106,23 @@ impl From<u128> for Number {
         Self::U128(value)
     }
 }
+
+impl Number {
+    /// Renders the number without any type information, so that `1u8` becomes `1`.
+    pub(crate) fn to_plain_string(self) -> String {
+        match self {
+            Number::I8(v) => v.to_string(),
+            Number::U8(v) => v.to_string(),
+            Number::I16(v) => v.to_string(),
+            Number::U16(v) => v.to_string(),
+            Number::I32(v) => v.to_string(),
+            Number::U32(v) => v.to_string(),
+            Number::F32(v) => v.to_string(),
+            Number::I64(v) => v.to_string(),
+            Number::U64(v) => v.to_string(),
+            Number::F64(v) => v.to_string(),
+            Number::I128(v) => v.to_string(),
+            Number::U128(v) => v.to_string(),
+        }
+    }
+}
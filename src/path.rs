@@ -0,0 +1,115 @@
+use crate::Value;
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+
+/// A single step from a value to one of its children.
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub(crate) enum Segment {
+    /// A field of an object-like struct or enum variant.
+    Field(Cow<'static, str>),
+    /// An element of a sequence, a tuple or a tuple struct or enum variant.
+    Index(usize),
+    /// The value of a map entry, identified by its key.
+    Key(String),
+    /// The data of an enum variant.
+    Variant(Cow<'static, str>),
+}
+
+impl Segment {
+    /// Creates a map entry segment from the key of the entry.
+    ///
+    /// Strings and characters are used as is. Other scalars are rendered without any type
+    /// information so that, for example, the key `1u8` becomes `1`.
+    pub(crate) fn key(key: &Value<'_>) -> Self {
+        let key = match key {
+            Value::String(v) => v.to_string(),
+            Value::Char(v) => v.to_string(),
+            Value::Bool(v) => v.to_string(),
+            Value::Number(v) => v.to_plain_string(),
+            key => key.clone().into_found().to_string(),
+        };
+        Self::Key(key)
+    }
+
+    pub(crate) fn matches(&self, pattern: &str) -> bool {
+        match self {
+            Segment::Field(v) | Segment::Variant(v) => glob(pattern, v),
+            Segment::Key(v) => glob(pattern, v),
+            Segment::Index(v) => glob(pattern, &v.to_string()),
+        }
+    }
+}
+
+/// Joins path segments into a path like `users[0].name`, as understood by [split].
+#[cfg(feature = "serde")]
+pub(crate) fn join(segments: &[Segment]) -> String {
+    let mut path = String::new();
+    for segment in segments {
+        match segment {
+            Segment::Index(index) => {
+                path.push('[');
+                path.push_str(&index.to_string());
+                path.push(']');
+            }
+            Segment::Field(name) | Segment::Variant(name) => push(&mut path, name),
+            Segment::Key(key) => push(&mut path, key),
+        }
+    }
+    path
+}
+
+#[cfg(feature = "serde")]
+fn push(path: &mut String, name: &str) {
+    if !path.is_empty() {
+        path.push('.');
+    }
+    path.push_str(name);
+}
+
+/// Matches `text` against a glob `pattern`.
+///
+/// `*` matches any number of characters and `?` matches exactly one character.
+pub(crate) fn glob(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    // The position of the last `*` in the pattern and the text position it was tried at.
+    let mut star = None;
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, t));
+                p += 1;
+            }
+            Some('?') => {
+                p += 1;
+                t += 1;
+            }
+            Some(c) if *c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match star {
+                Some((star_p, star_t)) => {
+                    p = star_p + 1;
+                    t = star_t + 1;
+                    star = Some((star_p, star_t + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+/// Splits a path pattern like `users.*.password` or `items[0].name` into its segments.
+pub(crate) fn split(pattern: &str) -> Vec<String> {
+    pattern
+        .split(['.', '['])
+        .map(|s| s.strip_suffix(']').unwrap_or(s))
+        .filter(|s| !s.is_empty())
+        .map(String::from)
+        .collect()
+}
@@ -0,0 +1,137 @@
+use crate::generate::Roundtrip;
+use crate::generate::MAX_DEPTH;
+use crate::generate::MAX_LEN;
+use crate::generate::NAMES;
+use crate::Data;
+use crate::Enum;
+use crate::Number;
+use crate::Struct;
+use crate::Value;
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::string::String;
+use proptest::arbitrary::any;
+use proptest::arbitrary::Arbitrary;
+use proptest::collection::vec;
+use proptest::num;
+use proptest::option;
+use proptest::prop_oneof;
+use proptest::sample::select;
+use proptest::strategy::BoxedStrategy;
+use proptest::strategy::Just;
+use proptest::strategy::Strategy;
+
+/// How many values a random value holds at most, counting nested values.
+const MAX_SIZE: u32 = 64;
+
+impl Arbitrary for Number {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with((): ()) -> Self::Strategy {
+        number(false)
+    }
+}
+
+impl Arbitrary for Value<'static> {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with((): ()) -> Self::Strategy {
+        value(false)
+    }
+}
+
+impl Arbitrary for Roundtrip<'static> {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with((): ()) -> Self::Strategy {
+        value(true).prop_map(Roundtrip).boxed()
+    }
+}
+
+fn number(roundtrip: bool) -> BoxedStrategy<Number> {
+    // NaN isn't equal to itself, so it never round-trips.
+    let (f32, f64) = match roundtrip {
+        true => (not_nan_f32().boxed(), not_nan_f64().boxed()),
+        false => (any::<f32>().boxed(), any::<f64>().boxed()),
+    };
+    prop_oneof![
+        any::<i8>().prop_map(Number::I8),
+        any::<u8>().prop_map(Number::U8),
+        any::<i16>().prop_map(Number::I16),
+        any::<u16>().prop_map(Number::U16),
+        any::<i32>().prop_map(Number::I32),
+        any::<u32>().prop_map(Number::U32),
+        f32.prop_map(Number::F32),
+        any::<i64>().prop_map(Number::I64),
+        any::<u64>().prop_map(Number::U64),
+        f64.prop_map(Number::F64),
+        any::<i128>().prop_map(Number::I128),
+        any::<u128>().prop_map(Number::U128),
+    ]
+    .boxed()
+}
+
+// Any float but NaN.
+fn not_nan_f32() -> num::f32::Any {
+    use num::f32::*;
+    POSITIVE | NEGATIVE | NORMAL | SUBNORMAL | ZERO | INFINITE
+}
+
+// Any float but NaN.
+fn not_nan_f64() -> num::f64::Any {
+    use num::f64::*;
+    POSITIVE | NEGATIVE | NORMAL | SUBNORMAL | ZERO | INFINITE
+}
+
+fn value(roundtrip: bool) -> BoxedStrategy<Value<'static>> {
+    let leaf = prop_oneof![
+        Just(Value::Unit),
+        any::<bool>().prop_map(Value::Bool),
+        number(roundtrip).prop_map(Value::Number),
+        any::<char>().prop_map(Value::Char),
+        any::<String>().prop_map(|v| Value::String(Cow::Owned(v))),
+        vec(any::<u8>(), 0..=MAX_LEN).prop_map(|v| Value::Bytes(Cow::Owned(v))),
+    ];
+    // The leaves count as the first level.
+    let depth = MAX_DEPTH as u32 - 1;
+    leaf.prop_recursive(depth, MAX_SIZE, MAX_LEN as u32, move |inner| {
+        let seq = vec(inner.clone(), 0..=MAX_LEN).prop_map(Value::Seq);
+        let map = vec((inner.clone(), inner.clone()), 0..=MAX_LEN).prop_map(Value::Map);
+        let option = option::of(inner.clone()).prop_map(|v| Value::Option(v.map(Box::new)));
+        if roundtrip {
+            return prop_oneof![seq, map, option].boxed();
+        }
+        let tuple = vec(inner.clone(), 0..=MAX_LEN).prop_map(Value::Tuple);
+        let structure = (name(), data(inner.clone()))
+            .prop_map(|(name, data)| Value::Struct(Box::new(Struct { name, data })));
+        let enumeration = (name(), any::<u32>(), name(), data(inner)).prop_map(
+            |(name, variant_index, variant, data)| {
+                Value::Enum(Box::new(Enum {
+                    name,
+                    variant_index,
+                    variant,
+                    data,
+                }))
+            },
+        );
+        prop_oneof![seq, map, option, tuple, structure, enumeration].boxed()
+    })
+    .boxed()
+}
+
+fn data(value: BoxedStrategy<Value<'static>>) -> BoxedStrategy<Data<'static>> {
+    prop_oneof![
+        Just(Data::Unit),
+        value.clone().prop_map(|value| Data::NewType { value }),
+        vec(value.clone(), 0..=MAX_LEN).prop_map(|values| Data::Tuple { values }),
+        vec((name(), value), 0..=MAX_LEN).prop_map(|fields| Data::Struct { fields }),
+    ]
+    .boxed()
+}
+
+fn name() -> impl Strategy<Value = Cow<'static, str>> {
+    select(NAMES).prop_map(Cow::Borrowed)
+}
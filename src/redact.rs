@@ -0,0 +1,472 @@
+use crate::hash::Fnv;
+use crate::hash::Sip;
+use crate::path;
+use crate::path::Segment;
+use crate::Data;
+use crate::Error;
+use crate::Number;
+use crate::Value;
+use alloc::borrow::Cow;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::hash::Hasher;
+use core::mem;
+
+/// A set of policies for hiding sensitive data inside a [Value].
+///
+/// A redactor can be applied to an existing value with [Redactor::redact], or to a
+/// [crate::Serializer] with [crate::Serializer::redact] so that matching data never makes it into
+/// the serialized value in the first place.
+///
+/// ## Example
+///
+/// ```
+/// # use serde_content::{Redactor, Replacement, Value};
+/// let redactor = Redactor::new()
+///     .field("User", "password", Replacement::Marker)
+///     .key("*token*", Replacement::Hash);
+///
+/// let mut value = Value::Map(vec![(Value::from("api_token"), Value::from("secret"))]);
+/// redactor.redact(&mut value).unwrap();
+/// assert_ne!(value, Value::Map(vec![(Value::from("api_token"), Value::from("secret"))]));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, PartialOrd)]
+pub struct Redactor {
+    policies: Vec<RedactPolicy>,
+    // The segments of each path pattern, in policy order.
+    patterns: Vec<Pattern>,
+    // For each policy, the bit of [PathState] that is set when its path pattern matches.
+    accepts: Vec<u128>,
+    // Whether the path patterns didn't fit in [PathState], which makes redacting fail.
+    too_many_segments: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+struct Pattern {
+    segments: Vec<String>,
+    // The bit of the first segment in [PathState].
+    offset: usize,
+}
+
+/// Tracks how far the current path has advanced through each path pattern of a [Redactor].
+///
+/// Each pattern segment, plus one accepting position per pattern, is a bit of the state. This
+/// keeps the state `Copy` so that it can be carried by [crate::Serializer].
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd)]
+pub(crate) struct PathState(u128);
+
+impl PathState {
+    /// The state used when nothing is being redacted.
+    #[cfg(feature = "serde")]
+    pub(crate) const NONE: Self = Self(0);
+}
+
+// The maximum number of path pattern segments, including one per pattern, in a redactor.
+const MAX_PATH_SEGMENTS: usize = u128::BITS as usize;
+
+/// A single redaction rule of a [Redactor].
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct RedactPolicy {
+    /// The values this policy applies to.
+    pub selector: Selector,
+    /// What matching values are replaced with.
+    pub replacement: Replacement,
+}
+
+/// Selects the values a [RedactPolicy] applies to.
+///
+/// All names and patterns are globs where `*` matches any number of characters and `?` matches
+/// a single character.
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+#[non_exhaustive] // In case we add new selectors in future.
+pub enum Selector {
+    /// Selects a field of an object-like struct, or a field of an object-like enum variant.
+    Field {
+        /// The name of the struct, or the enum for enum variants.
+        name: String,
+        /// The name of the field.
+        field: String,
+    },
+    /// Selects values by their path, for example `users.*.password` or `**.token`.
+    ///
+    /// Segments are separated by `.`, `[0]` may be used for indices and `**` matches any number
+    /// of segments. Segments are field names, map keys, sequence or tuple indices and enum
+    /// variant names. Newtypes and options don't add a segment of their own.
+    Path(String),
+    /// Selects map values by their key.
+    Key(String),
+}
+
+/// The value that a redacted value is replaced with.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub enum Replacement {
+    /// Replaces the value with the string `***`.
+    Marker,
+    /// Replaces each character of a string, byte of a byte array or a single character with `*`,
+    /// preserving the length of the value. Other values are replaced with the string `***`.
+    Mask,
+    /// Replaces the value with a hexadecimal string of its 64-bit FNV-1a hash.
+    ///
+    /// Equal values always produce the same hash, so redacted values can still be correlated.
+    ///
+    /// This is not a privacy guarantee. The hash is unkeyed, so values with few possibilities,
+    /// like email addresses, IDs or card numbers, can be recovered by hashing guesses until one
+    /// matches. Use [Replacement::KeyedHash] for those.
+    Hash,
+    /// Replaces the value with a hexadecimal string of its 64-bit SipHash-2-4 hash under a
+    /// secret key.
+    ///
+    /// Like [Replacement::Hash], equal values produce the same hash as long as the key stays the
+    /// same, but the hashes can't be computed, and so values can't be guessed, without the key.
+    KeyedHash(HashKey),
+}
+
+/// A secret key for [Replacement::KeyedHash].
+///
+/// The key is not shown when debug formatted.
+#[derive(Clone, Copy, PartialEq, PartialOrd)]
+pub struct HashKey([u8; 16]);
+
+impl HashKey {
+    /// Creates a key from 16 secret bytes, which should come from a secure random source.
+    pub const fn new(key: [u8; 16]) -> Self {
+        Self(key)
+    }
+}
+
+impl fmt::Debug for HashKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("HashKey(..)")
+    }
+}
+
+impl Redactor {
+    /// Creates a redactor without any policies.
+    pub const fn new() -> Self {
+        Self {
+            policies: Vec::new(),
+            patterns: Vec::new(),
+            accepts: Vec::new(),
+            too_many_segments: false,
+        }
+    }
+
+    /// Adds a policy to this redactor.
+    ///
+    /// The path patterns of a redactor can have at most 128 segments in total, counting one
+    /// extra segment per pattern. Redacting with more fails, as the policies can't all be
+    /// applied.
+    pub fn policy(self, selector: Selector, replacement: Replacement) -> Self {
+        match selector {
+            Selector::Path(pattern) => self.push_path(pattern, replacement),
+            selector => self.push(selector, replacement, 0),
+        }
+    }
+
+    // Adds a policy whose path pattern is accepted at the bit `accept` of [PathState].
+    fn push(mut self, selector: Selector, replacement: Replacement, accept: u128) -> Self {
+        self.accepts.push(accept);
+        self.policies.push(RedactPolicy {
+            selector,
+            replacement,
+        });
+        self
+    }
+
+    fn push_path(mut self, pattern: String, replacement: Replacement) -> Self {
+        let segments = path::split(&pattern);
+        let offset = self
+            .patterns
+            .last()
+            .map_or(0, |last| last.offset + last.segments.len() + 1);
+        if offset + segments.len() >= MAX_PATH_SEGMENTS {
+            self.too_many_segments = true;
+            return self.push(Selector::Path(pattern), replacement, 0);
+        }
+        let accept = 1 << (offset + segments.len());
+        self.patterns.push(Pattern { segments, offset });
+        self.push(Selector::Path(pattern), replacement, accept)
+    }
+
+    /// Redacts the `field` of structs (or enums) called `name`.
+    pub fn field(self, name: &str, field: &str, replacement: Replacement) -> Self {
+        let selector = Selector::Field {
+            name: name.into(),
+            field: field.into(),
+        };
+        self.push(selector, replacement, 0)
+    }
+
+    /// Redacts values whose path matches `pattern`.
+    ///
+    /// See [Selector::Path] for the syntax of patterns, and [Redactor::policy] for how many
+    /// path pattern segments a redactor can have.
+    pub fn path(self, pattern: &str, replacement: Replacement) -> Self {
+        self.push_path(pattern.into(), replacement)
+    }
+
+    /// Redacts map values whose key matches `pattern`.
+    pub fn key(self, pattern: &str, replacement: Replacement) -> Self {
+        self.push(Selector::Key(pattern.into()), replacement, 0)
+    }
+
+    /// Returns the policies of this redactor.
+    pub fn policies(&self) -> &[RedactPolicy] {
+        &self.policies
+    }
+
+    /// Redacts all values matching any of the policies in place.
+    ///
+    /// Fails without changing `value` if the path patterns of this redactor have too many
+    /// segments. See [Redactor::policy].
+    pub fn redact(&self, value: &mut Value<'_>) -> Result<(), Error> {
+        self.check()?;
+        if !self.policies.is_empty() {
+            self.walk(value, self.start());
+        }
+        Ok(())
+    }
+
+    /// Fails if the path patterns of this redactor have too many segments to be applied.
+    pub(crate) fn check(&self) -> Result<(), Error> {
+        if self.too_many_segments {
+            return Err(Error::custom(format_args!(
+                "a redactor supports at most {MAX_PATH_SEGMENTS} path pattern segments"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Returns the state of the root value.
+    pub(crate) fn start(&self) -> PathState {
+        let mut bits = 0;
+        for pattern in &self.patterns {
+            bits |= 1 << pattern.offset;
+        }
+        PathState(self.closure(bits))
+    }
+
+    /// Advances `state` to the child of the current value at `segment`.
+    pub(crate) fn step(&self, state: PathState, segment: &Segment) -> PathState {
+        let mut bits = 0;
+        for pattern in &self.patterns {
+            for (index, glob) in pattern.segments.iter().enumerate() {
+                let bit = 1 << (pattern.offset + index);
+                if state.0 & bit == 0 {
+                    continue;
+                }
+                if glob == "**" {
+                    bits |= bit;
+                } else if segment.matches(glob) {
+                    bits |= bit << 1;
+                }
+            }
+        }
+        PathState(self.closure(bits))
+    }
+
+    // Lets `**` match zero segments by also activating the segment after it.
+    fn closure(&self, mut bits: u128) -> u128 {
+        for pattern in &self.patterns {
+            for (index, glob) in pattern.segments.iter().enumerate() {
+                let bit = 1 << (pattern.offset + index);
+                if bits & bit != 0 && glob == "**" {
+                    bits |= bit << 1;
+                }
+            }
+        }
+        bits
+    }
+
+    /// Finds the replacement for the value reached through `segment`, where `state` is the
+    /// state of that value.
+    ///
+    /// `name` is the name of the struct or enum that directly contains the value, if any.
+    pub(crate) fn find(
+        &self,
+        state: PathState,
+        segment: &Segment,
+        name: Option<&str>,
+    ) -> Option<Replacement> {
+        self.policies
+            .iter()
+            .zip(&self.accepts)
+            .find(|(policy, accept)| match (&policy.selector, segment) {
+                (Selector::Field { name: n, field }, Segment::Field(f)) => {
+                    name.is_some_and(|name| path::glob(n, name)) && path::glob(field, f)
+                }
+                (Selector::Key(pattern), Segment::Key(key)) => path::glob(pattern, key),
+                (Selector::Path(_), _) => state.0 & *accept != 0,
+                _ => false,
+            })
+            .map(|(policy, _)| policy.replacement)
+    }
+
+    fn visit(&self, value: &mut Value<'_>, state: PathState, segment: Segment, name: Option<&str>) {
+        let state = self.step(state, &segment);
+        match self.find(state, &segment, name) {
+            Some(replacement) => *value = replacement.apply(mem::replace(value, Value::Unit)),
+            None => self.walk(value, state),
+        }
+    }
+
+    fn walk(&self, value: &mut Value<'_>, state: PathState) {
+        match value {
+            Value::Seq(v) | Value::Tuple(v) => self.walk_values(v, state, None),
+            Value::Map(v) => {
+                for (key, value) in v {
+                    self.visit(value, state, Segment::key(key), None);
+                }
+            }
+            Value::Option(Some(v)) => self.walk(v, state),
+            Value::Struct(v) => self.walk_data(&mut v.data, state, &v.name),
+            Value::Enum(v) => {
+                let state = self.step(state, &Segment::Variant(v.variant.clone()));
+                self.walk_data(&mut v.data, state, &v.name);
+            }
+            _ => {}
+        }
+    }
+
+    fn walk_data(&self, data: &mut Data<'_>, state: PathState, name: &str) {
+        match data {
+            Data::Unit => {}
+            Data::NewType { value } => self.walk(value, state),
+            Data::Tuple { values } => self.walk_values(values, state, Some(name)),
+            Data::Struct { fields } => {
+                for (key, value) in fields {
+                    self.visit(value, state, Segment::Field(key.clone()), Some(name));
+                }
+            }
+        }
+    }
+
+    fn walk_values(&self, values: &mut [Value<'_>], state: PathState, name: Option<&str>) {
+        for (index, value) in values.iter_mut().enumerate() {
+            self.visit(value, state, Segment::Index(index), name);
+        }
+    }
+}
+
+const MARKER: &str = "***";
+
+// Replaces `value` with a hexadecimal string of its hash.
+fn hash(mut hasher: impl Hasher, value: &Value<'_>) -> Value<'static> {
+    hasher.value(value);
+    Value::String(Cow::Owned(format!("{:016x}", hasher.finish())))
+}
+
+impl Replacement {
+    /// Returns the replacement for `value`.
+    pub fn apply<'a>(self, value: Value<'a>) -> Value<'a> {
+        match (self, value) {
+            (Replacement::Mask, Value::String(v)) => {
+                Value::String(Cow::Owned(v.chars().map(|_| '*').collect()))
+            }
+            (Replacement::Mask, Value::Bytes(v)) => Value::Bytes(Cow::Owned(vec![b'*'; v.len()])),
+            (Replacement::Mask, Value::Char(_)) => Value::Char('*'),
+            (Replacement::Hash, value) => hash(Fnv::new(), &value),
+            (Replacement::KeyedHash(key), value) => hash(Sip::new(&key.0), &value),
+            _ => Value::String(Cow::Borrowed(MARKER)),
+        }
+    }
+}
+
+// Feeds values to a hasher in an encoding that is stable across platforms and Rust versions,
+// unlike `core::hash::Hash` implementations, which keeps hashes comparable between log lines.
+trait HashValue: Hasher {
+    fn len(&mut self, tag: u8, len: usize) {
+        self.write(&[tag]);
+        self.write(&(len as u64).to_le_bytes());
+    }
+
+    fn number(&mut self, tag: u8, bytes: &[u8]) {
+        self.write(&[2, tag]);
+        self.write(bytes);
+    }
+
+    fn str(&mut self, tag: u8, value: &str) {
+        self.len(tag, value.len());
+        self.write(value.as_bytes());
+    }
+
+    fn value(&mut self, value: &Value<'_>) {
+        match value {
+            Value::Unit => self.write(&[0]),
+            Value::Bool(v) => self.write(&[1, *v as u8]),
+            Value::Number(v) => match *v {
+                Number::I8(v) => self.number(0, &v.to_le_bytes()),
+                Number::U8(v) => self.number(1, &v.to_le_bytes()),
+                Number::I16(v) => self.number(2, &v.to_le_bytes()),
+                Number::U16(v) => self.number(3, &v.to_le_bytes()),
+                Number::I32(v) => self.number(4, &v.to_le_bytes()),
+                Number::U32(v) => self.number(5, &v.to_le_bytes()),
+                Number::F32(v) => self.number(6, &v.to_le_bytes()),
+                Number::I64(v) => self.number(7, &v.to_le_bytes()),
+                Number::U64(v) => self.number(8, &v.to_le_bytes()),
+                Number::F64(v) => self.number(9, &v.to_le_bytes()),
+                Number::I128(v) => self.number(10, &v.to_le_bytes()),
+                Number::U128(v) => self.number(11, &v.to_le_bytes()),
+            },
+            Value::Char(v) => self.str(3, v.encode_utf8(&mut [0; 4])),
+            Value::String(v) => self.str(4, v),
+            Value::Bytes(v) => {
+                self.len(5, v.len());
+                self.write(v);
+            }
+            Value::Seq(v) => self.values(6, v),
+            Value::Map(v) => {
+                self.len(7, v.len());
+                for (key, value) in v {
+                    self.value(key);
+                    self.value(value);
+                }
+            }
+            Value::Option(None) => self.write(&[8]),
+            Value::Option(Some(v)) => {
+                self.write(&[9]);
+                self.value(v);
+            }
+            Value::Struct(v) => {
+                self.str(10, &v.name);
+                self.data(&v.data);
+            }
+            Value::Enum(v) => {
+                self.str(11, &v.name);
+                self.str(11, &v.variant);
+                self.data(&v.data);
+            }
+            Value::Tuple(v) => self.values(12, v),
+        }
+    }
+
+    fn values(&mut self, tag: u8, values: &[Value<'_>]) {
+        self.len(tag, values.len());
+        for value in values {
+            self.value(value);
+        }
+    }
+
+    fn data(&mut self, data: &Data<'_>) {
+        match data {
+            Data::Unit => self.write(&[0]),
+            Data::NewType { value } => {
+                self.write(&[1]);
+                self.value(value);
+            }
+            Data::Tuple { values } => self.values(2, values),
+            Data::Struct { fields } => {
+                self.len(3, fields.len());
+                for (key, value) in fields {
+                    self.str(3, key);
+                    self.value(value);
+                }
+            }
+        }
+    }
+}
+
+impl<H> HashValue for H where H: Hasher {}
@@ -0,0 +1,152 @@
+use crate::Value;
+use crate::ValueVisitor;
+use alloc::vec::Vec;
+use core::fmt;
+use serde::de;
+use serde::ser;
+use serde::ser::SerializeMap;
+
+/// The remaining fields of a struct, for use with `#[serde(flatten)]`.
+///
+/// Fields that none of the other fields of the struct claim are kept as key-value pairs, in the
+/// order they were found, and written back out when the struct is serialized. This lets types
+/// carry along fields they don't understand, for example when newer versions of a message add
+/// fields, but it is not lossless.
+///
+/// `serde` buffers the fields of structs with flattened fields in its own internal
+/// representation before they reach `Rest`, which only keeps the data model types that
+/// self-describing formats have. Field names, strings, bytes, options, maps, sequences and the
+/// exact types of numbers up to 64 bits are preserved. [Deserializer](crate::Deserializer) also
+/// hands enums and 128-bit numbers over in a form `serde` can buffer, so they are preserved too.
+/// Other values are not:
+///
+/// - structs and struct variants lose their names and become maps, tuples become sequences and
+///   newtype structs become their inner value, so they serialize differently;
+/// - enums and 128-bit numbers from other deserializers fail deserialization of the whole
+///   struct;
+/// - enums come back with owned rather than static names, so they serialize as maps from the
+///   variant name to its data, as enums in a [Value] with owned names always do.
+///
+/// Use [Ignored](crate::Ignored) to capture unknown fields exactly as they appear in a [Value].
+///
+/// Structs with flattened fields are deserialized as maps, so [crate::Deserializer::coerce_shapes]
+/// is needed to deserialize them from object-like structs.
+///
+/// ## Example
+///
+/// ```
+/// # use serde::{Deserialize, Serialize};
+/// # use serde_content::{value, Deserializer, Rest, Serializer};
+/// #[derive(Deserialize, Serialize)]
+/// struct Message<'a> {
+///     id: u32,
+///     #[serde(borrow, flatten)]
+///     rest: Rest<'a>,
+/// }
+///
+/// let value = value!({ "id": 1u32, "priority": 2u8, "tags": ["a", "b"] });
+/// let message: Message = Deserializer::new(value.clone()).deserialize().unwrap();
+/// assert_eq!(message.rest.len(), 2);
+/// assert_eq!(Serializer::new().serialize(&message).unwrap(), value);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, PartialOrd)]
+pub struct Rest<'de>(pub Vec<(Value<'de>, Value<'de>)>);
+
+impl<'de> Rest<'de> {
+    /// Creates an empty set of fields.
+    pub const fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    /// Returns the number of fields.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether there are no fields.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the value of the field named `name`, if any.
+    pub fn get(&self, name: &str) -> Option<&Value<'de>> {
+        self.0.iter().find_map(|(key, value)| match key {
+            Value::String(key) if key == name => Some(value),
+            _ => None,
+        })
+    }
+
+    /// Converts the fields into owned data.
+    pub fn into_owned(self) -> Rest<'static> {
+        Rest(
+            self.0
+                .into_iter()
+                .map(|(key, value)| (key.into_owned(), value.into_owned()))
+                .collect(),
+        )
+    }
+}
+
+impl<'de> From<Rest<'de>> for Value<'de> {
+    fn from(rest: Rest<'de>) -> Self {
+        Value::Map(rest.0)
+    }
+}
+
+impl ser::Serialize for Rest<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (key, value) in &self.0 {
+            map.serialize_entry(key, value)?;
+        }
+        map.end()
+    }
+}
+
+impl<'de: 'a, 'a> de::Deserialize<'de> for Rest<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_map(RestVisitor)
+    }
+}
+
+struct RestVisitor;
+
+impl<'de> de::Visitor<'de> for RestVisitor {
+    type Value = Rest<'de>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a map of fields")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::MapAccess<'de>,
+    {
+        let mut fields = Vec::new();
+        while let Some(key) = map.next_key_seed(ValueSeed)? {
+            let value = map.next_value_seed(ValueSeed)?;
+            fields.push((key, value));
+        }
+        Ok(Rest(fields))
+    }
+}
+
+/// Deserializes a [Value] that may borrow from the input.
+struct ValueSeed;
+
+impl<'de> de::DeserializeSeed<'de> for ValueSeed {
+    type Value = Value<'de>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_any(ValueVisitor)
+    }
+}
@@ -0,0 +1,133 @@
+use crate::Data;
+use crate::Number;
+use crate::Value;
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use ron::value::Map;
+use ron::value::F32;
+use ron::value::F64;
+use ron::Value as Ron;
+
+impl From<Value<'_>> for Ron {
+    /// Converts a [Value] into a RON value.
+    ///
+    /// RON itself has struct names and enums, but `ron::Value` doesn't. Struct names are dropped,
+    /// so structs become their data. Unit enum variants become their name as a string, and other
+    /// variants a map with their data under their name. Tuples become sequences. Everything else
+    /// converts without loss.
+    ///
+    /// To keep struct names and enums, serialize the [Value] to RON text instead, as it
+    /// implements `Serialize`.
+    fn from(value: Value<'_>) -> Self {
+        match value {
+            Value::Unit => Ron::Unit,
+            Value::Bool(v) => Ron::Bool(v),
+            Value::Number(v) => Ron::Number(v.into()),
+            Value::Char(v) => Ron::Char(v),
+            Value::String(v) => Ron::String(v.into_owned()),
+            Value::Bytes(v) => Ron::Bytes(v.into_owned()),
+            Value::Seq(v) | Value::Tuple(v) => seq(v),
+            Value::Map(v) => Ron::Map(
+                v.into_iter()
+                    .map(|(key, value)| (Ron::from(key), Ron::from(value)))
+                    .collect(),
+            ),
+            Value::Option(v) => Ron::Option(v.map(|v| Box::new((*v).into()))),
+            Value::Struct(v) => data(v.data),
+            Value::Enum(v) => match v.data {
+                Data::Unit => Ron::String(v.variant.into_owned()),
+                data => {
+                    let mut map = Map::new();
+                    map.insert(v.variant.into_owned(), self::data(data));
+                    Ron::Map(map)
+                }
+            },
+        }
+    }
+}
+
+fn seq(values: Vec<Value<'_>>) -> Ron {
+    Ron::Seq(values.into_iter().map(Ron::from).collect())
+}
+
+/// Converts the data of a struct or enum variant, without its name.
+fn data(data: Data<'_>) -> Ron {
+    match data {
+        Data::Unit => Ron::Unit,
+        Data::NewType { value } => value.into(),
+        Data::Tuple { values } => seq(values),
+        Data::Struct { fields } => Ron::Map(
+            fields
+                .into_iter()
+                .map(|(key, value)| (Ron::String(key.into_owned()), Ron::from(value)))
+                .collect(),
+        ),
+    }
+}
+
+impl From<Number> for ron::value::Number {
+    fn from(number: Number) -> Self {
+        match number {
+            Number::I8(v) => Self::I8(v),
+            Number::U8(v) => Self::U8(v),
+            Number::I16(v) => Self::I16(v),
+            Number::U16(v) => Self::U16(v),
+            Number::I32(v) => Self::I32(v),
+            Number::U32(v) => Self::U32(v),
+            Number::F32(v) => Self::F32(F32(v)),
+            Number::I64(v) => Self::I64(v),
+            Number::U64(v) => Self::U64(v),
+            Number::F64(v) => Self::F64(F64(v)),
+            Number::I128(v) => Self::I128(v),
+            Number::U128(v) => Self::U128(v),
+        }
+    }
+}
+
+impl From<Ron> for Value<'static> {
+    /// Converts a RON value into a [Value].
+    ///
+    /// Parsing RON text into a `ron::Value` already drops struct names and enum variants, so
+    /// they can't be recovered here. A struct like `Point(x: 1)` arrives as a map, a unit variant
+    /// like `Empty` as a unit and a tuple variant like `Circle(1.0)` as a sequence.
+    fn from(ron: Ron) -> Self {
+        match ron {
+            Ron::Unit => Value::Unit,
+            Ron::Bool(v) => Value::Bool(v),
+            Ron::Char(v) => Value::Char(v),
+            Ron::Number(v) => Value::Number(v.into()),
+            Ron::String(v) => Value::String(Cow::Owned(v)),
+            Ron::Bytes(v) => Value::Bytes(Cow::Owned(v)),
+            Ron::Seq(v) => Value::Seq(v.into_iter().map(Value::from).collect()),
+            Ron::Map(v) => Value::Map(
+                v.into_iter()
+                    .map(|(key, value)| (key.into(), value.into()))
+                    .collect(),
+            ),
+            Ron::Option(v) => Value::Option(v.map(|v| Box::new((*v).into()))),
+        }
+    }
+}
+
+impl From<ron::value::Number> for Number {
+    fn from(number: ron::value::Number) -> Self {
+        use ron::value::Number as Ron;
+        match number {
+            Ron::I8(v) => Number::I8(v),
+            Ron::U8(v) => Number::U8(v),
+            Ron::I16(v) => Number::I16(v),
+            Ron::U16(v) => Number::U16(v),
+            Ron::I32(v) => Number::I32(v),
+            Ron::U32(v) => Number::U32(v),
+            Ron::F32(v) => Number::F32(v.get()),
+            Ron::I64(v) => Number::I64(v),
+            Ron::U64(v) => Number::U64(v),
+            Ron::F64(v) => Number::F64(v.get()),
+            Ron::I128(v) => Number::I128(v),
+            Ron::U128(v) => Number::U128(v),
+            // `ron` may add more kinds of numbers in future.
+            number => Number::F64(number.into_f64()),
+        }
+    }
+}
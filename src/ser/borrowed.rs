@@ -0,0 +1,349 @@
+use super::to_static_str;
+use crate::Data;
+use crate::Enum;
+use crate::Error;
+use crate::Serializer;
+use crate::Struct;
+use crate::Value;
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+use serde::ser;
+use serde::ser::Serializer as _;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+/// A type that can be serialized into a [Value] borrowing its strings and bytes.
+///
+/// [Serializer::serialize] goes through [serde::Serialize], which hands strings and bytes to
+/// the serializer without their lifetime, so every one of them gets copied. Types implementing
+/// this trait are serialized with [Serializer::serialize_borrowed] instead, which leaves their
+/// strings and bytes as [Cow::Borrowed], pointing into the value being serialized. Redaction
+/// and limits apply just as they do with [Serializer::serialize].
+///
+/// Structs and enums with owned names can't be represented in the Serde data model, so a
+/// [Value] holding them falls back to [Serializer::serialize] for that part of the value.
+pub trait SerializeBorrowed {
+    /// Serializes `self` into a value borrowing from `self`.
+    fn serialize_borrowed<'a>(&'a self, serializer: Serializer<'a, '_>)
+        -> Result<Value<'a>, Error>;
+}
+
+impl<'a, 'c> Serializer<'a, 'c> {
+    /// Convert a `T` into `Value`, borrowing its strings and bytes instead of copying them.
+    ///
+    /// See [SerializeBorrowed] for details.
+    pub fn serialize_borrowed<T>(self, value: &'a T) -> Result<Value<'a>, Error>
+    where
+        T: ?Sized + SerializeBorrowed,
+    {
+        self.counted(|serializer| value.serialize_borrowed(serializer))
+    }
+
+    fn borrow_str(self, value: &'a str) -> Result<Value<'a>, Error> {
+        self.bytes(value.len())?;
+        Ok(Value::String(Cow::Borrowed(value)))
+    }
+
+    fn borrow_bytes(self, value: &'a [u8]) -> Result<Value<'a>, Error> {
+        self.bytes(value.len())?;
+        Ok(Value::Bytes(Cow::Borrowed(value)))
+    }
+}
+
+impl SerializeBorrowed for Value<'_> {
+    fn serialize_borrowed<'a>(
+        &'a self,
+        serializer: Serializer<'a, '_>,
+    ) -> Result<Value<'a>, Error> {
+        match self {
+            Value::String(v) => serializer.borrow_str(v),
+            Value::Bytes(v) => serializer.borrow_bytes(v),
+            Value::Seq(v) => v.serialize_borrowed(serializer),
+            Value::Map(v) => map(v.iter().map(|(key, value)| (key, value)), serializer),
+            Value::Option(v) => v.serialize_borrowed(serializer),
+            Value::Struct(v) => v.serialize_borrowed(serializer),
+            Value::Enum(v) => v.serialize_borrowed(serializer),
+            Value::Tuple(v) => tuple(v, serializer),
+            Value::Unit | Value::Bool(_) | Value::Number(_) | Value::Char(_) => {
+                ser::Serialize::serialize(self, serializer)
+            }
+        }
+    }
+}
+
+impl SerializeBorrowed for Struct<'_> {
+    fn serialize_borrowed<'a>(
+        &'a self,
+        serializer: Serializer<'a, '_>,
+    ) -> Result<Value<'a>, Error> {
+        let Cow::Borrowed(name) = self.name else {
+            return ser::Serialize::serialize(self, serializer);
+        };
+        match &self.data {
+            Data::Unit => serializer.serialize_unit_struct(name),
+            Data::NewType { value } => {
+                serializer.newtype_struct_with(name, |s| value.serialize_borrowed(s))
+            }
+            Data::Tuple { values } => {
+                let mut tup = serializer.serialize_tuple_struct(name, values.len())?;
+                for value in values {
+                    tup.element_with(|s| value.serialize_borrowed(s))?;
+                }
+                ser::SerializeTupleStruct::end(tup)
+            }
+            Data::Struct { fields } => {
+                let mut st = serializer.serialize_struct(name, fields.len())?;
+                for (key, value) in fields {
+                    let key = to_static_str(key)?;
+                    st.field_with(key, |s| value.serialize_borrowed(s))?;
+                }
+                ser::SerializeStruct::end(st)
+            }
+        }
+    }
+}
+
+impl SerializeBorrowed for Enum<'_> {
+    fn serialize_borrowed<'a>(
+        &'a self,
+        serializer: Serializer<'a, '_>,
+    ) -> Result<Value<'a>, Error> {
+        let (Cow::Borrowed(name), Cow::Borrowed(variant)) = (&self.name, &self.variant) else {
+            return ser::Serialize::serialize(self, serializer);
+        };
+        let index = self.variant_index;
+        match &self.data {
+            Data::Unit => serializer.serialize_unit_variant(name, index, variant),
+            Data::NewType { value } => serializer
+                .newtype_variant_with(name, index, variant, |s| value.serialize_borrowed(s)),
+            Data::Tuple { values } => {
+                let mut tup =
+                    serializer.serialize_tuple_variant(name, index, variant, values.len())?;
+                for value in values {
+                    tup.element_with(|s| value.serialize_borrowed(s))?;
+                }
+                ser::SerializeTupleVariant::end(tup)
+            }
+            Data::Struct { fields } => {
+                let mut st =
+                    serializer.serialize_struct_variant(name, index, variant, fields.len())?;
+                for (key, value) in fields {
+                    let key = to_static_str(key)?;
+                    st.field_with(key, |s| value.serialize_borrowed(s))?;
+                }
+                ser::SerializeStructVariant::end(st)
+            }
+        }
+    }
+}
+
+impl SerializeBorrowed for str {
+    fn serialize_borrowed<'a>(
+        &'a self,
+        serializer: Serializer<'a, '_>,
+    ) -> Result<Value<'a>, Error> {
+        serializer.borrow_str(self)
+    }
+}
+
+impl SerializeBorrowed for String {
+    fn serialize_borrowed<'a>(
+        &'a self,
+        serializer: Serializer<'a, '_>,
+    ) -> Result<Value<'a>, Error> {
+        serializer.borrow_str(self)
+    }
+}
+
+impl SerializeBorrowed for Cow<'_, str> {
+    fn serialize_borrowed<'a>(
+        &'a self,
+        serializer: Serializer<'a, '_>,
+    ) -> Result<Value<'a>, Error> {
+        serializer.borrow_str(self)
+    }
+}
+
+// Serde serializes `[u8]` as a sequence of numbers, so only `Cow<[u8]>` is serialized as bytes,
+// matching `Value::Bytes`.
+impl SerializeBorrowed for Cow<'_, [u8]> {
+    fn serialize_borrowed<'a>(
+        &'a self,
+        serializer: Serializer<'a, '_>,
+    ) -> Result<Value<'a>, Error> {
+        serializer.borrow_bytes(self)
+    }
+}
+
+macro_rules! serialize {
+    ($($ty:ty),*) => {
+        $(
+            impl SerializeBorrowed for $ty {
+                fn serialize_borrowed<'a>(
+                    &'a self,
+                    serializer: Serializer<'a, '_>,
+                ) -> Result<Value<'a>, Error> {
+                    ser::Serialize::serialize(self, serializer)
+                }
+            }
+        )*
+    };
+}
+
+serialize!(
+    bool,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    f32,
+    f64,
+    char,
+    ()
+);
+
+impl<T> SerializeBorrowed for &T
+where
+    T: ?Sized + SerializeBorrowed,
+{
+    fn serialize_borrowed<'a>(
+        &'a self,
+        serializer: Serializer<'a, '_>,
+    ) -> Result<Value<'a>, Error> {
+        (**self).serialize_borrowed(serializer)
+    }
+}
+
+impl<T> SerializeBorrowed for Box<T>
+where
+    T: ?Sized + SerializeBorrowed,
+{
+    fn serialize_borrowed<'a>(
+        &'a self,
+        serializer: Serializer<'a, '_>,
+    ) -> Result<Value<'a>, Error> {
+        (**self).serialize_borrowed(serializer)
+    }
+}
+
+impl<T> SerializeBorrowed for Option<T>
+where
+    T: SerializeBorrowed,
+{
+    fn serialize_borrowed<'a>(
+        &'a self,
+        serializer: Serializer<'a, '_>,
+    ) -> Result<Value<'a>, Error> {
+        match self {
+            Some(value) => serializer.some_with(|s| value.serialize_borrowed(s)),
+            None => serializer.serialize_none(),
+        }
+    }
+}
+
+impl<T> SerializeBorrowed for [T]
+where
+    T: SerializeBorrowed,
+{
+    fn serialize_borrowed<'a>(
+        &'a self,
+        serializer: Serializer<'a, '_>,
+    ) -> Result<Value<'a>, Error> {
+        let mut seq = serializer.serialize_seq(Some(self.len()))?;
+        for value in self {
+            seq.element_with(|s| value.serialize_borrowed(s))?;
+        }
+        ser::SerializeSeq::end(seq)
+    }
+}
+
+impl<T> SerializeBorrowed for Vec<T>
+where
+    T: SerializeBorrowed,
+{
+    fn serialize_borrowed<'a>(
+        &'a self,
+        serializer: Serializer<'a, '_>,
+    ) -> Result<Value<'a>, Error> {
+        self.as_slice().serialize_borrowed(serializer)
+    }
+}
+
+// Like Serde, arrays are serialized as tuples.
+impl<T, const N: usize> SerializeBorrowed for [T; N]
+where
+    T: SerializeBorrowed,
+{
+    fn serialize_borrowed<'a>(
+        &'a self,
+        serializer: Serializer<'a, '_>,
+    ) -> Result<Value<'a>, Error> {
+        tuple(self, serializer)
+    }
+}
+
+impl<K, V> SerializeBorrowed for BTreeMap<K, V>
+where
+    K: SerializeBorrowed,
+    V: SerializeBorrowed,
+{
+    fn serialize_borrowed<'a>(
+        &'a self,
+        serializer: Serializer<'a, '_>,
+    ) -> Result<Value<'a>, Error> {
+        map(self, serializer)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, V, H> SerializeBorrowed for HashMap<K, V, H>
+where
+    K: SerializeBorrowed,
+    V: SerializeBorrowed,
+{
+    fn serialize_borrowed<'a>(
+        &'a self,
+        serializer: Serializer<'a, '_>,
+    ) -> Result<Value<'a>, Error> {
+        map(self, serializer)
+    }
+}
+
+fn tuple<'a, T>(values: &'a [T], serializer: Serializer<'a, '_>) -> Result<Value<'a>, Error>
+where
+    T: SerializeBorrowed,
+{
+    let mut tup = serializer.serialize_tuple(values.len())?;
+    for value in values {
+        tup.element_with(|s| value.serialize_borrowed(s))?;
+    }
+    ser::SerializeTuple::end(tup)
+}
+
+fn map<'a, K, V>(
+    entries: impl IntoIterator<Item = (&'a K, &'a V)>,
+    serializer: Serializer<'a, '_>,
+) -> Result<Value<'a>, Error>
+where
+    K: 'a + SerializeBorrowed,
+    V: 'a + SerializeBorrowed,
+{
+    let entries = entries.into_iter();
+    let mut map = serializer.serialize_map(Some(entries.size_hint().0))?;
+    for (key, value) in entries {
+        map.entry_with(
+            |s| key.serialize_borrowed(s),
+            |s| value.serialize_borrowed(s),
+        )?;
+    }
+    ser::SerializeMap::end(map)
+}
@@ -1,3 +1,4 @@
+use crate::path::Segment;
 use crate::ser::to_static_str;
 use crate::ser::Value;
 use crate::Data;
@@ -11,17 +12,51 @@ use serde::ser::SerializeMap;
 use serde::ser::SerializeStructVariant;
 use serde::ser::SerializeTupleVariant;
 
-pub struct Enum<'a> {
+pub struct Enum<'a, 'c> {
     r#enum: crate::Enum<'a>,
-    human_readable: bool,
+    serializer: Serializer<'a, 'c>,
 }
 
-impl<'a> Enum<'a> {
-    pub(super) const fn new(r#enum: crate::Enum<'a>, human_readable: bool) -> Self {
-        Self {
-            r#enum,
-            human_readable,
+impl<'a, 'c> Enum<'a, 'c> {
+    pub(super) const fn new(r#enum: crate::Enum<'a>, serializer: Serializer<'a, 'c>) -> Self {
+        Self { r#enum, serializer }
+    }
+
+    // Adds a field serialized with `serialize` to object-like data.
+    pub(super) fn field_with(
+        &mut self,
+        key: &'static str,
+        serialize: impl FnOnce(Serializer<'a, 'c>) -> Result<Value<'a>, Error>,
+    ) -> Result<(), Error> {
+        self.serializer.element()?;
+        self.serializer.bytes(key.len())?;
+        if let Data::Struct { fields } = &mut self.r#enum.data {
+            let name = Some(self.r#enum.name.as_ref());
+            let value = self.serializer.nested_with(
+                || Segment::Field(Cow::Borrowed(key)),
+                name,
+                serialize,
+            )?;
+            fields.push((Cow::Borrowed(key), value));
         }
+        Ok(())
+    }
+
+    // Adds a value serialized with `serialize` to tuple data.
+    pub(super) fn element_with(
+        &mut self,
+        serialize: impl FnOnce(Serializer<'a, 'c>) -> Result<Value<'a>, Error>,
+    ) -> Result<(), Error> {
+        self.serializer.element()?;
+        if let Data::Tuple { values } = &mut self.r#enum.data {
+            let name = Some(self.r#enum.name.as_ref());
+            let index = values.len();
+            let value = self
+                .serializer
+                .nested_with(|| Segment::Index(index), name, serialize)?;
+            values.push(value);
+        }
+        Ok(())
     }
 }
 
@@ -94,7 +129,7 @@ impl ser::Serialize for crate::Enum<'_> {
     }
 }
 
-impl<'a> ser::SerializeStructVariant for Enum<'a> {
+impl<'a, 'c> ser::SerializeStructVariant for Enum<'a, 'c> {
     type Ok = Value<'a>;
     type Error = Error;
 
@@ -102,11 +137,7 @@ impl<'a> ser::SerializeStructVariant for Enum<'a> {
     where
         T: ?Sized + ser::Serialize,
     {
-        if let Data::Struct { fields } = &mut self.r#enum.data {
-            let value = value.serialize(Serializer::with_human_readable(self.human_readable))?;
-            fields.push((Cow::Borrowed(key), value));
-        }
-        Ok(())
+        self.field_with(key, |serializer| value.serialize(serializer))
     }
 
     fn end(self) -> Result<Self::Ok, Error> {
@@ -114,7 +145,7 @@ impl<'a> ser::SerializeStructVariant for Enum<'a> {
     }
 }
 
-impl<'a> ser::SerializeTupleVariant for Enum<'a> {
+impl<'a, 'c> ser::SerializeTupleVariant for Enum<'a, 'c> {
     type Ok = Value<'a>;
     type Error = Error;
 
@@ -122,11 +153,7 @@ impl<'a> ser::SerializeTupleVariant for Enum<'a> {
     where
         T: ?Sized + ser::Serialize,
     {
-        if let Data::Tuple { values } = &mut self.r#enum.data {
-            let value = value.serialize(Serializer::with_human_readable(self.human_readable))?;
-            values.push(value);
-        }
-        Ok(())
+        self.element_with(|serializer| value.serialize(serializer))
     }
 
     fn end(self) -> Result<Self::Ok, Error> {
@@ -1,24 +1,40 @@
+use crate::path::Segment;
 use crate::ser::Value;
 use crate::Error;
 use crate::Serializer;
 use alloc::vec::Vec;
 use serde::ser;
 
-pub struct Map<'a> {
+pub struct Map<'a, 'c> {
     vec: Vec<(Value<'a>, Value<'a>)>,
-    human_readable: bool,
+    serializer: Serializer<'a, 'c>,
 }
 
-impl<'a> Map<'a> {
-    pub(super) const fn new(vec: Vec<(Value<'a>, Value<'a>)>, human_readable: bool) -> Self {
-        Self {
-            vec,
-            human_readable,
-        }
+impl<'a, 'c> Map<'a, 'c> {
+    pub(super) const fn new(
+        vec: Vec<(Value<'a>, Value<'a>)>,
+        serializer: Serializer<'a, 'c>,
+    ) -> Self {
+        Self { vec, serializer }
+    }
+
+    // Adds an entry with its key and value serialized with `key` and `value`.
+    pub(super) fn entry_with(
+        &mut self,
+        key: impl FnOnce(Serializer<'a, 'c>) -> Result<Value<'a>, Error>,
+        value: impl FnOnce(Serializer<'a, 'c>) -> Result<Value<'a>, Error>,
+    ) -> Result<(), Error> {
+        self.serializer.element()?;
+        let key = key(self.serializer)?;
+        let value = self
+            .serializer
+            .nested_with(|| Segment::key(&key), None, value)?;
+        self.vec.push((key, value));
+        Ok(())
     }
 }
 
-impl<'a> ser::SerializeMap for Map<'a> {
+impl<'a, 'c> ser::SerializeMap for Map<'a, 'c> {
     type Ok = Value<'a>;
     type Error = Error;
 
@@ -26,7 +42,8 @@ impl<'a> ser::SerializeMap for Map<'a> {
     where
         T: ?Sized + ser::Serialize,
     {
-        let key = key.serialize(Serializer::with_human_readable(self.human_readable))?;
+        self.serializer.element()?;
+        let key = key.serialize(self.serializer)?;
         self.vec.push((key, Value::Unit));
         Ok(())
     }
@@ -35,8 +52,10 @@ impl<'a> ser::SerializeMap for Map<'a> {
     where
         T: ?Sized + ser::Serialize,
     {
-        if let Some(last) = self.vec.last_mut() {
-            last.1 = value.serialize(Serializer::with_human_readable(self.human_readable))?;
+        if let Some((key, last)) = self.vec.last_mut() {
+            *last = self
+                .serializer
+                .serialize_nested(|| Segment::key(key), None, value)?;
         }
         Ok(())
     }
@@ -46,11 +65,10 @@ impl<'a> ser::SerializeMap for Map<'a> {
         K: ?Sized + ser::Serialize,
         V: ?Sized + ser::Serialize,
     {
-        let serializer = Serializer::with_human_readable(self.human_readable);
-        let key = key.serialize(serializer)?;
-        let value = value.serialize(serializer)?;
-        self.vec.push((key, value));
-        Ok(())
+        self.entry_with(
+            |serializer| key.serialize(serializer),
+            |serializer| value.serialize(serializer),
+        )
     }
 
     fn end(self) -> Result<Self::Ok, Error> {
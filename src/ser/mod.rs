@@ -1,5 +1,6 @@
 #![cfg(feature = "serde")]
 
+mod borrowed;
 mod r#enum;
 mod map;
 mod number;
@@ -8,11 +9,23 @@ mod r#struct;
 mod tests;
 mod tuple;
 
+pub use borrowed::SerializeBorrowed;
+
+#[cfg(feature = "std")]
+use crate::intern::Shared;
+use crate::limits::Counts;
+use crate::path::Segment;
+use crate::redact::PathState;
 use crate::Data;
 use crate::Error;
 use crate::Expected;
 use crate::Found;
+#[cfg(feature = "std")]
+use crate::Interner;
+use crate::Limits;
 use crate::Number;
+use crate::Redactor;
+use crate::Replacement;
 use crate::Value;
 use alloc::borrow::Cow;
 use alloc::borrow::ToOwned;
@@ -20,6 +33,7 @@ use alloc::boxed::Box;
 use alloc::string::ToString;
 use alloc::vec::Vec;
 use core::fmt;
+#[cfg(not(feature = "std"))]
 use core::marker::PhantomData;
 use map::Map;
 use r#enum::Enum;
@@ -32,13 +46,28 @@ use serde::ser::SerializeTuple;
 use tuple::Tuple;
 
 /// A structure for serialising Rust values into [crate::Value].
+///
+/// The values serialized live for `'a`, which is how long the strings they borrow, from an
+/// [Interner] or from [Serializer::serialize_borrowed], are borrowed for. The redactor and
+/// limits the serializer uses are only borrowed for `'c`, while serializing.
 #[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd)]
-pub struct Serializer<'a> {
+pub struct Serializer<'a, 'c> {
     human_readable: bool,
-    lifetime: PhantomData<&'a ()>,
+    redactor: Option<&'c Redactor>,
+    state: PathState,
+    limits: Option<&'c Limits>,
+    // The elements and bytes counted against the limits by the current call to `serialize`.
+    counts: Option<&'c Counts>,
+    #[cfg(feature = "std")]
+    interner: Option<Shared<'a>>,
+    // Without an interner, only `serialize_borrowed` borrows strings for `'a`.
+    #[cfg(not(feature = "std"))]
+    borrowed: PhantomData<&'a str>,
+    // How deeply the value being serialized is nested.
+    depth: usize,
 }
 
-impl<'a> Serializer<'a> {
+impl<'a, 'c> Serializer<'a, 'c> {
     /// Creates a serializer.
     ///
     /// The serializer created doesn't serialize in human-readable form. To serialize
@@ -46,7 +75,15 @@ impl<'a> Serializer<'a> {
     pub const fn new() -> Self {
         Self {
             human_readable: false,
-            lifetime: PhantomData,
+            redactor: None,
+            state: PathState::NONE,
+            limits: None,
+            counts: None,
+            #[cfg(feature = "std")]
+            interner: None,
+            #[cfg(not(feature = "std"))]
+            borrowed: PhantomData,
+            depth: 0,
         }
     }
 
@@ -56,33 +93,203 @@ impl<'a> Serializer<'a> {
         self
     }
 
+    /// Redact values matching the policies of `redactor` while serializing.
+    ///
+    /// Values replaced with [Replacement::Marker] are never serialized at all. The other
+    /// replacements are computed from the value, so it is serialized in full first, counting
+    /// against any [Limits], and dropped as soon as it has been replaced. Prefer
+    /// [Replacement::Marker] for secrets that shouldn't be held in memory even briefly.
+    ///
+    /// Serializing fails if the path patterns of `redactor` have too many segments. See
+    /// [Redactor::policy].
+    pub fn redact(mut self, redactor: &'c Redactor) -> Self {
+        self.redactor = Some(redactor);
+        self.state = redactor.start();
+        self
+    }
+
+    /// Stop serializing with an error once the value goes over `limits`.
+    ///
+    /// The elements and bytes in total are counted afresh by each call to
+    /// [Serializer::serialize] and [Serializer::serialize_borrowed]. When the serializer is passed
+    /// to a `Serialize` implementation directly, only the depth and string length limits apply.
+    /// See [Limits] for details.
+    pub const fn limits(mut self, limits: &'c Limits) -> Self {
+        self.limits = Some(limits);
+        self
+    }
+
+    /// Share equal strings between values by interning them in `interner`.
+    ///
+    /// The strings of the values serialized borrow from `interner`. See [Interner] for details.
+    #[cfg(feature = "std")]
+    pub const fn intern(mut self, interner: &'a Interner) -> Self {
+        self.interner = Some(Shared(interner));
+        self
+    }
+
     /// Convert a `T` into `Value` which is an enum that can represent any valid Rust data.
     pub fn serialize<T>(self, value: T) -> Result<Value<'a>, Error>
     where
         T: ser::Serialize,
     {
-        value.serialize(self)
+        self.counted(|serializer| value.serialize(serializer))
     }
 
-    const fn with_human_readable(human_readable: bool) -> Self {
-        Self {
-            human_readable,
-            lifetime: PhantomData,
+    // Runs `serialize` with a serializer counting elements and bytes against the limits afresh.
+    fn counted(
+        self,
+        serialize: impl FnOnce(Serializer<'a, '_>) -> Result<Value<'a>, Error>,
+    ) -> Result<Value<'a>, Error> {
+        if self.limits.is_none() {
+            return serialize(self);
+        }
+        let counts = Counts::new();
+        serialize(Serializer {
+            counts: Some(&counts),
+            ..self
+        })
+    }
+
+    // Creates a string value, interning it if there is a pool to intern it in.
+    fn string(self, value: &str) -> Value<'a> {
+        #[cfg(feature = "std")]
+        if let Some(Shared(interner)) = self.interner {
+            return Value::String(Cow::Borrowed(interner.intern(value)));
+        }
+        Value::String(Cow::Owned(value.to_owned()))
+    }
+
+    // Creates a serializer for the values nested inside a value, one level deeper.
+    fn enter(self) -> Result<Self, Error> {
+        match self.limits {
+            Some(limits) => Ok(Self {
+                depth: limits.enter(self.depth)?,
+                ..self
+            }),
+            None => Ok(self),
+        }
+    }
+
+    // Counts an element, map entry or field of a value.
+    fn element(self) -> Result<(), Error> {
+        match (self.limits, self.counts) {
+            (Some(limits), Some(counts)) => limits.elements(counts, 1),
+            _ => Ok(()),
+        }
+    }
+
+    // Counts a string, byte array or field name of `len` bytes.
+    fn bytes(self, len: usize) -> Result<(), Error> {
+        match (self.limits, self.counts) {
+            (Some(limits), Some(counts)) => limits.bytes(counts, len),
+            (Some(limits), None) => limits.string(len),
+            (None, _) => Ok(()),
         }
     }
+
+    // Creates a serializer for data nested under `segment`.
+    fn nested(self, segment: impl FnOnce() -> Segment) -> Self {
+        match self.redactor {
+            Some(redactor) => Self {
+                state: redactor.step(self.state, &segment()),
+                ..self
+            },
+            None => self,
+        }
+    }
+
+    // Serializes a value nested under `segment`, redacting it if it matches any policy.
+    // `name` is the name of the struct or enum the value is a part of.
+    fn serialize_nested<T>(
+        self,
+        segment: impl FnOnce() -> Segment,
+        name: Option<&str>,
+        value: &T,
+    ) -> Result<Value<'a>, Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        self.nested_with(segment, name, |serializer| value.serialize(serializer))
+    }
+
+    // Like `serialize_nested`, but serializes the value with `serialize`.
+    fn nested_with(
+        self,
+        segment: impl FnOnce() -> Segment,
+        name: Option<&str>,
+        serialize: impl FnOnce(Self) -> Result<Value<'a>, Error>,
+    ) -> Result<Value<'a>, Error> {
+        let Some(redactor) = self.redactor else {
+            return serialize(self);
+        };
+        redactor.check()?;
+        let segment = segment();
+        let serializer = Self {
+            state: redactor.step(self.state, &segment),
+            ..self
+        };
+        match redactor.find(serializer.state, &segment, name) {
+            Some(Replacement::Marker) => Ok(Replacement::Marker.apply(Value::Unit)),
+            // Masks and hashes need the value itself, which is dropped once it is replaced.
+            Some(replacement) => Ok(replacement.apply(serialize(serializer)?)),
+            None => serialize(serializer),
+        }
+    }
+
+    fn newtype_struct_with(
+        self,
+        name: &'static str,
+        serialize: impl FnOnce(Self) -> Result<Value<'a>, Error>,
+    ) -> Result<Value<'a>, Error> {
+        Ok(Value::Struct(Box::new(super::Struct {
+            name: Cow::Borrowed(name),
+            data: Data::NewType {
+                value: serialize(self.enter()?)?,
+            },
+        })))
+    }
+
+    fn newtype_variant_with(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        serialize: impl FnOnce(Self) -> Result<Value<'a>, Error>,
+    ) -> Result<Value<'a>, Error> {
+        let serializer = self
+            .enter()?
+            .nested(|| Segment::Variant(Cow::Borrowed(variant)));
+        Ok(Value::Enum(Box::new(super::Enum {
+            name: Cow::Borrowed(name),
+            variant_index,
+            variant: Cow::Borrowed(variant),
+            data: Data::NewType {
+                value: serialize(serializer)?,
+            },
+        })))
+    }
+
+    fn some_with(
+        self,
+        serialize: impl FnOnce(Self) -> Result<Value<'a>, Error>,
+    ) -> Result<Value<'a>, Error> {
+        let value = serialize(self.enter()?)?;
+        Ok(Value::Option(Some(Box::new(value))))
+    }
 }
 
-impl<'a> ser::Serializer for Serializer<'a> {
+impl<'a, 'c> ser::Serializer for Serializer<'a, 'c> {
     type Ok = Value<'a>;
     type Error = Error;
 
-    type SerializeSeq = Seq<'a>;
-    type SerializeTuple = Tuple<'a>;
-    type SerializeTupleStruct = Struct<'a>;
-    type SerializeTupleVariant = Enum<'a>;
-    type SerializeMap = Map<'a>;
-    type SerializeStruct = Struct<'a>;
-    type SerializeStructVariant = Enum<'a>;
+    type SerializeSeq = Seq<'a, 'c>;
+    type SerializeTuple = Tuple<'a, 'c>;
+    type SerializeTupleStruct = Struct<'a, 'c>;
+    type SerializeTupleVariant = Enum<'a, 'c>;
+    type SerializeMap = Map<'a, 'c>;
+    type SerializeStruct = Struct<'a, 'c>;
+    type SerializeStructVariant = Enum<'a, 'c>;
 
     fn serialize_bool(self, value: bool) -> Result<Self::Ok, Error> {
         Ok(Value::Bool(value))
@@ -141,10 +348,12 @@ impl<'a> ser::Serializer for Serializer<'a> {
     }
 
     fn serialize_str(self, value: &str) -> Result<Self::Ok, Error> {
-        Ok(Value::String(Cow::Owned(value.to_owned())))
+        self.bytes(value.len())?;
+        Ok(self.string(value))
     }
 
     fn serialize_bytes(self, value: &[u8]) -> Result<Self::Ok, Error> {
+        self.bytes(value.len())?;
         Ok(Value::Bytes(Cow::Owned(value.to_owned())))
     }
 
@@ -177,12 +386,7 @@ impl<'a> ser::Serializer for Serializer<'a> {
     where
         T: ?Sized + ser::Serialize,
     {
-        Ok(Value::Struct(Box::new(super::Struct {
-            name: Cow::Borrowed(name),
-            data: Data::NewType {
-                value: value.serialize(self)?,
-            },
-        })))
+        self.newtype_struct_with(name, |serializer| value.serialize(serializer))
     }
 
     fn serialize_newtype_variant<T>(
@@ -195,14 +399,9 @@ impl<'a> ser::Serializer for Serializer<'a> {
     where
         T: ?Sized + ser::Serialize,
     {
-        Ok(Value::Enum(Box::new(super::Enum {
-            name: Cow::Borrowed(name),
-            variant_index,
-            variant: Cow::Borrowed(variant),
-            data: Data::NewType {
-                value: value.serialize(self)?,
-            },
-        })))
+        self.newtype_variant_with(name, variant_index, variant, |serializer| {
+            value.serialize(serializer)
+        })
     }
 
     fn serialize_none(self) -> Result<Self::Ok, Error> {
@@ -213,19 +412,18 @@ impl<'a> ser::Serializer for Serializer<'a> {
     where
         T: ?Sized + ser::Serialize,
     {
-        let value = value.serialize(self)?;
-        Ok(Value::Option(Some(Box::new(value))))
+        self.some_with(|serializer| value.serialize(serializer))
     }
 
     fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, Error> {
         Ok(Seq::new(
             Vec::with_capacity(len.unwrap_or_default()),
-            self.human_readable,
+            self.enter()?,
         ))
     }
 
     fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Error> {
-        Ok(Tuple::new(Vec::with_capacity(len), self.human_readable))
+        Ok(Tuple::new(Vec::with_capacity(len), self.enter()?))
     }
 
     fn serialize_tuple_struct(
@@ -239,7 +437,7 @@ impl<'a> ser::Serializer for Serializer<'a> {
                 values: Vec::with_capacity(len),
             },
         };
-        Ok(Struct::new(st, self.human_readable))
+        Ok(Struct::new(st, self.enter()?))
     }
 
     fn serialize_tuple_variant(
@@ -249,6 +447,9 @@ impl<'a> ser::Serializer for Serializer<'a> {
         variant: &'static str,
         len: usize,
     ) -> Result<Self::SerializeTupleVariant, Error> {
+        let serializer = self
+            .enter()?
+            .nested(|| Segment::Variant(Cow::Borrowed(variant)));
         let en = super::Enum {
             name: Cow::Borrowed(name),
             variant_index,
@@ -257,13 +458,13 @@ impl<'a> ser::Serializer for Serializer<'a> {
                 values: Vec::with_capacity(len),
             },
         };
-        Ok(Enum::new(en, self.human_readable))
+        Ok(Enum::new(en, serializer))
     }
 
     fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap, Error> {
         Ok(Map::new(
             Vec::with_capacity(len.unwrap_or_default()),
-            self.human_readable,
+            self.enter()?,
         ))
     }
 
@@ -278,7 +479,7 @@ impl<'a> ser::Serializer for Serializer<'a> {
                 fields: Vec::with_capacity(len),
             },
         };
-        Ok(Struct::new(st, self.human_readable))
+        Ok(Struct::new(st, self.enter()?))
     }
 
     fn serialize_struct_variant(
@@ -288,6 +489,9 @@ impl<'a> ser::Serializer for Serializer<'a> {
         variant: &'static str,
         len: usize,
     ) -> Result<Self::SerializeStructVariant, Error> {
+        let serializer = self
+            .enter()?
+            .nested(|| Segment::Variant(Cow::Borrowed(variant)));
         let en = super::Enum {
             name: Cow::Borrowed(name),
             variant_index,
@@ -296,14 +500,20 @@ impl<'a> ser::Serializer for Serializer<'a> {
                 fields: Vec::with_capacity(len),
             },
         };
-        Ok(Enum::new(en, self.human_readable))
+        Ok(Enum::new(en, serializer))
     }
 
     fn collect_str<T>(self, value: &T) -> Result<Self::Ok, Self::Error>
     where
         T: ?Sized + fmt::Display,
     {
-        Ok(Value::String(Cow::Owned(value.to_string())))
+        let value = value.to_string();
+        self.bytes(value.len())?;
+        #[cfg(feature = "std")]
+        if self.interner.is_some() {
+            return Ok(self.string(&value));
+        }
+        Ok(Value::String(Cow::Owned(value)))
     }
 
     fn is_human_readable(&self) -> bool {
@@ -1,24 +1,36 @@
+use crate::path::Segment;
 use crate::ser::Value;
 use crate::Error;
 use crate::Serializer;
 use alloc::vec::Vec;
 use serde::ser;
 
-pub struct Seq<'a> {
+pub struct Seq<'a, 'c> {
     vec: Vec<Value<'a>>,
-    human_readable: bool,
+    serializer: Serializer<'a, 'c>,
 }
 
-impl<'a> Seq<'a> {
-    pub(super) const fn new(vec: Vec<Value<'a>>, human_readable: bool) -> Self {
-        Self {
-            vec,
-            human_readable,
-        }
+impl<'a, 'c> Seq<'a, 'c> {
+    pub(super) const fn new(vec: Vec<Value<'a>>, serializer: Serializer<'a, 'c>) -> Self {
+        Self { vec, serializer }
+    }
+
+    // Adds an element serialized with `serialize`.
+    pub(super) fn element_with(
+        &mut self,
+        serialize: impl FnOnce(Serializer<'a, 'c>) -> Result<Value<'a>, Error>,
+    ) -> Result<(), Error> {
+        self.serializer.element()?;
+        let index = self.vec.len();
+        let value = self
+            .serializer
+            .nested_with(|| Segment::Index(index), None, serialize)?;
+        self.vec.push(value);
+        Ok(())
     }
 }
 
-impl<'a> ser::SerializeSeq for Seq<'a> {
+impl<'a, 'c> ser::SerializeSeq for Seq<'a, 'c> {
     type Ok = Value<'a>;
     type Error = Error;
 
@@ -26,9 +38,7 @@ impl<'a> ser::SerializeSeq for Seq<'a> {
     where
         T: ?Sized + ser::Serialize,
     {
-        let value = value.serialize(Serializer::with_human_readable(self.human_readable))?;
-        self.vec.push(value);
-        Ok(())
+        self.element_with(|serializer| value.serialize(serializer))
     }
 
     fn end(self) -> Result<Self::Ok, Error> {
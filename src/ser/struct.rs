@@ -1,3 +1,4 @@
+use crate::path::Segment;
 use crate::ser::Value;
 use crate::Data;
 use crate::Error;
@@ -11,18 +12,55 @@ use serde::ser::SerializeTupleStruct;
 
 use super::to_static_str;
 
-pub struct Struct<'a> {
+pub struct Struct<'a, 'c> {
     r#struct: crate::Struct<'a>,
-    human_readable: bool,
+    serializer: Serializer<'a, 'c>,
 }
 
-impl<'a> Struct<'a> {
-    pub(super) const fn new(r#struct: crate::Struct<'a>, human_readable: bool) -> Self {
+impl<'a, 'c> Struct<'a, 'c> {
+    pub(super) const fn new(r#struct: crate::Struct<'a>, serializer: Serializer<'a, 'c>) -> Self {
         Self {
             r#struct,
-            human_readable,
+            serializer,
         }
     }
+
+    // Adds a field serialized with `serialize` to object-like data.
+    pub(super) fn field_with(
+        &mut self,
+        key: &'static str,
+        serialize: impl FnOnce(Serializer<'a, 'c>) -> Result<Value<'a>, Error>,
+    ) -> Result<(), Error> {
+        self.serializer.element()?;
+        self.serializer.bytes(key.len())?;
+        if let Data::Struct { fields } = &mut self.r#struct.data {
+            let name = Some(self.r#struct.name.as_ref());
+            let value = self.serializer.nested_with(
+                || Segment::Field(Cow::Borrowed(key)),
+                name,
+                serialize,
+            )?;
+            fields.push((Cow::Borrowed(key), value));
+        }
+        Ok(())
+    }
+
+    // Adds a value serialized with `serialize` to tuple data.
+    pub(super) fn element_with(
+        &mut self,
+        serialize: impl FnOnce(Serializer<'a, 'c>) -> Result<Value<'a>, Error>,
+    ) -> Result<(), Error> {
+        self.serializer.element()?;
+        if let Data::Tuple { values } = &mut self.r#struct.data {
+            let name = Some(self.r#struct.name.as_ref());
+            let index = values.len();
+            let value = self
+                .serializer
+                .nested_with(|| Segment::Index(index), name, serialize)?;
+            values.push(value);
+        }
+        Ok(())
+    }
 }
 
 impl ser::Serialize for crate::Struct<'_> {
@@ -70,7 +108,7 @@ impl ser::Serialize for crate::Struct<'_> {
     }
 }
 
-impl<'a> ser::SerializeStruct for Struct<'a> {
+impl<'a, 'c> ser::SerializeStruct for Struct<'a, 'c> {
     type Ok = Value<'a>;
     type Error = Error;
 
@@ -78,11 +116,7 @@ impl<'a> ser::SerializeStruct for Struct<'a> {
     where
         T: ?Sized + ser::Serialize,
     {
-        if let Data::Struct { fields } = &mut self.r#struct.data {
-            let value = value.serialize(Serializer::with_human_readable(self.human_readable))?;
-            fields.push((Cow::Borrowed(key), value));
-        }
-        Ok(())
+        self.field_with(key, |serializer| value.serialize(serializer))
     }
 
     fn end(self) -> Result<Self::Ok, Error> {
@@ -90,7 +124,7 @@ impl<'a> ser::SerializeStruct for Struct<'a> {
     }
 }
 
-impl<'a> ser::SerializeTupleStruct for Struct<'a> {
+impl<'a, 'c> ser::SerializeTupleStruct for Struct<'a, 'c> {
     type Ok = Value<'a>;
     type Error = Error;
 
@@ -98,11 +132,7 @@ impl<'a> ser::SerializeTupleStruct for Struct<'a> {
     where
         T: ?Sized + ser::Serialize,
     {
-        if let Data::Tuple { values } = &mut self.r#struct.data {
-            let value = value.serialize(Serializer::with_human_readable(self.human_readable))?;
-            values.push(value);
-        }
-        Ok(())
+        self.element_with(|serializer| value.serialize(serializer))
     }
 
     fn end(self) -> Result<Self::Ok, Error> {
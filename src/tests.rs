@@ -1,7 +1,11 @@
 #![cfg(test)]
 
+use crate::hash::Sip;
+#[cfg(feature = "std")]
+use crate::intern::branch;
 use crate::Error;
 use crate::Value;
+use core::hash::Hasher;
 use core::mem::size_of;
 
 // Ensure the value works well with derive macros.
@@ -14,6 +18,16 @@ struct Bar(Value<'static>);
 #[derive(serde::Serialize, serde::Deserialize)]
 struct Foo(Bar);
 
+#[cfg(feature = "derive")]
+#[test]
+fn derive() {
+    let value = crate::Serializer::new().serialize(Foo(Bar(Value::Unit)));
+    let foo: Foo = crate::Deserializer::new(value.unwrap())
+        .deserialize()
+        .unwrap();
+    assert_eq!(foo.0 .0, Value::Unit);
+}
+
 #[test]
 fn value_size() {
     assert!(size_of::<Value>() <= 32);
@@ -23,3 +37,42 @@ fn value_size() {
 fn error_size() {
     assert!(size_of::<Error>() <= 8);
 }
+
+#[test]
+fn send_sync() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<crate::limits::Counts>();
+    #[cfg(feature = "std")]
+    assert_send_sync::<crate::Interner>();
+    #[cfg(feature = "serde")]
+    assert_send_sync::<crate::Serializer<'static, 'static>>();
+    #[cfg(feature = "serde")]
+    assert_send_sync::<crate::Deserializer<'static>>();
+}
+
+#[test]
+fn siphash() {
+    // The test vectors from the SipHash paper.
+    let key = core::array::from_fn(|i| i as u8);
+    assert_eq!(Sip::new(&key).finish(), 0x726f_db47_dd0e_0e31);
+    let message: [u8; 15] = core::array::from_fn(|i| i as u8);
+    let mut hasher = Sip::new(&key);
+    hasher.write(&message);
+    assert_eq!(hasher.finish(), 0xa129_ca61_49be_45e5);
+    // Writes can be split anywhere.
+    let mut hasher = Sip::new(&key);
+    hasher.write(&message[..3]);
+    hasher.write(&message[3..11]);
+    hasher.write(&message[11..]);
+    assert_eq!(hasher.finish(), 0xa129_ca61_49be_45e5);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn intern_branches() {
+    // Strings with the same hash part ways at the first bits of their bytes that differ.
+    let level = (0..64).find(|level| branch(0, "ab", *level) != branch(0, "ac", *level));
+    assert_eq!(level, Some(36));
+    assert_eq!(branch(0b1101, "", 1), 0b11);
+    assert_eq!(branch(0, "", 100), 0);
+}
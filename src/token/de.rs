@@ -0,0 +1,359 @@
+use super::Token;
+use crate::Error;
+use alloc::borrow::Cow;
+use serde::de;
+use serde::de::IntoDeserializer;
+use serde::de::Unexpected;
+
+/// A deserializer replaying [Token]s into a value.
+///
+/// Tokens are consumed as the `Deserialize` implementation asks for them, like
+/// `serde_test` does, so it sees the same calls it would from a self-describing format.
+/// Strings and byte arrays borrowed by the tokens can be borrowed by the value.
+///
+/// ## Example
+///
+/// ```
+/// # use serde_content::token::{Deserializer, Token};
+/// let tokens = [Token::Some, Token::Str("Ferris".into())];
+/// let name: Option<&str> = Deserializer::new(&tokens).deserialize().unwrap();
+/// assert_eq!(name, Some("Ferris"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Deserializer<'t, 'de> {
+    tokens: &'t [Token<'de>],
+    human_readable: bool,
+}
+
+impl<'t, 'de> Deserializer<'t, 'de> {
+    /// Creates a deserializer replaying `tokens`.
+    ///
+    /// The deserializer created doesn't deserialize in human-readable form. To deserialize
+    /// in human-readable form, call [Deserializer::human_readable] on the resulting deserializer.
+    pub const fn new(tokens: &'t [Token<'de>]) -> Self {
+        Self {
+            tokens,
+            human_readable: false,
+        }
+    }
+
+    /// Make `Deserialize` implementations deserialize in human-readable form.
+    pub const fn human_readable(mut self) -> Self {
+        self.human_readable = true;
+        self
+    }
+
+    /// Deserializes a `T` from the tokens, which must all be used up.
+    pub fn deserialize<T>(mut self) -> Result<T, Error>
+    where
+        T: de::Deserialize<'de>,
+    {
+        let value = T::deserialize(&mut self)?;
+        match self.tokens.first() {
+            Some(token) => Err(Error::custom(format_args!("trailing token {token:?}"))),
+            None => Ok(value),
+        }
+    }
+
+    fn peek(&self) -> Option<&'t Token<'de>> {
+        self.tokens.first()
+    }
+
+    fn next(&mut self) -> Result<&'t Token<'de>, Error> {
+        let (token, rest) = self
+            .tokens
+            .split_first()
+            .ok_or_else(|| Error::custom("unexpected end of tokens"))?;
+        self.tokens = rest;
+        Ok(token)
+    }
+
+    fn expect(&mut self, end: &Token<'_>) -> Result<(), Error> {
+        match self.next()? {
+            token if token == end => Ok(()),
+            token => Err(Error::custom(format_args!(
+                "expected {end:?}, found {token:?}"
+            ))),
+        }
+    }
+
+    // Visits the elements of a sequence or the entries of a map, up to `end`.
+    fn compound<V>(
+        &mut self,
+        visitor: V,
+        len: Option<usize>,
+        end: Token<'static>,
+        map: bool,
+    ) -> Result<V::Value, Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        let mut access = Access {
+            de: self,
+            len,
+            end: &end,
+        };
+        let value = match map {
+            true => visitor.visit_map(&mut access)?,
+            false => visitor.visit_seq(&mut access)?,
+        };
+        self.expect(&end)?;
+        Ok(value)
+    }
+}
+
+impl<'de> de::Deserializer<'de> for &mut Deserializer<'_, 'de> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        let token = self.next()?;
+        match token {
+            Token::Bool(v) => visitor.visit_bool(*v),
+            Token::I8(v) => visitor.visit_i8(*v),
+            Token::I16(v) => visitor.visit_i16(*v),
+            Token::I32(v) => visitor.visit_i32(*v),
+            Token::I64(v) => visitor.visit_i64(*v),
+            Token::I128(v) => visitor.visit_i128(*v),
+            Token::U8(v) => visitor.visit_u8(*v),
+            Token::U16(v) => visitor.visit_u16(*v),
+            Token::U32(v) => visitor.visit_u32(*v),
+            Token::U64(v) => visitor.visit_u64(*v),
+            Token::U128(v) => visitor.visit_u128(*v),
+            Token::F32(v) => visitor.visit_f32(*v),
+            Token::F64(v) => visitor.visit_f64(*v),
+            Token::Char(v) => visitor.visit_char(*v),
+            Token::Str(Cow::Borrowed(v)) => visitor.visit_borrowed_str(v),
+            Token::Str(Cow::Owned(v)) => visitor.visit_str(v),
+            Token::Field(Cow::Borrowed(v)) => visitor.visit_borrowed_str(v),
+            Token::Field(Cow::Owned(v)) => visitor.visit_str(v),
+            Token::Bytes(Cow::Borrowed(v)) => visitor.visit_borrowed_bytes(v),
+            Token::Bytes(Cow::Owned(v)) => visitor.visit_bytes(v),
+            Token::None => visitor.visit_none(),
+            Token::Some => visitor.visit_some(self),
+            Token::Unit | Token::UnitStruct { .. } => visitor.visit_unit(),
+            Token::NewtypeStruct { .. } => visitor.visit_newtype_struct(self),
+            Token::Seq { len } => self.compound(visitor, *len, Token::SeqEnd, false),
+            Token::Tuple { len } => self.compound(visitor, Some(*len), Token::TupleEnd, false),
+            Token::TupleStruct { len, .. } => {
+                self.compound(visitor, Some(*len), Token::TupleStructEnd, false)
+            }
+            Token::Map { len } => self.compound(visitor, *len, Token::MapEnd, true),
+            Token::Struct { len, .. } => self.compound(visitor, Some(*len), Token::StructEnd, true),
+            Token::UnitVariant { .. }
+            | Token::NewtypeVariant { .. }
+            | Token::TupleVariant { .. }
+            | Token::StructVariant { .. } => visitor.visit_enum(Variant { de: self, token }),
+            token => Err(Error::custom(format_args!("unexpected token {token:?}"))),
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self.peek() {
+            Some(Token::None | Token::Unit) => {
+                self.tokens = &self.tokens[1..];
+                visitor.visit_none()
+            }
+            Some(Token::Some) => {
+                self.tokens = &self.tokens[1..];
+                visitor.visit_some(self)
+            }
+            // Any other value stands for itself wrapped in `Some`.
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        if let Some(Token::NewtypeStruct { .. }) = self.peek() {
+            self.tokens = &self.tokens[1..];
+        }
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self.peek() {
+            // A unit variant can also be given by its name alone.
+            Some(Token::Str(variant)) => {
+                self.tokens = &self.tokens[1..];
+                visitor.visit_enum(variant.as_ref().into_deserializer())
+            }
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.human_readable
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
+
+struct Access<'a, 't, 'de> {
+    de: &'a mut Deserializer<'t, 'de>,
+    len: Option<usize>,
+    end: &'a Token<'static>,
+}
+
+impl Access<'_, '_, '_> {
+    // Whether the next token is the one ending the sequence or map.
+    fn done(&mut self) -> bool {
+        let done = self.de.peek() == Some(self.end);
+        if !done {
+            self.len = self.len.map(|len| len.saturating_sub(1));
+        }
+        done
+    }
+}
+
+impl<'de> de::SeqAccess<'de> for &mut Access<'_, '_, 'de> {
+    type Error = Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Error>
+    where
+        T: de::DeserializeSeed<'de>,
+    {
+        match self.done() {
+            true => Ok(None),
+            false => seed.deserialize(&mut *self.de).map(Some),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.len
+    }
+}
+
+impl<'de> de::MapAccess<'de> for &mut Access<'_, '_, 'de> {
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Error>
+    where
+        K: de::DeserializeSeed<'de>,
+    {
+        match self.done() {
+            true => Ok(None),
+            false => seed.deserialize(&mut *self.de).map(Some),
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Error>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        seed.deserialize(&mut *self.de)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.len
+    }
+}
+
+struct Variant<'a, 't, 'de> {
+    de: &'a mut Deserializer<'t, 'de>,
+    token: &'t Token<'de>,
+}
+
+impl<'de> de::EnumAccess<'de> for Variant<'_, '_, 'de> {
+    type Error = Error;
+    type Variant = Self;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self), Error>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        let variant = match self.token {
+            Token::UnitVariant { variant, .. }
+            | Token::NewtypeVariant { variant, .. }
+            | Token::TupleVariant { variant, .. }
+            | Token::StructVariant { variant, .. } => variant,
+            token => return Err(Error::custom(format_args!("unexpected token {token:?}"))),
+        };
+        let deserializer: de::value::StrDeserializer<Error> = variant.as_ref().into_deserializer();
+        Ok((seed.deserialize(deserializer)?, self))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for Variant<'_, '_, 'de> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        match self.token {
+            Token::UnitVariant { .. } => Ok(()),
+            token => Err(invalid_variant(token, &"unit variant")),
+        }
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, Error>
+    where
+        T: de::DeserializeSeed<'de>,
+    {
+        match self.token {
+            Token::NewtypeVariant { .. } => seed.deserialize(self.de),
+            token => Err(invalid_variant(token, &"newtype variant")),
+        }
+    }
+
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value, Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self.token {
+            Token::TupleVariant { len, .. } => {
+                self.de
+                    .compound(visitor, Some(*len), Token::TupleVariantEnd, false)
+            }
+            token => Err(invalid_variant(token, &"tuple variant")),
+        }
+    }
+
+    fn struct_variant<V>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self.token {
+            Token::StructVariant { len, .. } => {
+                self.de
+                    .compound(visitor, Some(*len), Token::StructVariantEnd, true)
+            }
+            token => Err(invalid_variant(token, &"struct variant")),
+        }
+    }
+}
+
+fn invalid_variant(token: &Token<'_>, expected: &dyn de::Expected) -> Error {
+    let unexpected = match token {
+        Token::UnitVariant { .. } => Unexpected::UnitVariant,
+        Token::NewtypeVariant { .. } => Unexpected::NewtypeVariant,
+        Token::TupleVariant { .. } => Unexpected::TupleVariant,
+        _ => Unexpected::StructVariant,
+    };
+    de::Error::invalid_type(unexpected, expected)
+}
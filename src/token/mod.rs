@@ -0,0 +1,590 @@
+//! Token streams describing values, like those of `serde_test`.
+//!
+//! A [Token] stands for one call a `Serialize` implementation makes into a serializer, so a
+//! sequence of them spells out a value the way `serde_test` does. Unlike `serde_test`, tokens
+//! own their strings, and cover 128-bit integers and the indices of enum variants.
+//!
+//! [Value::to_tokens] and [Value::from_tokens] convert between values and tokens. With the
+//! `serde` feature, [Serializer] records the tokens of any `Serialize` type, and
+//! [Deserializer] replays them into any `Deserialize` type.
+//!
+//! ## Example
+//!
+//! ```
+//! # use serde_content::token::Token;
+//! # use serde_content::value;
+//! let value = value!([1u8, "two"]);
+//! let tokens = value.to_tokens();
+//! assert_eq!(
+//!     tokens,
+//!     [
+//!         Token::Seq { len: Some(2) },
+//!         Token::U8(1),
+//!         Token::Str("two".into()),
+//!         Token::SeqEnd,
+//!     ]
+//! );
+//! assert_eq!(serde_content::Value::from_tokens(&tokens).unwrap(), value);
+//! ```
+
+#[cfg(feature = "serde")]
+mod de;
+#[cfg(feature = "serde")]
+mod ser;
+
+use crate::Data;
+use crate::Enum;
+use crate::Error;
+use crate::Number;
+use crate::Struct;
+use crate::Value;
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+
+#[cfg(feature = "serde")]
+pub use de::Deserializer;
+#[cfg(feature = "serde")]
+pub use ser::Serializer;
+
+/// One step of serializing a value, named after the `serde_test` token for the same step.
+///
+/// The fields of structs and struct variants are each a [Token::Field] holding the name of the
+/// field, followed by the tokens of its value. [Value::from_tokens] also takes a [Token::Str] as
+/// a field name, as `serde_test` writes them, but the name is then copied.
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub enum Token<'a> {
+    /// A call to `serialize_bool`.
+    Bool(bool),
+    /// A call to `serialize_i8`.
+    I8(i8),
+    /// A call to `serialize_i16`.
+    I16(i16),
+    /// A call to `serialize_i32`.
+    I32(i32),
+    /// A call to `serialize_i64`.
+    I64(i64),
+    /// A call to `serialize_i128`.
+    I128(i128),
+    /// A call to `serialize_u8`.
+    U8(u8),
+    /// A call to `serialize_u16`.
+    U16(u16),
+    /// A call to `serialize_u32`.
+    U32(u32),
+    /// A call to `serialize_u64`.
+    U64(u64),
+    /// A call to `serialize_u128`.
+    U128(u128),
+    /// A call to `serialize_f32`.
+    F32(f32),
+    /// A call to `serialize_f64`.
+    F64(f64),
+    /// A call to `serialize_char`.
+    Char(char),
+    /// A call to `serialize_str`.
+    Str(Cow<'a, str>),
+    /// The name of a struct field.
+    Field(Cow<'static, str>),
+    /// A call to `serialize_bytes`.
+    Bytes(Cow<'a, [u8]>),
+    /// A call to `serialize_none`.
+    None,
+    /// A call to `serialize_some`, followed by the tokens of the value.
+    Some,
+    /// A call to `serialize_unit`.
+    Unit,
+    /// A call to `serialize_unit_struct`.
+    UnitStruct {
+        /// The name of the struct.
+        name: Cow<'static, str>,
+    },
+    /// A call to `serialize_unit_variant`.
+    UnitVariant {
+        /// The name of the enum.
+        name: Cow<'static, str>,
+        /// The index of the variant.
+        variant_index: u32,
+        /// The name of the variant.
+        variant: Cow<'static, str>,
+    },
+    /// A call to `serialize_newtype_struct`, followed by the tokens of the value.
+    NewtypeStruct {
+        /// The name of the struct.
+        name: Cow<'static, str>,
+    },
+    /// A call to `serialize_newtype_variant`, followed by the tokens of the value.
+    NewtypeVariant {
+        /// The name of the enum.
+        name: Cow<'static, str>,
+        /// The index of the variant.
+        variant_index: u32,
+        /// The name of the variant.
+        variant: Cow<'static, str>,
+    },
+    /// The start of a sequence, ended by [Token::SeqEnd].
+    Seq {
+        /// The length of the sequence, if known.
+        len: Option<usize>,
+    },
+    /// The end of a sequence.
+    SeqEnd,
+    /// The start of a tuple, ended by [Token::TupleEnd].
+    Tuple {
+        /// The length of the tuple.
+        len: usize,
+    },
+    /// The end of a tuple.
+    TupleEnd,
+    /// The start of a tuple struct, ended by [Token::TupleStructEnd].
+    TupleStruct {
+        /// The name of the struct.
+        name: Cow<'static, str>,
+        /// The number of fields.
+        len: usize,
+    },
+    /// The end of a tuple struct.
+    TupleStructEnd,
+    /// The start of a tuple variant, ended by [Token::TupleVariantEnd].
+    TupleVariant {
+        /// The name of the enum.
+        name: Cow<'static, str>,
+        /// The index of the variant.
+        variant_index: u32,
+        /// The name of the variant.
+        variant: Cow<'static, str>,
+        /// The number of fields.
+        len: usize,
+    },
+    /// The end of a tuple variant.
+    TupleVariantEnd,
+    /// The start of a map, ended by [Token::MapEnd]. Keys and values alternate.
+    Map {
+        /// The number of entries, if known.
+        len: Option<usize>,
+    },
+    /// The end of a map.
+    MapEnd,
+    /// The start of a struct, ended by [Token::StructEnd].
+    Struct {
+        /// The name of the struct.
+        name: Cow<'static, str>,
+        /// The number of fields.
+        len: usize,
+    },
+    /// The end of a struct.
+    StructEnd,
+    /// The start of a struct variant, ended by [Token::StructVariantEnd].
+    StructVariant {
+        /// The name of the enum.
+        name: Cow<'static, str>,
+        /// The index of the variant.
+        variant_index: u32,
+        /// The name of the variant.
+        variant: Cow<'static, str>,
+        /// The number of fields.
+        len: usize,
+    },
+    /// The end of a struct variant.
+    StructVariantEnd,
+}
+
+impl Token<'_> {
+    /// Moves data where possible or otherwise clones it into an owned object.
+    pub fn into_owned(self) -> Token<'static> {
+        match self {
+            Token::Str(v) => Token::Str(Cow::Owned(v.into_owned())),
+            Token::Field(v) => Token::Field(v),
+            Token::Bytes(v) => Token::Bytes(Cow::Owned(v.into_owned())),
+            Token::Bool(v) => Token::Bool(v),
+            Token::I8(v) => Token::I8(v),
+            Token::I16(v) => Token::I16(v),
+            Token::I32(v) => Token::I32(v),
+            Token::I64(v) => Token::I64(v),
+            Token::I128(v) => Token::I128(v),
+            Token::U8(v) => Token::U8(v),
+            Token::U16(v) => Token::U16(v),
+            Token::U32(v) => Token::U32(v),
+            Token::U64(v) => Token::U64(v),
+            Token::U128(v) => Token::U128(v),
+            Token::F32(v) => Token::F32(v),
+            Token::F64(v) => Token::F64(v),
+            Token::Char(v) => Token::Char(v),
+            Token::None => Token::None,
+            Token::Some => Token::Some,
+            Token::Unit => Token::Unit,
+            Token::UnitStruct { name } => Token::UnitStruct { name },
+            Token::UnitVariant {
+                name,
+                variant_index,
+                variant,
+            } => Token::UnitVariant {
+                name,
+                variant_index,
+                variant,
+            },
+            Token::NewtypeStruct { name } => Token::NewtypeStruct { name },
+            Token::NewtypeVariant {
+                name,
+                variant_index,
+                variant,
+            } => Token::NewtypeVariant {
+                name,
+                variant_index,
+                variant,
+            },
+            Token::Seq { len } => Token::Seq { len },
+            Token::SeqEnd => Token::SeqEnd,
+            Token::Tuple { len } => Token::Tuple { len },
+            Token::TupleEnd => Token::TupleEnd,
+            Token::TupleStruct { name, len } => Token::TupleStruct { name, len },
+            Token::TupleStructEnd => Token::TupleStructEnd,
+            Token::TupleVariant {
+                name,
+                variant_index,
+                variant,
+                len,
+            } => Token::TupleVariant {
+                name,
+                variant_index,
+                variant,
+                len,
+            },
+            Token::TupleVariantEnd => Token::TupleVariantEnd,
+            Token::Map { len } => Token::Map { len },
+            Token::MapEnd => Token::MapEnd,
+            Token::Struct { name, len } => Token::Struct { name, len },
+            Token::StructEnd => Token::StructEnd,
+            Token::StructVariant {
+                name,
+                variant_index,
+                variant,
+                len,
+            } => Token::StructVariant {
+                name,
+                variant_index,
+                variant,
+                len,
+            },
+            Token::StructVariantEnd => Token::StructVariantEnd,
+        }
+    }
+}
+
+impl From<Number> for Token<'_> {
+    fn from(number: Number) -> Self {
+        match number {
+            Number::I8(v) => Token::I8(v),
+            Number::U8(v) => Token::U8(v),
+            Number::I16(v) => Token::I16(v),
+            Number::U16(v) => Token::U16(v),
+            Number::I32(v) => Token::I32(v),
+            Number::U32(v) => Token::U32(v),
+            Number::F32(v) => Token::F32(v),
+            Number::I64(v) => Token::I64(v),
+            Number::U64(v) => Token::U64(v),
+            Number::F64(v) => Token::F64(v),
+            Number::I128(v) => Token::I128(v),
+            Number::U128(v) => Token::U128(v),
+        }
+    }
+}
+
+impl<'a> Value<'a> {
+    /// Lists the tokens of this value, the same ones [Serializer] records when serializing it.
+    pub fn to_tokens(&self) -> Vec<Token<'a>> {
+        let mut tokens = Vec::new();
+        push(&mut tokens, self);
+        tokens
+    }
+
+    /// Builds a value from its tokens.
+    ///
+    /// This is the inverse of [Value::to_tokens]. The lengths in the tokens are only hints, so
+    /// a sequence ends at its end token whatever its length says.
+    pub fn from_tokens(tokens: &[Token<'a>]) -> Result<Self, Error> {
+        let mut reader = Reader { tokens };
+        let value = reader.value()?;
+        match reader.tokens.first() {
+            Some(token) => Err(Error::custom(format_args!("trailing token {token:?}"))),
+            None => Ok(value),
+        }
+    }
+}
+
+fn push<'a>(tokens: &mut Vec<Token<'a>>, value: &Value<'a>) {
+    match value {
+        Value::Unit => tokens.push(Token::Unit),
+        Value::Bool(v) => tokens.push(Token::Bool(*v)),
+        Value::Number(v) => tokens.push((*v).into()),
+        Value::Char(v) => tokens.push(Token::Char(*v)),
+        Value::String(v) => tokens.push(Token::Str(v.clone())),
+        Value::Bytes(v) => tokens.push(Token::Bytes(v.clone())),
+        Value::Seq(v) => {
+            tokens.push(Token::Seq { len: Some(v.len()) });
+            push_all(tokens, v);
+            tokens.push(Token::SeqEnd);
+        }
+        Value::Map(v) => {
+            tokens.push(Token::Map { len: Some(v.len()) });
+            for (key, value) in v {
+                push(tokens, key);
+                push(tokens, value);
+            }
+            tokens.push(Token::MapEnd);
+        }
+        Value::Option(None) => tokens.push(Token::None),
+        Value::Option(Some(v)) => {
+            tokens.push(Token::Some);
+            push(tokens, v);
+        }
+        Value::Struct(v) => {
+            let name = v.name.clone();
+            match &v.data {
+                Data::Unit => tokens.push(Token::UnitStruct { name }),
+                Data::NewType { value } => {
+                    tokens.push(Token::NewtypeStruct { name });
+                    push(tokens, value);
+                }
+                Data::Tuple { values } => {
+                    let len = values.len();
+                    tokens.push(Token::TupleStruct { name, len });
+                    push_all(tokens, values);
+                    tokens.push(Token::TupleStructEnd);
+                }
+                Data::Struct { fields } => {
+                    let len = fields.len();
+                    tokens.push(Token::Struct { name, len });
+                    push_fields(tokens, fields);
+                    tokens.push(Token::StructEnd);
+                }
+            }
+        }
+        Value::Enum(v) => {
+            let name = v.name.clone();
+            let variant_index = v.variant_index;
+            let variant = v.variant.clone();
+            match &v.data {
+                Data::Unit => tokens.push(Token::UnitVariant {
+                    name,
+                    variant_index,
+                    variant,
+                }),
+                Data::NewType { value } => {
+                    tokens.push(Token::NewtypeVariant {
+                        name,
+                        variant_index,
+                        variant,
+                    });
+                    push(tokens, value);
+                }
+                Data::Tuple { values } => {
+                    tokens.push(Token::TupleVariant {
+                        name,
+                        variant_index,
+                        variant,
+                        len: values.len(),
+                    });
+                    push_all(tokens, values);
+                    tokens.push(Token::TupleVariantEnd);
+                }
+                Data::Struct { fields } => {
+                    tokens.push(Token::StructVariant {
+                        name,
+                        variant_index,
+                        variant,
+                        len: fields.len(),
+                    });
+                    push_fields(tokens, fields);
+                    tokens.push(Token::StructVariantEnd);
+                }
+            }
+        }
+        Value::Tuple(v) => {
+            tokens.push(Token::Tuple { len: v.len() });
+            push_all(tokens, v);
+            tokens.push(Token::TupleEnd);
+        }
+    }
+}
+
+fn push_all<'a>(tokens: &mut Vec<Token<'a>>, values: &[Value<'a>]) {
+    for value in values {
+        push(tokens, value);
+    }
+}
+
+fn push_fields<'a>(tokens: &mut Vec<Token<'a>>, fields: &[(Cow<'static, str>, Value<'a>)]) {
+    for (key, value) in fields {
+        tokens.push(Token::Field(key.clone()));
+        push(tokens, value);
+    }
+}
+
+struct Reader<'t, 'a> {
+    tokens: &'t [Token<'a>],
+}
+
+impl<'t, 'a> Reader<'t, 'a> {
+    fn next(&mut self) -> Result<&'t Token<'a>, Error> {
+        let (token, rest) = self
+            .tokens
+            .split_first()
+            .ok_or_else(|| Error::custom("unexpected end of tokens"))?;
+        self.tokens = rest;
+        Ok(token)
+    }
+
+    // Consumes `end` if it is the next token.
+    fn end(&mut self, end: &Token<'_>) -> bool {
+        match self.tokens.split_first() {
+            Some((token, rest)) if token == end => {
+                self.tokens = rest;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn value(&mut self) -> Result<Value<'a>, Error> {
+        let value = match self.next()? {
+            Token::Bool(v) => Value::Bool(*v),
+            Token::I8(v) => Value::Number(Number::I8(*v)),
+            Token::I16(v) => Value::Number(Number::I16(*v)),
+            Token::I32(v) => Value::Number(Number::I32(*v)),
+            Token::I64(v) => Value::Number(Number::I64(*v)),
+            Token::I128(v) => Value::Number(Number::I128(*v)),
+            Token::U8(v) => Value::Number(Number::U8(*v)),
+            Token::U16(v) => Value::Number(Number::U16(*v)),
+            Token::U32(v) => Value::Number(Number::U32(*v)),
+            Token::U64(v) => Value::Number(Number::U64(*v)),
+            Token::U128(v) => Value::Number(Number::U128(*v)),
+            Token::F32(v) => Value::Number(Number::F32(*v)),
+            Token::F64(v) => Value::Number(Number::F64(*v)),
+            Token::Char(v) => Value::Char(*v),
+            Token::Str(v) => Value::String(v.clone()),
+            Token::Bytes(v) => Value::Bytes(v.clone()),
+            Token::None => Value::Option(None),
+            Token::Some => Value::Option(Some(Box::new(self.value()?))),
+            Token::Unit => Value::Unit,
+            Token::Seq { .. } => Value::Seq(self.values(&Token::SeqEnd)?),
+            Token::Tuple { .. } => Value::Tuple(self.values(&Token::TupleEnd)?),
+            Token::Map { .. } => {
+                let mut entries = Vec::new();
+                while !self.end(&Token::MapEnd) {
+                    entries.push((self.value()?, self.value()?));
+                }
+                Value::Map(entries)
+            }
+            Token::UnitStruct { name } => structure(name.clone(), Data::Unit),
+            Token::NewtypeStruct { name } => structure(
+                name.clone(),
+                Data::NewType {
+                    value: self.value()?,
+                },
+            ),
+            Token::TupleStruct { name, .. } => structure(
+                name.clone(),
+                Data::Tuple {
+                    values: self.values(&Token::TupleStructEnd)?,
+                },
+            ),
+            Token::Struct { name, .. } => structure(
+                name.clone(),
+                Data::Struct {
+                    fields: self.fields(&Token::StructEnd)?,
+                },
+            ),
+            Token::UnitVariant {
+                name,
+                variant_index,
+                variant,
+            } => enumeration(name.clone(), *variant_index, variant.clone(), Data::Unit),
+            Token::NewtypeVariant {
+                name,
+                variant_index,
+                variant,
+            } => enumeration(
+                name.clone(),
+                *variant_index,
+                variant.clone(),
+                Data::NewType {
+                    value: self.value()?,
+                },
+            ),
+            Token::TupleVariant {
+                name,
+                variant_index,
+                variant,
+                ..
+            } => enumeration(
+                name.clone(),
+                *variant_index,
+                variant.clone(),
+                Data::Tuple {
+                    values: self.values(&Token::TupleVariantEnd)?,
+                },
+            ),
+            Token::StructVariant {
+                name,
+                variant_index,
+                variant,
+                ..
+            } => enumeration(
+                name.clone(),
+                *variant_index,
+                variant.clone(),
+                Data::Struct {
+                    fields: self.fields(&Token::StructVariantEnd)?,
+                },
+            ),
+            token => return Err(Error::custom(format_args!("unexpected token {token:?}"))),
+        };
+        Ok(value)
+    }
+
+    fn values(&mut self, end: &Token<'_>) -> Result<Vec<Value<'a>>, Error> {
+        let mut values = Vec::new();
+        while !self.end(end) {
+            values.push(self.value()?);
+        }
+        Ok(values)
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn fields(&mut self, end: &Token<'_>) -> Result<Vec<(Cow<'static, str>, Value<'a>)>, Error> {
+        let mut fields = Vec::new();
+        while !self.end(end) {
+            let key = match self.next()? {
+                Token::Field(key) => key.clone(),
+                Token::Str(key) => Cow::Owned(key.to_string()),
+                token => {
+                    return Err(Error::custom(format_args!(
+                        "expected field name, found {token:?}"
+                    )))
+                }
+            };
+            fields.push((key, self.value()?));
+        }
+        Ok(fields)
+    }
+}
+
+fn structure<'a>(name: Cow<'static, str>, data: Data<'a>) -> Value<'a> {
+    Value::Struct(Box::new(Struct { name, data }))
+}
+
+fn enumeration<'a>(
+    name: Cow<'static, str>,
+    variant_index: u32,
+    variant: Cow<'static, str>,
+    data: Data<'a>,
+) -> Value<'a> {
+    Value::Enum(Box::new(Enum {
+        name,
+        variant_index,
+        variant,
+        data,
+    }))
+}
@@ -0,0 +1,423 @@
+use super::Token;
+use crate::Error;
+use alloc::borrow::Cow;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use serde::ser;
+
+/// A serializer recording the [Token]s of a value.
+///
+/// ## Example
+///
+/// ```
+/// # use serde_content::token::{Serializer, Token};
+/// let tokens = Serializer::new().serialize(Some(('a', 1u8))).unwrap();
+/// assert_eq!(
+///     tokens,
+///     [
+///         Token::Some,
+///         Token::Tuple { len: 2 },
+///         Token::Char('a'),
+///         Token::U8(1),
+///         Token::TupleEnd,
+///     ]
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Serializer {
+    human_readable: bool,
+}
+
+impl Serializer {
+    /// Creates a serializer.
+    ///
+    /// The serializer created doesn't serialize in human-readable form. To serialize
+    /// in human-readable form, call [Serializer::human_readable] on the resulting serializer.
+    pub const fn new() -> Self {
+        Self {
+            human_readable: false,
+        }
+    }
+
+    /// Make `Serialize` implementations serialize in human-readable form.
+    pub const fn human_readable(mut self) -> Self {
+        self.human_readable = true;
+        self
+    }
+
+    /// Records the tokens of a `T`.
+    pub fn serialize<T>(self, value: T) -> Result<Vec<Token<'static>>, Error>
+    where
+        T: ser::Serialize,
+    {
+        let mut recorder = Recorder {
+            tokens: Vec::new(),
+            human_readable: self.human_readable,
+        };
+        value.serialize(&mut recorder)?;
+        Ok(recorder.tokens)
+    }
+}
+
+struct Recorder {
+    tokens: Vec<Token<'static>>,
+    human_readable: bool,
+}
+
+impl Recorder {
+    fn push(&mut self, token: Token<'static>) -> Result<(), Error> {
+        self.tokens.push(token);
+        Ok(())
+    }
+
+    // Starts a compound value, to be closed with `end`.
+    fn start(&mut self, token: Token<'static>, end: Token<'static>) -> Compound<'_> {
+        self.tokens.push(token);
+        Compound {
+            recorder: self,
+            end,
+        }
+    }
+}
+
+impl<'r> ser::Serializer for &'r mut Recorder {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = Compound<'r>;
+    type SerializeTuple = Compound<'r>;
+    type SerializeTupleStruct = Compound<'r>;
+    type SerializeTupleVariant = Compound<'r>;
+    type SerializeMap = Compound<'r>;
+    type SerializeStruct = Compound<'r>;
+    type SerializeStructVariant = Compound<'r>;
+
+    fn serialize_bool(self, v: bool) -> Result<(), Error> {
+        self.push(Token::Bool(v))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<(), Error> {
+        self.push(Token::I8(v))
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<(), Error> {
+        self.push(Token::I16(v))
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<(), Error> {
+        self.push(Token::I32(v))
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<(), Error> {
+        self.push(Token::I64(v))
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<(), Error> {
+        self.push(Token::I128(v))
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<(), Error> {
+        self.push(Token::U8(v))
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<(), Error> {
+        self.push(Token::U16(v))
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<(), Error> {
+        self.push(Token::U32(v))
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<(), Error> {
+        self.push(Token::U64(v))
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<(), Error> {
+        self.push(Token::U128(v))
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<(), Error> {
+        self.push(Token::F32(v))
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<(), Error> {
+        self.push(Token::F64(v))
+    }
+
+    fn serialize_char(self, v: char) -> Result<(), Error> {
+        self.push(Token::Char(v))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<(), Error> {
+        self.push(Token::Str(Cow::Owned(v.to_string())))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<(), Error> {
+        self.push(Token::Bytes(Cow::Owned(v.to_vec())))
+    }
+
+    fn serialize_none(self) -> Result<(), Error> {
+        self.push(Token::None)
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        self.tokens.push(Token::Some);
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), Error> {
+        self.push(Token::Unit)
+    }
+
+    fn serialize_unit_struct(self, name: &'static str) -> Result<(), Error> {
+        self.push(Token::UnitStruct {
+            name: Cow::Borrowed(name),
+        })
+    }
+
+    fn serialize_unit_variant(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+    ) -> Result<(), Error> {
+        self.push(Token::UnitVariant {
+            name: Cow::Borrowed(name),
+            variant_index,
+            variant: Cow::Borrowed(variant),
+        })
+    }
+
+    fn serialize_newtype_struct<T>(self, name: &'static str, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        self.tokens.push(Token::NewtypeStruct {
+            name: Cow::Borrowed(name),
+        });
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<(), Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        self.tokens.push(Token::NewtypeVariant {
+            name: Cow::Borrowed(name),
+            variant_index,
+            variant: Cow::Borrowed(variant),
+        });
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Compound<'r>, Error> {
+        Ok(self.start(Token::Seq { len }, Token::SeqEnd))
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Compound<'r>, Error> {
+        Ok(self.start(Token::Tuple { len }, Token::TupleEnd))
+    }
+
+    fn serialize_tuple_struct(self, name: &'static str, len: usize) -> Result<Compound<'r>, Error> {
+        let name = Cow::Borrowed(name);
+        Ok(self.start(Token::TupleStruct { name, len }, Token::TupleStructEnd))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Compound<'r>, Error> {
+        let token = Token::TupleVariant {
+            name: Cow::Borrowed(name),
+            variant_index,
+            variant: Cow::Borrowed(variant),
+            len,
+        };
+        Ok(self.start(token, Token::TupleVariantEnd))
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Compound<'r>, Error> {
+        Ok(self.start(Token::Map { len }, Token::MapEnd))
+    }
+
+    fn serialize_struct(self, name: &'static str, len: usize) -> Result<Compound<'r>, Error> {
+        let name = Cow::Borrowed(name);
+        Ok(self.start(Token::Struct { name, len }, Token::StructEnd))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Compound<'r>, Error> {
+        let token = Token::StructVariant {
+            name: Cow::Borrowed(name),
+            variant_index,
+            variant: Cow::Borrowed(variant),
+            len,
+        };
+        Ok(self.start(token, Token::StructVariantEnd))
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.human_readable
+    }
+}
+
+struct Compound<'r> {
+    recorder: &'r mut Recorder,
+    end: Token<'static>,
+}
+
+impl Compound<'_> {
+    fn element<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        value.serialize(&mut *self.recorder)
+    }
+
+    fn field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        self.recorder.tokens.push(Token::Field(Cow::Borrowed(key)));
+        value.serialize(&mut *self.recorder)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.recorder.push(self.end)
+    }
+}
+
+impl ser::SerializeSeq for Compound<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        Compound::end(self)
+    }
+}
+
+impl ser::SerializeTuple for Compound<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        Compound::end(self)
+    }
+}
+
+impl ser::SerializeTupleStruct for Compound<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        Compound::end(self)
+    }
+}
+
+impl ser::SerializeTupleVariant for Compound<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        Compound::end(self)
+    }
+}
+
+impl ser::SerializeMap for Compound<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_key<T>(&mut self, key: &T) -> Result<(), Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        self.element(key)
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        Compound::end(self)
+    }
+}
+
+impl ser::SerializeStruct for Compound<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        self.field(key, value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        Compound::end(self)
+    }
+}
+
+impl ser::SerializeStructVariant for Compound<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        self.field(key, value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        Compound::end(self)
+    }
+}
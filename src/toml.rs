@@ -0,0 +1,211 @@
+use crate::Data;
+use crate::Error;
+use crate::Found;
+use crate::Number;
+use crate::Struct;
+use crate::Value;
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::string::ToString;
+use alloc::vec;
+use alloc::vec::Vec;
+use toml::map::Entry;
+use toml::value::Datetime;
+use toml::Table;
+use toml::Value as Toml;
+
+/// The name of the struct a TOML datetime is stored in.
+///
+/// This and [FIELD] are how `toml` itself serializes [Datetime], so a datetime serialized by
+/// [Serializer](crate::Serializer) converts into a TOML datetime, and a converted one
+/// deserializes back into a [Datetime].
+const DATETIME: &str = "$__toml_private_Datetime";
+/// The name of the only field of a [DATETIME] struct, holding the datetime as text.
+const FIELD: &str = "$__toml_private_datetime";
+
+impl TryFrom<Value<'_>> for Toml {
+    type Error = Error;
+
+    /// Converts a [Value] into TOML.
+    ///
+    /// TOML has no null, so map entries and struct fields that are `None` or the unit type are
+    /// left out, like `toml` does when serializing, and any other unit or `None` is an error.
+    /// Integers must fit in an `i64`. Characters become strings and byte arrays become arrays of
+    /// integers. Struct names are dropped, while enums are externally tagged by their variant.
+    /// Map keys must be strings or characters, and keys that convert into the same string are an
+    /// error.
+    ///
+    /// `toml::Value` has an inherent `try_from` that serializes instead, so use `try_into` to
+    /// convert with this.
+    fn try_from(value: Value<'_>) -> Result<Self, Self::Error> {
+        convert(value)
+    }
+}
+
+fn convert(value: Value<'_>) -> Result<Toml, Error> {
+    match value {
+        Value::Bool(v) => Ok(Toml::Boolean(v)),
+        Value::Number(v) => number(v),
+        Value::Char(v) => Ok(Toml::String(v.to_string())),
+        Value::String(v) => Ok(Toml::String(v.into_owned())),
+        Value::Bytes(v) => Ok(Toml::Array(
+            v.iter().map(|&byte| Toml::Integer(byte.into())).collect(),
+        )),
+        Value::Seq(v) | Value::Tuple(v) => array(v),
+        Value::Map(v) => {
+            let mut table = Table::new();
+            for (key, value) in v {
+                let key = match key {
+                    Value::String(v) => v.into_owned(),
+                    Value::Char(v) => v.to_string(),
+                    key => return Err(unrepresentable(key.into_found())),
+                };
+                insert(&mut table, key, value)?;
+            }
+            Ok(Toml::Table(table))
+        }
+        Value::Option(Some(v)) => convert(*v),
+        Value::Struct(v) if v.name == DATETIME => datetime(*v),
+        Value::Struct(v) if v.data == Data::Unit => {
+            Err(unrepresentable(Value::Struct(v).into_found()))
+        }
+        Value::Struct(v) => data(v.data),
+        Value::Enum(v) => match v.data {
+            Data::Unit => Ok(Toml::String(v.variant.into_owned())),
+            data => {
+                let mut table = Table::new();
+                table.insert(v.variant.into_owned(), self::data(data)?);
+                Ok(Toml::Table(table))
+            }
+        },
+        value @ (Value::Unit | Value::Option(None)) => Err(unrepresentable(value.into_found())),
+    }
+}
+
+fn number(number: Number) -> Result<Toml, Error> {
+    let integer = match number {
+        Number::I8(v) => Some(v.into()),
+        Number::U8(v) => Some(v.into()),
+        Number::I16(v) => Some(v.into()),
+        Number::U16(v) => Some(v.into()),
+        Number::I32(v) => Some(v.into()),
+        Number::U32(v) => Some(v.into()),
+        Number::I64(v) => Some(v),
+        Number::U64(v) => v.try_into().ok(),
+        Number::I128(v) => v.try_into().ok(),
+        Number::U128(v) => v.try_into().ok(),
+        Number::F32(v) => return Ok(Toml::Float(v.into())),
+        Number::F64(v) => return Ok(Toml::Float(v)),
+    };
+    integer
+        .map(Toml::Integer)
+        .ok_or_else(|| unrepresentable(Found::Number(number)))
+}
+
+fn array(values: Vec<Value<'_>>) -> Result<Toml, Error> {
+    values
+        .into_iter()
+        .map(convert)
+        .collect::<Result<_, _>>()
+        .map(Toml::Array)
+}
+
+/// Converts the data of a struct or enum variant, without its name.
+fn data(data: Data<'_>) -> Result<Toml, Error> {
+    match data {
+        Data::Unit => Err(unrepresentable(Found::Unit)),
+        Data::NewType { value } => convert(value),
+        Data::Tuple { values } => array(values),
+        Data::Struct { fields } => {
+            let mut table = Table::new();
+            for (key, value) in fields {
+                insert(&mut table, key.into_owned(), value)?;
+            }
+            Ok(Toml::Table(table))
+        }
+    }
+}
+
+fn datetime(datetime: Struct<'_>) -> Result<Toml, Error> {
+    if let Data::Struct { fields } = &datetime.data {
+        if let [(key, Value::String(v))] = fields.as_slice() {
+            if key == FIELD {
+                return v.parse().map(Toml::Datetime).map_err(|error| {
+                    Error::custom(format_args!("invalid TOML datetime: {error}"))
+                });
+            }
+        }
+    }
+    Err(unrepresentable(
+        Value::Struct(Box::new(datetime)).into_found(),
+    ))
+}
+
+/// Inserts an entry into a table, leaving it out if the value is null.
+///
+/// Different keys can convert into the same string, like `'a'` and `"a"`, which is an error
+/// rather than overwriting the entry.
+fn insert(table: &mut Table, key: String, value: Value<'_>) -> Result<(), Error> {
+    if is_null(&value) {
+        return Ok(());
+    }
+    match table.entry(key) {
+        Entry::Occupied(entry) => Err(Error::custom(format_args!(
+            "duplicate key `{}` in TOML table",
+            entry.key()
+        ))),
+        Entry::Vacant(entry) => {
+            entry.insert(convert(value)?);
+            Ok(())
+        }
+    }
+}
+
+fn is_null(value: &Value<'_>) -> bool {
+    matches!(value, Value::Unit | Value::Option(None))
+}
+
+fn unrepresentable(found: Found) -> Error {
+    Error::custom(format_args!("{found} can't be represented in TOML"))
+}
+
+impl From<Toml> for Value<'static> {
+    /// Converts TOML into a [Value].
+    ///
+    /// Integers become [Number::I64] and floats [Number::F64]. Datetimes become a struct named
+    /// `$__toml_private_Datetime` holding their text in a `$__toml_private_datetime` field, which
+    /// is how `toml` serializes them.
+    fn from(toml: Toml) -> Self {
+        match toml {
+            Toml::String(v) => Value::String(Cow::Owned(v)),
+            Toml::Integer(v) => Value::Number(Number::I64(v)),
+            Toml::Float(v) => Value::Number(Number::F64(v)),
+            Toml::Boolean(v) => Value::Bool(v),
+            Toml::Datetime(v) => datetime_struct(&v),
+            Toml::Array(v) => Value::Seq(v.into_iter().map(Value::from).collect()),
+            Toml::Table(v) => v.into(),
+        }
+    }
+}
+
+impl From<Table> for Value<'static> {
+    /// Converts a TOML table, like a whole TOML document, into a [Value::Map].
+    fn from(table: Table) -> Self {
+        Value::Map(
+            table
+                .into_iter()
+                .map(|(key, value)| (Value::String(Cow::Owned(key)), value.into()))
+                .collect(),
+        )
+    }
+}
+
+fn datetime_struct(datetime: &Datetime) -> Value<'static> {
+    let text = datetime.to_string();
+    Value::Struct(Box::new(Struct {
+        name: Cow::Borrowed(DATETIME),
+        data: Data::Struct {
+            fields: vec![(Cow::Borrowed(FIELD), Value::String(Cow::Owned(text)))],
+        },
+    }))
+}
@@ -0,0 +1,188 @@
+use crate::Data;
+use crate::Enum;
+use crate::Error;
+use crate::Found;
+use crate::Number;
+use crate::Value;
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use serde_yaml::mapping::Entry;
+use serde_yaml::value::Tag;
+use serde_yaml::value::TaggedValue;
+use serde_yaml::Mapping;
+use serde_yaml::Value as Yaml;
+
+impl TryFrom<Value<'_>> for Yaml {
+    type Error = Error;
+
+    /// Converts a [Value] into YAML, the way `serde_yaml` serializes the same data.
+    ///
+    /// The unit type and `None` become null. Integers must fit in an `i64` or a `u64`.
+    /// Characters become strings and byte arrays become sequences of integers. Struct names are
+    /// dropped. Unit enum variants become their name as a string, and other variants become
+    /// their data tagged with their name, like `!Circle 1.0`. Map keys that convert into the same
+    /// YAML value, like `1u8` and `1u64`, are an error.
+    fn try_from(value: Value<'_>) -> Result<Self, Self::Error> {
+        match value {
+            Value::Unit | Value::Option(None) => Ok(Yaml::Null),
+            Value::Bool(v) => Ok(Yaml::Bool(v)),
+            Value::Number(v) => number(v).map(Yaml::Number),
+            Value::Char(v) => Ok(Yaml::String(v.to_string())),
+            Value::String(v) => Ok(Yaml::String(v.into_owned())),
+            Value::Bytes(v) => Ok(Yaml::Sequence(v.iter().map(|&byte| byte.into()).collect())),
+            Value::Seq(v) | Value::Tuple(v) => sequence(v),
+            Value::Map(v) => mapping(
+                v.into_iter()
+                    .map(|(key, value)| Ok((key.try_into()?, value))),
+            ),
+            Value::Option(Some(v)) => Yaml::try_from(*v),
+            Value::Struct(v) => data(v.data),
+            Value::Enum(v) => match v.data {
+                Data::Unit => Ok(Yaml::String(v.variant.into_owned())),
+                data => Ok(Yaml::Tagged(Box::new(TaggedValue {
+                    tag: Tag::new(v.variant.into_owned()),
+                    value: self::data(data)?,
+                }))),
+            },
+        }
+    }
+}
+
+fn number(number: Number) -> Result<serde_yaml::Number, Error> {
+    match number {
+        Number::I8(v) => Ok(v.into()),
+        Number::U8(v) => Ok(v.into()),
+        Number::I16(v) => Ok(v.into()),
+        Number::U16(v) => Ok(v.into()),
+        Number::I32(v) => Ok(v.into()),
+        Number::U32(v) => Ok(v.into()),
+        Number::I64(v) => Ok(v.into()),
+        Number::U64(v) => Ok(v.into()),
+        Number::F32(v) => Ok(v.into()),
+        Number::F64(v) => Ok(v.into()),
+        Number::I128(v) => match (u64::try_from(v), i64::try_from(v)) {
+            (Ok(v), _) => Ok(v.into()),
+            (_, Ok(v)) => Ok(v.into()),
+            _ => Err(unrepresentable(Found::Number(number))),
+        },
+        Number::U128(v) => u64::try_from(v)
+            .map(Into::into)
+            .map_err(|_| unrepresentable(Found::Number(number))),
+    }
+}
+
+fn sequence(values: Vec<Value<'_>>) -> Result<Yaml, Error> {
+    values.into_iter().map(Yaml::try_from).collect()
+}
+
+/// Converts the data of a struct or enum variant, without its name.
+fn data(data: Data<'_>) -> Result<Yaml, Error> {
+    match data {
+        Data::Unit => Ok(Yaml::Null),
+        Data::NewType { value } => value.try_into(),
+        Data::Tuple { values } => sequence(values),
+        Data::Struct { fields } => mapping(
+            fields
+                .into_iter()
+                .map(|(key, value)| Ok((Yaml::String(key.into_owned()), value))),
+        ),
+    }
+}
+
+/// Converts the entries of a map or the fields of a struct into a mapping.
+///
+/// Different keys can convert into the same YAML value, like `'a'` and `"a"`, which is an error
+/// rather than overwriting the entry.
+fn mapping<'a>(
+    entries: impl Iterator<Item = Result<(Yaml, Value<'a>), Error>>,
+) -> Result<Yaml, Error> {
+    let mut mapping = Mapping::new();
+    for entry in entries {
+        let (key, value) = entry?;
+        match mapping.entry(key) {
+            Entry::Occupied(entry) => {
+                let key = Value::from(entry.key().clone()).into_found();
+                return Err(Error::custom(format_args!(
+                    "duplicate key {key} in YAML mapping"
+                )));
+            }
+            Entry::Vacant(entry) => entry.insert(value.try_into()?),
+        };
+    }
+    Ok(Yaml::Mapping(mapping))
+}
+
+fn unrepresentable(found: Found) -> Error {
+    Error::custom(format_args!("{found} can't be represented in YAML"))
+}
+
+impl From<Yaml> for Value<'static> {
+    /// Converts YAML into a [Value].
+    ///
+    /// Null becomes [Value::Unit], and numbers the first of [Number::U64], [Number::I64] and
+    /// [Number::F64] that holds them. Mappings keep keys of any type.
+    ///
+    /// A tagged value, like `!Circle 1.0`, becomes an enum variant named after the tag, as
+    /// `serde_yaml` deserializes enums. YAML has no enum names, so the name of the enum is
+    /// empty. The variant holds a tuple if the value is a sequence, fields if it is a mapping
+    /// with string keys, and any other value as a newtype.
+    fn from(yaml: Yaml) -> Self {
+        match yaml {
+            Yaml::Null => Value::Unit,
+            Yaml::Bool(v) => Value::Bool(v),
+            Yaml::Number(v) => Value::Number(v.into()),
+            Yaml::String(v) => Value::String(Cow::Owned(v)),
+            Yaml::Sequence(v) => Value::Seq(v.into_iter().map(Value::from).collect()),
+            Yaml::Mapping(v) => Value::Map(
+                v.into_iter()
+                    .map(|(key, value)| (key.into(), value.into()))
+                    .collect(),
+            ),
+            Yaml::Tagged(v) => {
+                let tag = v.tag.to_string();
+                let variant = tag.strip_prefix('!').unwrap_or(&tag).to_string();
+                Value::Enum(Box::new(Enum {
+                    name: Cow::Borrowed(""),
+                    variant_index: 0,
+                    variant: Cow::Owned(variant),
+                    data: tagged_data(v.value),
+                }))
+            }
+        }
+    }
+}
+
+fn tagged_data(value: Yaml) -> Data<'static> {
+    match value {
+        Yaml::Sequence(v) => Data::Tuple {
+            values: v.into_iter().map(Value::from).collect(),
+        },
+        Yaml::Mapping(v) if v.keys().all(Yaml::is_string) => Data::Struct {
+            fields: v
+                .into_iter()
+                .filter_map(|(key, value)| match key {
+                    Yaml::String(key) => Some((Cow::Owned(key), value.into())),
+                    _ => None,
+                })
+                .collect(),
+        },
+        value => Data::NewType {
+            value: value.into(),
+        },
+    }
+}
+
+impl From<serde_yaml::Number> for Number {
+    /// Converts a YAML number into the first of `U64`, `I64` and `F64` that holds it.
+    fn from(number: serde_yaml::Number) -> Self {
+        if let Some(v) = number.as_u64() {
+            Number::U64(v)
+        } else if let Some(v) = number.as_i64() {
+            Number::I64(v)
+        } else {
+            Number::F64(number.as_f64().unwrap_or(f64::NAN))
+        }
+    }
+}
@@ -0,0 +1,112 @@
+#![cfg(all(feature = "derive", feature = "arbitrary"))]
+
+extern crate alloc;
+
+use alloc::borrow::Cow;
+use arbitrary::Arbitrary;
+use arbitrary::Unstructured;
+use serde_content::Data;
+use serde_content::Deserializer;
+use serde_content::Number;
+use serde_content::Roundtrip;
+use serde_content::Serializer;
+use serde_content::Value;
+use std::collections::BTreeSet;
+use std::collections::HashSet;
+
+// Pseudo-random input for `Unstructured`, the same for every run.
+fn inputs() -> impl Iterator<Item = Vec<u8>> {
+    let mut state = 0x2545_f491_4f6c_dd1d_u64;
+    (0..500).map(move |i| {
+        (0..i * 4)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                state as u8
+            })
+            .collect()
+    })
+}
+
+fn depth(value: &Value) -> usize {
+    let data = |data: &Data| match data {
+        Data::Unit => 0,
+        Data::NewType { value } => depth(value),
+        Data::Tuple { values } => values.iter().map(depth).max().unwrap_or(0),
+        Data::Struct { fields } => fields.iter().map(|(_, v)| depth(v)).max().unwrap_or(0),
+    };
+    1 + match value {
+        Value::Seq(v) | Value::Tuple(v) => v.iter().map(depth).max().unwrap_or(0),
+        Value::Map(v) => v
+            .iter()
+            .map(|(key, value)| depth(key).max(depth(value)))
+            .max()
+            .unwrap_or(0),
+        Value::Option(Some(v)) => depth(v),
+        Value::Struct(v) => data(&v.data),
+        Value::Enum(v) => data(&v.data),
+        _ => 0,
+    }
+}
+
+fn kind(value: &Value) -> &'static str {
+    match value {
+        Value::Unit => "unit",
+        Value::Bool(_) => "bool",
+        Value::Number(_) => "number",
+        Value::Char(_) => "char",
+        Value::String(_) => "string",
+        Value::Bytes(_) => "bytes",
+        Value::Seq(_) => "seq",
+        Value::Map(_) => "map",
+        Value::Option(_) => "option",
+        Value::Struct(_) => "struct",
+        Value::Enum(_) => "enum",
+        Value::Tuple(_) => "tuple",
+    }
+}
+
+#[test]
+fn values() {
+    let mut kinds = BTreeSet::new();
+    for input in inputs() {
+        let value = Value::arbitrary(&mut Unstructured::new(&input)).unwrap();
+        assert!(depth(&value) <= 4, "{value:?}");
+        kinds.insert(kind(&value));
+        // Names are `'static`, so the value can be serialized.
+        Serializer::new().serialize(&value).unwrap();
+    }
+    assert_eq!(kinds.len(), 12, "{kinds:?}");
+}
+
+#[test]
+fn numbers() {
+    let mut kinds = HashSet::new();
+    for input in inputs() {
+        let number = Number::arbitrary(&mut Unstructured::new(&input)).unwrap();
+        kinds.insert(core::mem::discriminant(&number));
+    }
+    assert_eq!(kinds.len(), 12);
+}
+
+#[test]
+fn borrowed() {
+    let input = [4, 3, b'a', b'b', b'c'];
+    let value = Value::arbitrary(&mut Unstructured::new(&input)).unwrap();
+    assert!(
+        matches!(value, Value::String(Cow::Borrowed(_))),
+        "{value:?}"
+    );
+}
+
+#[test]
+fn roundtrip() {
+    for input in inputs() {
+        let Roundtrip(value) = Roundtrip::arbitrary(&mut Unstructured::new(&input)).unwrap();
+        assert!(depth(&value) <= 4, "{value:?}");
+        assert_eq!(Serializer::new().serialize(&value).unwrap(), value);
+        let found: Value = Deserializer::new(value.clone()).deserialize().unwrap();
+        assert_eq!(found, value);
+    }
+}
@@ -0,0 +1,221 @@
+#![cfg(all(feature = "derive", feature = "arena"))]
+
+extern crate alloc;
+
+use alloc::borrow::Cow;
+use alloc::collections::BTreeMap;
+use serde::de::DeserializeSeed;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_content::arena;
+use serde_content::arena::ArenaData;
+use serde_content::arena::ArenaEnum;
+use serde_content::arena::ArenaValue;
+use serde_content::arena::Bump;
+use serde_content::value;
+use serde_content::Data;
+use serde_content::Deserializer;
+use serde_content::Serializer;
+use serde_content::Struct;
+use serde_content::Value;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Unit;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Newtype(u8);
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Pair(i32, String);
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+enum Shape {
+    Empty,
+    Circle(f64),
+    Line(i32, i32),
+    Rect { width: u32, height: u32 },
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Record<'a> {
+    name: &'a str,
+    #[serde(with = "serde_bytes_borrowed")]
+    data: &'a [u8],
+    tags: Vec<String>,
+    labels: BTreeMap<String, u64>,
+    unit: Unit,
+    newtype: Newtype,
+    pair: Pair,
+    shapes: Vec<Shape>,
+    nothing: Option<char>,
+    something: Option<Option<bool>>,
+    big: (i128, u128),
+}
+
+// Borrows byte arrays like `serde_bytes` does.
+mod serde_bytes_borrowed {
+    pub fn serialize<S>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_bytes(bytes)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<&'de [u8], D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        serde::Deserialize::deserialize(deserializer)
+    }
+}
+
+fn record() -> Record<'static> {
+    Record {
+        name: "record",
+        data: &[1, 2, 3],
+        tags: vec!["a".into(), "b".into()],
+        labels: BTreeMap::from([("x".into(), 1), ("y".into(), 2)]),
+        unit: Unit,
+        newtype: Newtype(7),
+        pair: Pair(-1, "pair".into()),
+        shapes: vec![
+            Shape::Empty,
+            Shape::Circle(1.5),
+            Shape::Line(1, 2),
+            Shape::Rect {
+                width: 3,
+                height: 4,
+            },
+        ],
+        nothing: None,
+        something: Some(Some(true)),
+        big: (i128::MIN, u128::MAX),
+    }
+}
+
+#[test]
+fn serialize() {
+    let arena = Bump::new();
+    let value = arena::Serializer::new(&arena).serialize(record()).unwrap();
+    assert_eq!(
+        value.to_value(),
+        Serializer::new().serialize(record()).unwrap()
+    );
+}
+
+#[test]
+fn roundtrip() {
+    let arena = Bump::new();
+    let value = arena::Serializer::new(&arena).serialize(record()).unwrap();
+    let found: Record = arena::Deserializer::new(value).deserialize().unwrap();
+    assert_eq!(found, record());
+
+    // Strings and byte arrays are borrowed from the arena.
+    let ArenaValue::Struct(st) = value else {
+        panic!("expected a struct");
+    };
+    let ArenaData::Struct { fields } = st.data else {
+        panic!("expected an object-like struct");
+    };
+    let ArenaValue::String(name) = fields[0].1 else {
+        panic!("expected a string");
+    };
+    assert!(core::ptr::eq(found.name, name));
+}
+
+#[test]
+fn unit_variant_by_name() {
+    let arena = Bump::new();
+    let shape: Shape = arena::Deserializer::new(ArenaValue::String("Empty"))
+        .deserialize()
+        .unwrap();
+    assert_eq!(shape, Shape::Empty);
+
+    let value = arena::Serializer::new(&arena)
+        .serialize(Shape::Empty)
+        .unwrap();
+    let shape: Shape = arena::Deserializer::new(value).deserialize().unwrap();
+    assert_eq!(shape, Shape::Empty);
+
+    // The data has to match the variant.
+    let value = ArenaValue::Enum(&ArenaEnum {
+        name: "Shape",
+        variant_index: 0,
+        variant: "Empty",
+        data: ArenaData::NewType {
+            value: ArenaValue::Unit,
+        },
+    });
+    let error = arena::Deserializer::new(value)
+        .deserialize::<Shape>()
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "invalid type: newtype variant, expected unit variant"
+    );
+}
+
+#[test]
+fn values() {
+    let arena = Bump::new();
+    let value = value!({
+        "point": Point { x: 1i32, y: 2i32 },
+        "shape": Shape::Circle @ 1 ((3.0f64)),
+        "bytes": (Value::Bytes(Cow::Borrowed(&[1, 2]))),
+        "tuple": ('c', ()),
+        "option": Some(None),
+    });
+    let copy = ArenaValue::from_value(&arena, &value).unwrap();
+    assert_eq!(copy.to_value(), value);
+
+    // Owned names are converted like the serializer converts them.
+    let value = Value::Struct(Box::new(Struct {
+        name: Cow::Owned("Point".into()),
+        data: Data::Tuple {
+            values: vec![value!(1u8)],
+        },
+    }));
+    let copy = ArenaValue::from_value(&arena, &value).unwrap();
+    assert_eq!(
+        copy.to_value(),
+        Serializer::new().serialize(&value).unwrap()
+    );
+}
+
+#[test]
+fn visitor() {
+    let arena = Bump::new();
+    let value = value!({
+        "name": "record",
+        "data": (Value::Bytes(Cow::Borrowed(&[1, 2]))),
+        "tags": ["a", "b"],
+        "pair": Pair(-1i32, 'c'),
+        "option": Some(None),
+        "big": ((i128::MIN), (u128::MAX)),
+    });
+    let found = arena::ValueVisitor::new(&arena)
+        .deserialize(Deserializer::new(value.clone()))
+        .unwrap();
+    let expected: Value = Deserializer::new(value).deserialize().unwrap();
+    assert_eq!(found.to_value(), expected);
+
+    // Arena values serialize like the values they stand for.
+    let value = arena::Serializer::new(&arena).serialize(record()).unwrap();
+    assert_eq!(
+        Serializer::new().serialize(value).unwrap(),
+        Serializer::new().serialize(record()).unwrap()
+    );
+}
+
+#[test]
+fn reuse() {
+    let mut arena = Bump::new();
+    for i in 0..100u32 {
+        let value = arena::Serializer::new(&arena)
+            .serialize((i, vec![i; 8], format!("{i}")))
+            .unwrap();
+        let found: (u32, Vec<u32>, &str) = arena::Deserializer::new(value).deserialize().unwrap();
+        assert_eq!(found, (i, vec![i; 8], &*format!("{i}")));
+        arena.reset();
+    }
+}
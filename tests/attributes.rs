@@ -67,5 +67,5 @@ fn untagged() {
     let value = Serializer::new().serialize(&foo).unwrap();
     let deserializer = Deserializer::new(value);
     assert_eq!(foo, deserializer.clone().deserialize().unwrap());
-    assert_eq!(bar, deserializer.deserialize().unwrap());
+    assert_eq!(bar, deserializer.deserialize::<usize>().unwrap());
 }
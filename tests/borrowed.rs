@@ -0,0 +1,165 @@
+#![cfg(feature = "derive")]
+
+extern crate alloc;
+
+use alloc::borrow::Cow;
+use alloc::collections::BTreeMap;
+use serde_content::value;
+use serde_content::Data;
+use serde_content::ErrorKind;
+use serde_content::Limit;
+use serde_content::Limits;
+use serde_content::Redactor;
+use serde_content::Replacement;
+use serde_content::Serializer;
+use serde_content::Struct;
+use serde_content::Value;
+
+fn source() -> Value<'static> {
+    value!({
+        "user": User { name: (String::from("alice")), password: (String::from("s3cret")) },
+        "tags": [(String::from("a")), (String::from("b"))],
+        "avatar": (vec![1u8, 2, 3]),
+        "shape": Shape::Circle @ 1 ((String::from("red")), 3.0f64),
+        "nothing": None,
+        "pair": ((String::from("x")), Some((String::from("y")))),
+        "status": Status::Active @ 0,
+    })
+}
+
+// Checks that every string and byte array in `value` is borrowed.
+fn assert_borrowed(value: &Value) {
+    let data = |data: &Data| match data {
+        Data::Unit => {}
+        Data::NewType { value } => assert_borrowed(value),
+        Data::Tuple { values } => values.iter().for_each(assert_borrowed),
+        Data::Struct { fields } => fields.iter().for_each(|(_, v)| assert_borrowed(v)),
+    };
+    match value {
+        Value::String(v) => assert!(matches!(v, Cow::Borrowed(_)), "{v:?}"),
+        Value::Bytes(v) => assert!(matches!(v, Cow::Borrowed(_)), "{v:?}"),
+        Value::Seq(v) | Value::Tuple(v) => v.iter().for_each(assert_borrowed),
+        Value::Map(v) => v.iter().for_each(|(key, value)| {
+            assert_borrowed(key);
+            assert_borrowed(value);
+        }),
+        Value::Option(Some(v)) => assert_borrowed(v),
+        Value::Struct(v) => data(&v.data),
+        Value::Enum(v) => data(&v.data),
+        _ => {}
+    }
+}
+
+#[test]
+fn value() {
+    let source = source();
+    let value = Serializer::new().serialize_borrowed(&source).unwrap();
+    assert_eq!(value, Serializer::new().serialize(&source).unwrap());
+    assert_borrowed(&value);
+
+    // The strings point into the source.
+    let (Value::Map(source), Value::Map(value)) = (&source, &value) else {
+        panic!("expected maps");
+    };
+    let (Value::String(key), Value::String(found)) = (&source[0].0, &value[0].0) else {
+        panic!("expected strings");
+    };
+    assert_eq!(key.as_ptr(), found.as_ptr());
+}
+
+#[test]
+fn collections() {
+    let strings = vec![String::from("a"), String::from("b")];
+    let value = Serializer::new().serialize_borrowed(&strings).unwrap();
+    assert_eq!(value, Serializer::new().serialize(&strings).unwrap());
+    assert_borrowed(&value);
+
+    let mut map = BTreeMap::new();
+    map.insert(String::from("key"), vec![Some("value"), None]);
+    let value = Serializer::new().serialize_borrowed(&map).unwrap();
+    assert_eq!(value, Serializer::new().serialize(&map).unwrap());
+    assert_borrowed(&value);
+
+    let array = [Cow::Borrowed("a"), Cow::Owned(String::from("b"))];
+    let value = Serializer::new().serialize_borrowed(&array).unwrap();
+    assert_eq!(value, Serializer::new().serialize(array.clone()).unwrap());
+    assert_borrowed(&value);
+
+    let bytes: Cow<[u8]> = Cow::Owned(vec![1, 2, 3]);
+    let value = Serializer::new().serialize_borrowed(&bytes).unwrap();
+    assert_eq!(value, Value::Bytes(Cow::Borrowed(&[1, 2, 3])));
+
+    let number = Serializer::new().serialize_borrowed(&1u8).unwrap();
+    assert_eq!(number, value!(1u8));
+}
+
+#[test]
+fn owned_names() {
+    // Owned names fall back to the owned representation `serialize` uses.
+    let source = Value::Struct(Box::new(Struct {
+        name: Cow::Owned(String::from("Point")),
+        data: Data::Struct {
+            fields: vec![(Cow::Borrowed("x"), value!(1i32))],
+        },
+    }));
+    let value = Serializer::new().serialize_borrowed(&source).unwrap();
+    assert_eq!(value, Serializer::new().serialize(&source).unwrap());
+    assert_eq!(value, value!({ "x": 1i32 }));
+
+    let source = Value::Struct(Box::new(Struct {
+        name: Cow::Borrowed("Point"),
+        data: Data::Struct {
+            fields: vec![(Cow::Owned(String::from("x")), value!(1i32))],
+        },
+    }));
+    let error = Serializer::new().serialize_borrowed(&source).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        Serializer::new()
+            .serialize(&source)
+            .unwrap_err()
+            .to_string()
+    );
+}
+
+#[test]
+fn redact() {
+    let source = source();
+    let redactors = [
+        Redactor::new().field("User", "password", Replacement::Marker),
+        Redactor::new().path("tags[*]", Replacement::Mask),
+        Redactor::new().path("shape.Circle[0]", Replacement::Hash),
+        Redactor::new().key("avatar", Replacement::Marker),
+    ];
+    for redactor in &redactors {
+        let serializer = Serializer::new().redact(redactor);
+        let value = serializer.serialize_borrowed(&source).unwrap();
+        assert_eq!(value, serializer.serialize(&source).unwrap());
+        assert_ne!(value, source);
+    }
+}
+
+#[test]
+fn limits() {
+    let source = source();
+    let limits = [
+        (Limits::new().max_depth(2), Limit::Depth(2)),
+        (Limits::new().max_elements(10), Limit::Elements(10)),
+        (Limits::new().max_string_len(5), Limit::StringLength(5)),
+        (Limits::new().max_total_bytes(20), Limit::TotalBytes(20)),
+    ];
+    for (limits, limit) in &limits {
+        let serializer = Serializer::new().limits(limits);
+        let error = serializer.serialize_borrowed(&source).unwrap_err();
+        assert_eq!(error.kind(), &ErrorKind::LimitExceeded(*limit));
+        let error = serializer.serialize(&source).unwrap_err();
+        assert_eq!(error.kind(), &ErrorKind::LimitExceeded(*limit));
+    }
+
+    // Limits are reset for each value.
+    let limits = Limits::new().max_elements(3);
+    let serializer = Serializer::new().limits(&limits);
+    for _ in 0..3 {
+        assert!(serializer.serialize_borrowed(&["a", "b", "c"]).is_ok());
+    }
+}
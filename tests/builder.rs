@@ -0,0 +1,135 @@
+#![cfg(feature = "derive")]
+
+extern crate alloc;
+
+use serde::Deserialize;
+use serde::Serialize;
+use serde_content::Data;
+use serde_content::Deserializer;
+use serde_content::Enum;
+use serde_content::ErrorKind;
+use serde_content::Serializer;
+use serde_content::Struct;
+use serde_content::Value;
+
+#[test]
+fn structs() {
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Unit;
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct NewType(i32);
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Tuple(i32, String);
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    let value = Value::from(Struct::unit("Unit"));
+    assert_eq!(value, Serializer::new().serialize(Unit).unwrap());
+    let unit: Unit = Deserializer::new(value).deserialize().unwrap();
+    assert_eq!(unit, Unit);
+
+    let value = Value::from(Struct::newtype("NewType", 1i32));
+    assert_eq!(value, Serializer::new().serialize(NewType(1)).unwrap());
+
+    let value = Value::from(Struct::tuple("Tuple").field(1i32).field("a").build());
+    assert_eq!(
+        value,
+        Serializer::new().serialize(Tuple(1, "a".into())).unwrap()
+    );
+
+    let point = Struct::object("Point")
+        .field("x", 1i32)
+        .field("y", 2i32)
+        .build()
+        .unwrap();
+    let value = Value::from(point);
+    assert_eq!(
+        value,
+        Serializer::new().serialize(Point { x: 1, y: 2 }).unwrap()
+    );
+    let point: Point = Deserializer::new(value).deserialize().unwrap();
+    assert_eq!(point, Point { x: 1, y: 2 });
+}
+
+#[test]
+fn enums() {
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    enum Shape {
+        Empty,
+        Circle(f64),
+        Line(i32, i32),
+        Rect { w: u8, h: u8 },
+    }
+
+    const VARIANTS: &[&str] = &["Empty", "Circle", "Line", "Rect"];
+
+    let value = Value::from(Enum::new("Shape", 0, "Empty"));
+    assert_eq!(value, Serializer::new().serialize(Shape::Empty).unwrap());
+
+    let value = Value::from(Enum::new("Shape", 1, "Circle").newtype(3.0f64));
+    assert_eq!(
+        value,
+        Serializer::new().serialize(Shape::Circle(3.0)).unwrap()
+    );
+
+    let line = Enum::from_variants("Shape", VARIANTS, "Line").unwrap();
+    let value = Value::from(line.tuple().field(1i32).field(2i32).build());
+    assert_eq!(
+        value,
+        Serializer::new().serialize(Shape::Line(1, 2)).unwrap()
+    );
+
+    let rect = Enum::from_variants("Shape", VARIANTS, "Rect").unwrap();
+    let value = Value::from(
+        rect.object()
+            .field("w", 1u8)
+            .field("h", 2u8)
+            .build()
+            .unwrap(),
+    );
+    assert_eq!(
+        value,
+        Serializer::new()
+            .serialize(Shape::Rect { w: 1, h: 2 })
+            .unwrap()
+    );
+    let rect: Shape = Deserializer::new(value).deserialize().unwrap();
+    assert_eq!(rect, Shape::Rect { w: 1, h: 2 });
+
+    assert!(Enum::from_variants("Shape", VARIANTS, "Square").is_err());
+}
+
+#[test]
+fn data() {
+    assert_eq!(
+        Data::newtype(true),
+        Data::NewType {
+            value: Value::Bool(true)
+        }
+    );
+    assert_eq!(Data::tuple().build(), Data::Tuple { values: Vec::new() });
+    assert_eq!(
+        Data::object().field("a", 'a').build().unwrap(),
+        Data::Struct {
+            fields: vec![("a".into(), Value::Char('a'))]
+        }
+    );
+}
+
+#[test]
+fn duplicate_fields() {
+    let error = Struct::object("Point")
+        .field("x", 1i32)
+        .field("y", 2i32)
+        .field("x", 3i32)
+        .build()
+        .unwrap_err();
+    assert_eq!(error.kind(), &ErrorKind::DuplicateField("x".into()));
+    assert_eq!(error.to_string(), "duplicate field `x`");
+}
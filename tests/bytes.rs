@@ -0,0 +1,155 @@
+#![cfg(feature = "derive")]
+
+extern crate alloc;
+
+use core::fmt;
+use serde::de;
+use serde::Deserialize;
+use serde_content::value;
+use serde_content::BytesEncoding;
+use serde_content::Deserializer;
+use serde_content::Error;
+use serde_content::Expected;
+use serde_content::Found;
+use serde_content::Number;
+use serde_content::Value;
+
+/// Deserializes via `deserialize_byte_buf`, like `serde_bytes::ByteBuf`.
+#[derive(Debug, PartialEq)]
+struct ByteBuf(Vec<u8>);
+
+impl<'de> Deserialize<'de> for ByteBuf {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct Visitor;
+
+        impl de::Visitor<'_> for Visitor {
+            type Value = ByteBuf;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a byte array")
+            }
+
+            fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Ok(ByteBuf(v.to_vec()))
+            }
+        }
+
+        deserializer.deserialize_byte_buf(Visitor)
+    }
+}
+
+#[test]
+fn bytes_from_seq() {
+    let value = value!([1u64, 2u64, 255u64]);
+    assert_eq!(
+        Deserializer::new(value.clone()).deserialize::<ByteBuf>(),
+        Err(Error::unexpected(
+            Found::Seq(vec![
+                Found::Number(Number::U64(1)),
+                Found::Number(Number::U64(2)),
+                Found::Number(Number::U64(255)),
+            ]),
+            Expected::Bytes
+        ))
+    );
+    let bytes = Deserializer::new(value).coerce_bytes().deserialize();
+    assert_eq!(bytes, Ok(ByteBuf(vec![1, 2, 255])));
+
+    let value = value!([1i32, 256i32]);
+    let bytes = Deserializer::new(value)
+        .coerce_bytes()
+        .deserialize::<ByteBuf>();
+    assert_eq!(
+        bytes,
+        Err(Error::unexpected(
+            Found::Seq(vec![
+                Found::Number(Number::I32(1)),
+                Found::Number(Number::I32(256)),
+            ]),
+            Expected::Bytes
+        ))
+    );
+    let value = value!([-1i8]);
+    let bytes = Deserializer::new(value)
+        .coerce_bytes()
+        .deserialize::<ByteBuf>();
+    assert!(bytes.is_err());
+}
+
+#[test]
+fn seq_from_bytes() {
+    let value = Value::from(vec![1u8, 2, 3]);
+    assert!(Deserializer::new(value.clone())
+        .deserialize::<Vec<u8>>()
+        .is_err());
+    let vec = Deserializer::new(value.clone())
+        .coerce_bytes()
+        .deserialize::<Vec<u8>>();
+    assert_eq!(vec, Ok(vec![1, 2, 3]));
+    let array = Deserializer::new(value.clone())
+        .coerce_bytes()
+        .deserialize::<[u8; 3]>();
+    assert_eq!(array, Ok([1, 2, 3]));
+    let array = Deserializer::new(value)
+        .coerce_bytes()
+        .strict()
+        .deserialize::<[u8; 2]>();
+    assert!(array.is_err());
+}
+
+#[test]
+fn encoded_strings() {
+    let decode = |text: &'static str, encoding| {
+        Deserializer::new(value!((text)))
+            .human_readable()
+            .bytes_encoding(encoding)
+            .deserialize::<ByteBuf>()
+    };
+    assert_eq!(
+        decode("aGVsbG8=", BytesEncoding::Base64),
+        Ok(ByteBuf(b"hello".to_vec()))
+    );
+    assert_eq!(
+        decode("aGVsbG8", BytesEncoding::Base64),
+        Ok(ByteBuf(b"hello".to_vec()))
+    );
+    assert_eq!(
+        decode("+/8=", BytesEncoding::Base64),
+        Ok(ByteBuf(vec![0xfb, 0xff]))
+    );
+    assert_eq!(
+        decode("-_8", BytesEncoding::Base64Url),
+        Ok(ByteBuf(vec![0xfb, 0xff]))
+    );
+    assert_eq!(decode("", BytesEncoding::Base64), Ok(ByteBuf(vec![])));
+    assert_eq!(
+        decode("00fFaB", BytesEncoding::Hex),
+        Ok(ByteBuf(vec![0x00, 0xff, 0xab]))
+    );
+    assert_eq!(
+        decode("abc", BytesEncoding::Hex),
+        Err(Error::unexpected(
+            Found::String("abc".into()),
+            Expected::Bytes
+        ))
+    );
+    assert!(decode("a$==", BytesEncoding::Base64).is_err());
+    assert!(decode("abcde", BytesEncoding::Base64).is_err());
+
+    // Strings are only decoded in human-readable form.
+    let bytes = Deserializer::new(value!("aGVsbG8="))
+        .bytes_encoding(BytesEncoding::Base64)
+        .deserialize::<ByteBuf>();
+    assert!(bytes.is_err());
+    // And only with an encoding.
+    let bytes = Deserializer::new(value!("aGVsbG8="))
+        .human_readable()
+        .deserialize::<ByteBuf>();
+    assert!(bytes.is_err());
+}
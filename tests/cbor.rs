@@ -0,0 +1,76 @@
+#![cfg(all(feature = "derive", feature = "cbor"))]
+
+extern crate alloc;
+
+use ciborium::tag::Captured;
+use ciborium::Value as Cbor;
+use serde_content::value;
+use serde_content::Deserializer;
+use serde_content::Number;
+use serde_content::Serializer;
+use serde_content::Value;
+
+#[test]
+fn native() {
+    let value = value!({
+        (&b"key"[..]): (&b"\x00\xff"[..]),
+        [1u8, 2u8]: (),
+        (-1i128): (u128::from(u64::MAX)),
+    });
+    let cbor = Cbor::from(value.clone());
+    assert_eq!(
+        cbor,
+        Cbor::Map(vec![
+            (Cbor::Bytes(b"key".to_vec()), Cbor::Bytes(vec![0, 255])),
+            (Cbor::Array(vec![1.into(), 2.into()]), Cbor::Null),
+            (Cbor::from(-1), Cbor::from(u64::MAX)),
+        ])
+    );
+    let value = Value::try_from(cbor).unwrap();
+    assert_eq!(
+        value,
+        value!({
+            (&b"key"[..]): (&b"\x00\xff"[..]),
+            [1u64, 2u64]: (),
+            (-1i64): (u64::MAX),
+        })
+    );
+}
+
+#[test]
+fn bignums() {
+    for number in [
+        Number::U128(u128::MAX),
+        Number::U128(u128::from(u64::MAX) + 1),
+        Number::I128(i128::MIN),
+        Number::I128(-(1 << 64) - 1),
+    ] {
+        let cbor = Cbor::from(Value::Number(number));
+        assert!(matches!(cbor, Cbor::Tag(2 | 3, _)), "{cbor:?}");
+        assert_eq!(Value::try_from(cbor).unwrap(), Value::Number(number));
+    }
+
+    // The same bytes as `ciborium` writes for a `u128`.
+    let mut bytes = Vec::new();
+    ciborium::into_writer(&(u128::from(u64::MAX) + 1), &mut bytes).unwrap();
+    let cbor: Cbor = ciborium::from_reader(&bytes[..]).unwrap();
+    assert_eq!(cbor, Cbor::from(value!((u128::from(u64::MAX) + 1))));
+}
+
+#[test]
+fn tags() {
+    let cbor = Cbor::Tag(32, Box::new(Cbor::Text("https://example.com".into())));
+    let value = Value::try_from(cbor.clone()).unwrap();
+    let Value::Enum(tagged) = &value else {
+        panic!("expected an enum, found {value:?}");
+    };
+    assert_eq!(tagged.name, "@@TAG@@");
+    assert_eq!(tagged.variant, "@@TAGGED@@");
+    assert_eq!(Cbor::from(value.clone()), cbor);
+
+    // Tags round trip through `Captured`.
+    let captured: Captured<String> = Deserializer::new(value).deserialize().unwrap();
+    assert_eq!(captured, Captured(Some(32), "https://example.com".into()));
+    let value = Serializer::new().serialize(&captured).unwrap();
+    assert_eq!(Cbor::from(value), cbor);
+}
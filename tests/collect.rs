@@ -0,0 +1,259 @@
+#![cfg(feature = "derive")]
+
+extern crate alloc;
+
+use serde::Deserialize;
+use serde_content::value;
+use serde_content::Deserializer;
+use serde_content::Error;
+use serde_content::ErrorKind;
+use serde_content::Expected;
+use serde_content::Found;
+use serde_content::Number;
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Row {
+    id: u32,
+    name: String,
+    active: bool,
+    tags: Vec<String>,
+}
+
+#[test]
+fn no_errors() {
+    let value = value!([Row {
+        id: 1u32,
+        name: "a",
+        active: true,
+        tags: ["x"]
+    }]);
+    let rows = Deserializer::new(value).deserialize_all::<Vec<Row>>();
+    assert_eq!(
+        rows,
+        Ok(vec![Row {
+            id: 1,
+            name: "a".into(),
+            active: true,
+            tags: vec!["x".into()],
+        }])
+    );
+}
+
+#[test]
+fn all_errors() {
+    let value = value!([
+        Row {
+            id: 1u32,
+            name: "a",
+            active: true,
+            tags: []
+        },
+        Row {
+            id: "2",
+            name: "b",
+            active: true,
+            tags: ["x", 3u8]
+        },
+        Row {
+            id: 3u32,
+            name: 'c',
+            active: "yes",
+            tags: []
+        },
+    ]);
+    let errors = Deserializer::new(value)
+        .deserialize_all::<Vec<Row>>()
+        .unwrap_err();
+    assert_eq!(
+        errors.clone().into_vec(),
+        vec![
+            (
+                "[1].id".to_owned(),
+                Error::unexpected(Found::String("2".into()), Expected::U32)
+            ),
+            (
+                "[1].tags[1]".to_owned(),
+                Error::unexpected(Found::Number(Number::U8(3)), Expected::String)
+            ),
+            (
+                "[2].name".to_owned(),
+                Error::unexpected(Found::Char('c'), Expected::String)
+            ),
+            (
+                "[2].active".to_owned(),
+                Error::unexpected(Found::String("yes".into()), Expected::Bool)
+            ),
+        ]
+    );
+    assert_eq!(
+        errors.to_string(),
+        "[1].id: failed to deserialize; expected a 32-bit unsigned integer, found \"2\"\n\
+         [1].tags[1]: failed to deserialize; expected a string, found 3u8\n\
+         [2].name: failed to deserialize; expected a string, found 'c'\n\
+         [2].active: failed to deserialize; expected a boolean, found \"yes\""
+    );
+}
+
+#[test]
+fn defaults() {
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Outer {
+        row: Row,
+        pair: (u8, char),
+        count: u8,
+    }
+
+    let value = value!(Outer {
+        row: 1u8,
+        pair: "x",
+        count: 300u16
+    });
+    let errors = Deserializer::new(value.clone())
+        .deserialize_all::<Outer>()
+        .unwrap_err();
+    let paths = errors
+        .iter()
+        .map(|(path, _)| path.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(paths, ["row", "pair", "count"]);
+
+    let errors = Deserializer::new(value)
+        .coerce_numbers()
+        .deserialize_all::<Outer>()
+        .unwrap_err();
+    assert_eq!(errors.len(), 3);
+}
+
+#[test]
+fn fatal_errors() {
+    let value = value!([
+        Row {
+            id: true,
+            name: "a",
+            active: true,
+            tags: []
+        },
+        Row { id: 2u32 },
+    ]);
+    let errors = Deserializer::new(value)
+        .deserialize_all::<Vec<Row>>()
+        .unwrap_err()
+        .into_vec();
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0].0, "[0].id");
+    assert_eq!(errors[1].0, "[1]");
+    assert_eq!(
+        errors[1].1.kind(),
+        &ErrorKind::MissingField {
+            struct_name: Some("Row".into()),
+            field: "name".into(),
+        }
+    );
+}
+
+#[test]
+fn strict_unknown_fields() {
+    let value = value!(Row {
+        id: 1u32,
+        name: "a",
+        active: true,
+        tags: [],
+        extra: 1u8
+    });
+    let errors = Deserializer::new(value)
+        .strict()
+        .deserialize_all::<Row>()
+        .unwrap_err()
+        .into_vec();
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        errors[0].1.kind(),
+        ErrorKind::UnknownField { field, .. } if field == "extra"
+    ));
+}
+
+#[test]
+fn borrowed() {
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Borrowed<'a> {
+        name: &'a str,
+        id: u32,
+    }
+
+    let value = value!({ "name": "alice", "id": 1u32 });
+    let borrowed = Deserializer::new(value).deserialize_all::<Borrowed>();
+    assert_eq!(
+        borrowed,
+        Ok(Borrowed {
+            name: "alice",
+            id: 1
+        })
+    );
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+enum Expr {
+    Add(Box<Expr>, Box<Expr>),
+    Neg { expr: Box<Expr> },
+    Lit(i32),
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+enum List {
+    Cons(u8, Box<List>),
+    Nil,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+enum Endless {
+    More(Box<Endless>),
+}
+
+std::thread_local! {
+    static SEEN: core::cell::RefCell<String> = const { core::cell::RefCell::new(String::new()) };
+}
+
+/// Notes the value deserialized, which the errors returned don't show.
+#[derive(Debug)]
+struct Seen<T>(T);
+
+impl<'de, T> Deserialize<'de> for Seen<T>
+where
+    T: Deserialize<'de> + core::fmt::Debug,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let value = T::deserialize(deserializer)?;
+        SEEN.with(|seen| *seen.borrow_mut() = format!("{value:?}"));
+        Ok(Seen(value))
+    }
+}
+
+fn seen() -> String {
+    SEEN.with(|seen| seen.take())
+}
+
+#[test]
+fn recursive_enums() {
+    let errors = Deserializer::new(value!(true))
+        .deserialize_all::<Seen<Expr>>()
+        .unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(seen(), "Add(Neg { expr: Lit(0) }, Lit(0))");
+
+    let errors = Deserializer::new(value!(true))
+        .deserialize_all::<Seen<List>>()
+        .unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(seen(), "Cons(0, Nil)");
+
+    let errors = Deserializer::new(value!(true))
+        .deserialize_all::<Seen<Endless>>()
+        .unwrap_err();
+    assert_eq!(errors.len(), 2);
+    let (_, error) = &errors.iter().as_slice()[1];
+    assert!(error.to_string().contains("nested too deeply"));
+    assert_eq!(seen(), "");
+}
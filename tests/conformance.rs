@@ -0,0 +1,114 @@
+#![cfg(feature = "derive")]
+
+extern crate alloc;
+
+use core::cell::RefCell;
+use serde::de::DeserializeSeed;
+use serde_content::conformance;
+use serde_content::conformance::Failure;
+use serde_content::conformance::Feature;
+use serde_content::token;
+use serde_content::Deserializer;
+use serde_content::Value;
+
+// A format that keeps every value as it is, by recording its tokens and replaying them.
+fn tokens() -> conformance::Report {
+    let written = RefCell::new(Vec::new());
+    conformance::check(
+        |value| {
+            *written.borrow_mut() = token::Serializer::new().serialize(value)?;
+            Ok::<_, serde_content::Error>(Vec::new())
+        },
+        |_, seed| seed.deserialize(&mut token::Deserializer::new(&written.borrow())),
+    )
+}
+
+#[test]
+fn all_supported() {
+    let report = tokens();
+    assert_eq!(report.supported().collect::<Vec<_>>(), Feature::ALL);
+    assert_eq!(report.unsupported().count(), 0);
+    assert!(report
+        .to_string()
+        .lines()
+        .all(|line| line.starts_with("ok ")));
+}
+
+#[test]
+fn failures() {
+    // A format that can't write byte arrays, and loses the type of every integer.
+    let report = conformance::check(
+        |value| match value {
+            Value::Bytes(_) => Err("bytes aren't supported"),
+            value => Ok(value.to_tokens().len().to_le_bytes().to_vec()),
+        },
+        |_, seed| match seed.feature() {
+            Feature::Bool => Ok(Value::Bool(true)),
+            Feature::Unit => Err("nothing written".to_string()),
+            _ => seed
+                .deserialize(Deserializer::new(Value::Bool(false)))
+                .map_err(|e| e.to_string()),
+        },
+    );
+    assert!(report.is_supported(Feature::Bool));
+    assert_eq!(
+        report.failure(Feature::Bytes),
+        Some(&Failure::Serialize("bytes aren't supported".into()))
+    );
+    assert_eq!(
+        report.failure(Feature::Unit),
+        Some(&Failure::Deserialize("nothing written".into()))
+    );
+    assert!(matches!(
+        report.failure(Feature::I128),
+        Some(Failure::Deserialize(_))
+    ));
+    assert_eq!(report.supported().count(), 1);
+    assert!(report
+        .to_string()
+        .contains("FAIL bytes: serializing: bytes aren't supported\n"));
+}
+
+#[test]
+fn mismatch() {
+    let report = conformance::check(
+        |_| Ok::<_, serde_content::Error>(Vec::new()),
+        |_, seed| match seed.feature() {
+            Feature::Some => seed
+                .deserialize(Deserializer::new(Value::Option(None)))
+                .map_err(|e| e.to_string()),
+            _ => Err("unsupported".to_string()),
+        },
+    );
+    assert_eq!(
+        report.failure(Feature::Some),
+        Some(&Failure::Mismatch {
+            expected: Value::Option(Some(Box::new(Value::from(1u8)))),
+            found: Value::Option(None),
+        })
+    );
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn json() {
+    let report = conformance::check(serde_json::to_vec, |bytes, seed| {
+        seed.deserialize(&mut serde_json::Deserializer::from_slice(bytes))
+    });
+    let unsupported: Vec<_> = report.unsupported().map(|(feature, _)| feature).collect();
+    assert_eq!(
+        unsupported,
+        [
+            Feature::NonFinite,
+            Feature::NestedOptions,
+            Feature::CompoundKeys
+        ]
+    );
+    assert_eq!(
+        report.failure(Feature::NestedOptions),
+        Some(&Failure::Mismatch {
+            expected: Value::Option(Some(Box::new(Value::Option(None)))),
+            found: Value::Option(None),
+        })
+    );
+}
@@ -0,0 +1,155 @@
+#![cfg(feature = "derive")]
+
+extern crate alloc;
+
+use alloc::collections::BTreeMap;
+use serde_content::Error;
+use serde_content::Expected;
+use serde_content::Found;
+use serde_content::Number;
+use serde_content::Serializer;
+use serde_content::Value;
+use std::collections::HashMap;
+
+#[test]
+fn options() {
+    assert_eq!(
+        Value::from(Some(1u8)),
+        Serializer::new().serialize(Some(1u8)).unwrap()
+    );
+    assert_eq!(
+        Value::from(None::<u8>),
+        Serializer::new().serialize(None::<u8>).unwrap()
+    );
+    assert_eq!(
+        Value::from(Some(Some("a"))),
+        Serializer::new().serialize(Some(Some("a"))).unwrap()
+    );
+}
+
+#[test]
+fn sequences() {
+    assert_eq!(
+        Value::from(vec![1u16, 2]),
+        Serializer::new().serialize(vec![1u16, 2]).unwrap()
+    );
+    assert_eq!(
+        Value::from(&[true, false][..]),
+        Serializer::new()
+            .serialize([true, false].as_slice())
+            .unwrap()
+    );
+    assert_eq!(
+        Value::from(vec!["a", "b"]),
+        Serializer::new().serialize(vec!["a", "b"]).unwrap()
+    );
+    let strings = vec![String::from("a")];
+    assert_eq!(
+        Value::from(strings.as_slice()),
+        Serializer::new().serialize(&strings).unwrap()
+    );
+    assert_eq!(
+        Value::from(vec![Value::Unit, Value::Bool(true)]),
+        Value::Seq(vec![Value::Unit, Value::Bool(true)])
+    );
+    // Byte vectors stay byte arrays.
+    assert_eq!(Value::from(vec![1u8, 2]), Value::Bytes(vec![1, 2].into()));
+}
+
+#[test]
+fn tuples() {
+    assert_eq!(
+        Value::from((1u8,)),
+        Serializer::new().serialize((1u8,)).unwrap()
+    );
+    assert_eq!(
+        Value::from((1u8, 'a', "b")),
+        Serializer::new().serialize((1u8, 'a', "b")).unwrap()
+    );
+    let tuple = (
+        0u8, 1u8, 2u8, 3u8, 4u8, 5u8, 6u8, 7u8, 8u8, 9u8, 10u8, 11u8, 12u8, 13u8, 14u8, 15u8,
+    );
+    assert_eq!(
+        Value::from(tuple),
+        Serializer::new().serialize(tuple).unwrap()
+    );
+}
+
+#[test]
+fn maps() {
+    let mut map = BTreeMap::new();
+    map.insert("a", 1u8);
+    map.insert("b", 2u8);
+    assert_eq!(
+        Value::from(map.clone()),
+        Serializer::new().serialize(&map).unwrap()
+    );
+
+    let mut map = HashMap::new();
+    map.insert(1u32, vec![true]);
+    assert_eq!(
+        Value::from(map),
+        Value::Map(vec![(Value::from(1u32), Value::from(vec![true]))])
+    );
+}
+
+#[test]
+fn from_iterator() {
+    let value: Value = (1..=3u8).map(Value::from).collect();
+    assert_eq!(value, Serializer::new().serialize(vec![1u8, 2, 3]).unwrap());
+
+    let value: Value = [("a", 1u8), ("b", 2u8)].into_iter().collect();
+    let map = BTreeMap::from([("a", 1u8), ("b", 2u8)]);
+    assert_eq!(value, Serializer::new().serialize(map).unwrap());
+}
+
+#[test]
+fn try_from() {
+    assert_eq!(<()>::try_from(Value::Unit), Ok(()));
+    assert_eq!(bool::try_from(Value::Bool(true)), Ok(true));
+    assert_eq!(char::try_from(Value::Char('a')), Ok('a'));
+    assert_eq!(u8::try_from(Value::from(1u8)), Ok(1));
+    assert_eq!(f64::try_from(Value::from(1.5f64)), Ok(1.5));
+    assert_eq!(Number::try_from(Value::from(1i128)), Ok(Number::I128(1)));
+    assert_eq!(String::try_from(Value::from("a")), Ok("a".into()));
+    assert_eq!(Vec::<u8>::try_from(Value::from(vec![1u8])), Ok(vec![1]));
+    assert_eq!(
+        Vec::<Value>::try_from(Value::from(vec![1u16])),
+        Ok(vec![Value::from(1u16)])
+    );
+    assert_eq!(
+        Value::from(Some(1u8)).try_into_option(),
+        Ok(Some(Value::from(1u8)))
+    );
+    assert_eq!(Value::from(None::<u8>).try_into_option(), Ok(None));
+}
+
+#[test]
+fn try_from_errors() {
+    assert_eq!(
+        u8::try_from(Value::from(1u16)),
+        Err(Error::unexpected(
+            Found::Number(Number::U16(1)),
+            Expected::U8
+        ))
+    );
+    assert_eq!(
+        Number::try_from(Value::Unit),
+        Err(Error::unexpected(Found::Unit, Expected::Number))
+    );
+    assert_eq!(
+        String::try_from(Value::Char('a')),
+        Err(Error::unexpected(Found::Char('a'), Expected::String))
+    );
+    assert_eq!(
+        Vec::<u8>::try_from(Value::from(vec![1u16])),
+        Err(Error::unexpected(
+            Found::Seq(vec![Found::Number(Number::U16(1))]),
+            Expected::Bytes
+        ))
+    );
+    assert_eq!(
+        Value::Bool(false).try_into_option(),
+        Err(Error::unexpected(Found::Bool(false), Expected::Option))
+    );
+}
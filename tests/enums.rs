@@ -0,0 +1,110 @@
+#![cfg(feature = "derive")]
+
+extern crate alloc;
+
+use serde::Deserialize;
+use serde_content::value;
+use serde_content::Deserializer;
+use serde_content::Error;
+use serde_content::Expected;
+use serde_content::Found;
+use serde_content::Number;
+use serde_content::Value;
+
+#[derive(Debug, Deserialize, PartialEq)]
+enum Shape {
+    Empty,
+    Circle(u8),
+    Line(u8, u8),
+    Rect { w: u8, h: u8 },
+}
+
+fn deserialize(value: Value<'_>) -> Result<Shape, Error> {
+    Deserializer::new(value).deserialize()
+}
+
+fn adjacent(value: Value<'_>) -> Result<Shape, Error> {
+    Deserializer::new(value)
+        .adjacent_tags("t", "c")
+        .deserialize()
+}
+
+#[test]
+fn variant_index() {
+    assert_eq!(deserialize(value!(0u8)), Ok(Shape::Empty));
+    assert_eq!(deserialize(value!(0u64)), Ok(Shape::Empty));
+    assert_eq!(
+        deserialize(value!(4u32)),
+        Err(Error::unexpected(
+            Found::Number(Number::U32(4)),
+            Expected::Enum {
+                name: Some("Shape".into()),
+                typ: None,
+            }
+        ))
+    );
+    assert!(deserialize(value!(-1i32)).is_err());
+    assert!(deserialize(value!(0.0f64)).is_err());
+    assert!(Deserializer::new(value!(0u8))
+        .strict()
+        .deserialize::<Shape>()
+        .is_err());
+}
+
+#[test]
+fn number_keys() {
+    assert_eq!(deserialize(value!({ 1u8: 3u8 })), Ok(Shape::Circle(3)));
+    assert_eq!(
+        deserialize(value!({ 2u8: [1u8, 2u8] })),
+        Ok(Shape::Line(1, 2))
+    );
+    assert_eq!(
+        deserialize(value!({ 3u8: { "w": 1u8, "h": 2u8 } })),
+        Ok(Shape::Rect { w: 1, h: 2 })
+    );
+    assert!(deserialize(value!({ 9u8: 3u8 })).is_err());
+}
+
+#[test]
+fn adjacently_tagged_maps() {
+    assert_eq!(adjacent(value!({ "t": "Empty" })), Ok(Shape::Empty));
+    assert_eq!(
+        adjacent(value!({ "t": "Circle", "c": 3u8 })),
+        Ok(Shape::Circle(3))
+    );
+    assert_eq!(
+        adjacent(value!({ "c": [1u8, 2u8], "t": "Line" })),
+        Ok(Shape::Line(1, 2))
+    );
+    assert_eq!(
+        adjacent(value!({ "t": 3u8, "c": { "w": 1u8, "h": 2u8 } })),
+        Ok(Shape::Rect { w: 1, h: 2 })
+    );
+    assert!(adjacent(value!({ "t": "Circle", "c": 3u8, "x": 1u8 })).is_err());
+    // Adjacent tags are not accepted unless configured.
+    assert!(deserialize(value!({ "t": "Circle", "c": 3u8 })).is_err());
+    // Externally tagged maps are still accepted.
+    assert_eq!(adjacent(value!({ "Circle": 3u8 })), Ok(Shape::Circle(3)));
+}
+
+#[test]
+fn adjacently_tagged_structs() {
+    assert_eq!(
+        adjacent(value!(Tagged {
+            t: "Circle",
+            c: 3u8
+        })),
+        Ok(Shape::Circle(3))
+    );
+    assert_eq!(adjacent(value!(Tagged { t: 0u8 })), Ok(Shape::Empty));
+    assert!(adjacent(value!(Tagged {
+        tag: "Circle",
+        c: 3u8
+    }))
+    .is_err());
+    assert!(deserialize(value!(Tagged {
+        t: "Circle",
+        c: 3u8
+    }))
+    .is_err());
+}
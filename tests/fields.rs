@@ -0,0 +1,241 @@
+#![cfg(feature = "derive")]
+
+extern crate alloc;
+
+use serde::Deserialize;
+use serde_content::value;
+use serde_content::Deserializer;
+use serde_content::ErrorKind;
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Point {
+    x: u8,
+    y: u8,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+struct Rgb {
+    r: u8,
+    g: u8,
+    b: u8,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+enum Shape {
+    Rect { w: u8, h: u8 },
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Settings {
+    name: String,
+    nickname: Option<String>,
+    marker: (),
+    #[serde(default)]
+    retries: u8,
+}
+
+#[test]
+fn missing_fields() {
+    let error = Deserializer::new(value!(Point { x: 1u8 }))
+        .deserialize::<Point>()
+        .unwrap_err();
+    assert_eq!(
+        error.kind(),
+        &ErrorKind::MissingField {
+            struct_name: Some("Point".into()),
+            field: "y".into(),
+        }
+    );
+    assert_eq!(error.to_string(), "missing field `y` in `Point`");
+
+    let error = Deserializer::new(value!({ "y": 2u8 }))
+        .deserialize::<Point>()
+        .unwrap_err();
+    assert_eq!(
+        error.into_kind(),
+        ErrorKind::MissingField {
+            struct_name: Some("Point".into()),
+            field: "x".into(),
+        }
+    );
+
+    let error = Deserializer::new(value!(Shape::Rect @ 0 { w: 1u8 }))
+        .deserialize::<Shape>()
+        .unwrap_err();
+    assert_eq!(
+        error.into_kind(),
+        ErrorKind::MissingField {
+            struct_name: Some("Shape".into()),
+            field: "h".into(),
+        }
+    );
+
+    let error = Deserializer::new(value!({ "Rect": { "h": 2u8 } }))
+        .deserialize::<Shape>()
+        .unwrap_err();
+    assert_eq!(
+        error.into_kind(),
+        ErrorKind::MissingField {
+            struct_name: Some("Shape".into()),
+            field: "w".into(),
+        }
+    );
+}
+
+#[test]
+fn unknown_fields() {
+    let error = Deserializer::new(value!(Rgb {
+        r: 1u8,
+        g: 2u8,
+        a: 3u8
+    }))
+    .deserialize::<Rgb>()
+    .unwrap_err();
+    assert_eq!(
+        error.kind(),
+        &ErrorKind::UnknownField {
+            field: "a".into(),
+            expected: vec!["r".into(), "g".into(), "b".into()],
+        }
+    );
+    assert_eq!(
+        error.to_string(),
+        "unknown field `a`, expected one of `r`, `g`, `b`"
+    );
+
+    let error = Deserializer::new(value!(Point {
+        x: 1u8,
+        y: 2u8,
+        z: 3u8
+    }))
+    .strict()
+    .deserialize::<Point>()
+    .unwrap_err();
+    assert_eq!(
+        error.into_kind(),
+        ErrorKind::UnknownField {
+            field: "z".into(),
+            expected: vec!["x".into(), "y".into()],
+        }
+    );
+}
+
+#[test]
+fn fill_missing() {
+    let value = value!(Settings {
+        name: "a",
+        retries: 3u8
+    });
+    let error = Deserializer::new(value.clone())
+        .deserialize::<Settings>()
+        .unwrap_err();
+    assert_eq!(
+        error.into_kind(),
+        ErrorKind::MissingField {
+            struct_name: Some("Settings".into()),
+            field: "marker".into(),
+        }
+    );
+    assert_eq!(
+        Deserializer::new(value).fill_missing().deserialize(),
+        Ok(Settings {
+            name: "a".into(),
+            nickname: None,
+            marker: (),
+            retries: 3,
+        })
+    );
+
+    let value = value!({ "name": "a", "retries": 3u8 });
+    assert_eq!(
+        Deserializer::new(value).fill_missing().deserialize(),
+        Ok(Settings {
+            name: "a".into(),
+            nickname: None,
+            marker: (),
+            retries: 3,
+        })
+    );
+
+    let error = Deserializer::new(value!(Settings {}))
+        .fill_missing()
+        .deserialize::<Settings>()
+        .unwrap_err();
+    assert_eq!(
+        error.into_kind(),
+        ErrorKind::MissingField {
+            struct_name: Some("Settings".into()),
+            field: "name".into(),
+        }
+    );
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Cfg {
+    name: Option<String>,
+    #[serde(default)]
+    count: u8,
+    marker: (),
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+#[test]
+fn fill_missing_keeps_defaults() {
+    let expected = Cfg {
+        name: None,
+        count: 0,
+        marker: (),
+        tags: Vec::new(),
+    };
+    let value = value!(Cfg {});
+    assert_eq!(
+        Deserializer::new(value.clone())
+            .fill_missing()
+            .deserialize(),
+        Ok(expected)
+    );
+    assert_eq!(
+        Deserializer::new(value.clone())
+            .fill_missing()
+            .deserialize_all::<Cfg>()
+            .map(|cfg| cfg.count),
+        Ok(0)
+    );
+
+    let ignored = serde_content::Ignored::new();
+    let value = value!({ "count": 2u8, "extra": true });
+    let cfg = ignored
+        .deserialize::<Cfg>(Deserializer::new(value).fill_missing())
+        .unwrap();
+    assert_eq!((cfg.name, cfg.count, cfg.tags.len()), (None, 2, 0));
+    assert_eq!(ignored.paths(), ["extra"]);
+}
+
+#[test]
+fn fill_missing_retries_once() {
+    use core::sync::atomic::AtomicUsize;
+    use core::sync::atomic::Ordering;
+
+    static PASSES: AtomicUsize = AtomicUsize::new(0);
+
+    struct Counted(Cfg);
+
+    impl<'de> Deserialize<'de> for Counted {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            PASSES.fetch_add(1, Ordering::Relaxed);
+            Cfg::deserialize(deserializer).map(Counted)
+        }
+    }
+
+    let cfg = Deserializer::new(value!(Cfg {}))
+        .fill_missing()
+        .deserialize::<Counted>()
+        .unwrap();
+    assert_eq!((cfg.0.count, cfg.0.tags.len()), (0, 0));
+    assert_eq!(PASSES.load(Ordering::Relaxed), 2);
+}
@@ -0,0 +1,94 @@
+#![cfg(feature = "derive")]
+
+extern crate alloc;
+
+use serde::Deserialize;
+use serde_content::value;
+use serde_content::Deserializer;
+use serde_content::Value;
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct User {
+    user_id: u32,
+    name: String,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+enum Status {
+    NotFound,
+    ServerError { retry_after: u8 },
+}
+
+fn loose<'de, T>(value: Value<'de>) -> Result<T, serde_content::Error>
+where
+    T: Deserialize<'de>,
+{
+    Deserializer::new(value).loose_identifiers().deserialize()
+}
+
+#[test]
+fn numeric_fields() {
+    let value = value!({ 0u8: 1u32, 1u64: "a" });
+    let user = Deserializer::new(value).deserialize::<User>();
+    assert_eq!(
+        user,
+        Ok(User {
+            user_id: 1,
+            name: "a".into()
+        })
+    );
+
+    let value = value!({ 0u8: 1u32, 1u8: "a" });
+    assert!(Deserializer::new(value)
+        .strict()
+        .deserialize::<User>()
+        .is_err());
+    let value = value!({ (-1i8): 1u32, 1u8: "a" });
+    assert!(Deserializer::new(value).deserialize::<User>().is_err());
+}
+
+#[test]
+fn loose_fields() {
+    let expected = || User {
+        user_id: 1,
+        name: "a".into(),
+    };
+    for key in ["user_id", "userId", "UserID", "USER-ID", "user id"] {
+        let map = value!({ (key): 1u32, "NAME": "a" });
+        assert!(Deserializer::new(map.clone())
+            .deserialize::<User>()
+            .is_err());
+        assert_eq!(loose::<User>(map), Ok(expected()));
+    }
+    let value = value!(User {
+        userId: 1u32,
+        Name: "a"
+    });
+    assert!(Deserializer::new(value.clone())
+        .deserialize::<User>()
+        .is_err());
+    assert_eq!(loose::<User>(value.clone()), Ok(expected()));
+    let user = Deserializer::new(value)
+        .loose_identifiers()
+        .strict()
+        .deserialize::<User>();
+    assert_eq!(user, Ok(expected()));
+}
+
+#[test]
+fn loose_variants() {
+    assert_eq!(loose::<Status>(value!("not_found")), Ok(Status::NotFound));
+    assert_eq!(loose::<Status>(value!("NOT-FOUND")), Ok(Status::NotFound));
+    assert!(Deserializer::new(value!("not_found"))
+        .deserialize::<Status>()
+        .is_err());
+    assert_eq!(
+        loose::<Status>(value!({ "server_error": { "RetryAfter": 3u8 } })),
+        Ok(Status::ServerError { retry_after: 3 })
+    );
+    assert_eq!(
+        loose::<Status>(value!(Status::serverError @ 1 { RETRY_AFTER: 3u8 })),
+        Ok(Status::ServerError { retry_after: 3 })
+    );
+    assert!(loose::<Status>(value!("not_founds")).is_err());
+}
@@ -0,0 +1,114 @@
+#![cfg(feature = "derive")]
+
+extern crate alloc;
+
+use serde::Deserialize;
+use serde_content::value;
+use serde_content::Deserializer;
+use serde_content::Ignored;
+use serde_content::Value;
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Config {
+    server: Server,
+    users: Vec<User>,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Server {
+    port: u16,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct User {
+    name: String,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+enum Shape {
+    Rect { w: u8, h: u8 },
+}
+
+#[test]
+fn unknown_fields() {
+    let value = value!(Config {
+        server: Server {
+            port: 80u16,
+            prot: "tcp"
+        },
+        users: [
+            User { name: "a" },
+            User {
+                name: "b",
+                nmae: "c",
+                admin: true
+            },
+        ],
+    });
+    let ignored = Ignored::new();
+    let config = ignored
+        .deserialize::<Config>(Deserializer::new(value))
+        .unwrap();
+    assert_eq!(config.server.port, 80);
+    assert_eq!(config.users.len(), 2);
+    assert_eq!(
+        ignored.take(),
+        vec![
+            ("server.prot".to_owned(), Value::from("tcp")),
+            ("users[1].nmae".to_owned(), Value::from("c")),
+            ("users[1].admin".to_owned(), Value::Bool(true)),
+        ]
+    );
+    assert!(ignored.is_empty());
+}
+
+#[test]
+fn map_entries() {
+    let value = value!({
+        "server": { "port": 80u16, "tls": { "cert": "a", "key": "b" } },
+        "users": [],
+    });
+    let ignored = Ignored::new();
+    let config: Config = ignored.deserialize(Deserializer::new(value)).unwrap();
+    assert_eq!(config.server, Server { port: 80 });
+    assert_eq!(ignored.paths(), ["server.tls"]);
+    assert_eq!(
+        ignored.take()[0].1,
+        value!({ "cert": "a", "key": "b" }).into_owned()
+    );
+}
+
+#[test]
+fn enum_variants() {
+    let ignored = Ignored::new();
+    let value = value!(Shape::Rect @ 0 {
+        w: 1u8,
+        h: 2u8,
+        d: 3u8
+    });
+    let shape: Shape = ignored.deserialize(Deserializer::new(value)).unwrap();
+    assert_eq!(shape, Shape::Rect { w: 1, h: 2 });
+    assert_eq!(ignored.paths(), ["Rect.d"]);
+
+    let value = value!({ "Rect": { "w": 1u8, "h": 2u8, "x": 3u8 } });
+    let shape: Shape = ignored.deserialize(Deserializer::new(value)).unwrap();
+    assert_eq!(shape, Shape::Rect { w: 1, h: 2 });
+    assert_eq!(ignored.paths(), ["Rect.d", "Rect.x"]);
+}
+
+#[test]
+fn borrowed_values_outlive_collector() {
+    #[derive(Deserialize)]
+    struct Borrowed<'a> {
+        name: &'a str,
+    }
+
+    let value = value!({ "name": "alice", "extra": 1u8 });
+    let borrowed: Borrowed = {
+        let ignored = Ignored::new();
+        let borrowed = ignored.deserialize(Deserializer::new(value)).unwrap();
+        assert_eq!(ignored.paths(), ["extra"]);
+        borrowed
+    };
+    assert_eq!(borrowed.name, "alice");
+}
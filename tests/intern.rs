@@ -0,0 +1,188 @@
+#![cfg(all(feature = "derive", feature = "std"))]
+
+extern crate alloc;
+
+use alloc::borrow::Cow;
+use alloc::collections::BTreeMap;
+use serde::Serialize;
+use serde_content::value;
+use serde_content::Data;
+use serde_content::Deserializer;
+use serde_content::ErrorKind;
+use serde_content::Interner;
+use serde_content::Limit;
+use serde_content::Limits;
+use serde_content::Serializer;
+use serde_content::Value;
+
+#[derive(Serialize)]
+struct Row {
+    name: String,
+    labels: BTreeMap<String, String>,
+}
+
+fn rows() -> Vec<Row> {
+    (0..3)
+        .map(|i| Row {
+            name: format!("row-{i}"),
+            labels: BTreeMap::from([
+                ("region".into(), "eu".into()),
+                ("tier".into(), format!("{}", i % 2)),
+            ]),
+        })
+        .collect()
+}
+
+// Returns the strings in `value`.
+fn strings<'v, 'a>(value: &'v Value<'a>, found: &mut Vec<&'v Cow<'a, str>>) {
+    match value {
+        Value::String(v) => found.push(v),
+        Value::Seq(v) | Value::Tuple(v) => v.iter().for_each(|v| strings(v, found)),
+        Value::Map(v) => v.iter().for_each(|(key, value)| {
+            strings(key, found);
+            strings(value, found);
+        }),
+        Value::Option(Some(v)) => strings(v, found),
+        Value::Struct(v) => match &v.data {
+            Data::NewType { value } => strings(value, found),
+            Data::Tuple { values } => values.iter().for_each(|v| strings(v, found)),
+            Data::Struct { fields } => fields.iter().for_each(|(_, v)| strings(v, found)),
+            Data::Unit => {}
+        },
+        _ => {}
+    }
+}
+
+// Checks that every string in `value` is interned in `interner`.
+fn assert_interned(value: &Value, interner: &Interner) {
+    let mut found = Vec::new();
+    strings(value, &mut found);
+    assert!(!found.is_empty());
+    for string in found {
+        let Cow::Borrowed(string) = string else {
+            panic!("{string:?} isn't borrowed");
+        };
+        assert!(core::ptr::eq(*string, interner.intern(string)));
+    }
+}
+
+#[test]
+fn intern() {
+    let interner = Interner::new();
+    assert!(interner.is_empty());
+    let a = interner.intern("key");
+    let b = interner.intern(&String::from("key"));
+    assert!(core::ptr::eq(a, b));
+    assert_eq!(interner.len(), 1);
+
+    let strings: Vec<String> = (0..10_000).map(|i| i.to_string()).collect();
+    let interned: Vec<&str> = strings.iter().map(|s| interner.intern(s)).collect();
+    assert_eq!(interner.len(), 10_001);
+    for (string, interned) in strings.iter().zip(interned) {
+        assert_eq!(string, interned);
+        assert!(core::ptr::eq(interned, interner.intern(string)));
+    }
+    interner.intern("");
+    assert_eq!(interner.len(), 10_002);
+    assert_eq!(format!("{interner:?}"), "Interner { len: 10002 }");
+}
+
+#[test]
+fn serializer() {
+    let interner = Interner::new();
+    let value = Serializer::new()
+        .intern(&interner)
+        .serialize(rows())
+        .unwrap();
+    assert_eq!(value, Serializer::new().serialize(rows()).unwrap());
+    assert_interned(&value, &interner);
+    // 3 names, 2 keys and 3 distinct values.
+    assert_eq!(interner.len(), 8);
+
+    // Later values share the strings already in the pool.
+    let again = Serializer::new()
+        .intern(&interner)
+        .serialize(rows())
+        .unwrap();
+    assert_eq!(again, value);
+    assert_eq!(interner.len(), 8);
+}
+
+#[test]
+fn threads() {
+    let interner = Interner::new();
+    let serializer = Serializer::new().intern(&interner);
+    let values: Vec<Value> = std::thread::scope(|scope| {
+        let threads: Vec<_> = (0..4)
+            .map(|_| scope.spawn(|| serializer.serialize(rows()).unwrap()))
+            .collect();
+        threads.into_iter().map(|t| t.join().unwrap()).collect()
+    });
+    for value in &values {
+        assert_interned(value, &interner);
+    }
+    assert_eq!(interner.len(), 8);
+}
+
+#[test]
+fn collect_str() {
+    struct Display;
+
+    impl Serialize for Display {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            serializer.collect_str(&format_args!("{}-{}", "a", 1))
+        }
+    }
+
+    let interner = Interner::new();
+    let value = Serializer::new()
+        .intern(&interner)
+        .serialize(Display)
+        .unwrap();
+    assert_eq!(value, value!("a-1"));
+    assert!(matches!(value, Value::String(Cow::Borrowed(_))));
+    assert_eq!(interner.len(), 1);
+}
+
+#[test]
+fn limits() {
+    let interner = Interner::new();
+    let limits = Limits::new().max_string_len(4);
+    let error = Serializer::new()
+        .intern(&interner)
+        .limits(&limits)
+        .serialize(["tiny", "too long"])
+        .unwrap_err();
+    assert_eq!(
+        error.kind(),
+        &ErrorKind::LimitExceeded(Limit::StringLength(4))
+    );
+    assert_eq!(interner.len(), 1);
+}
+
+#[test]
+fn deserialize() {
+    let interner = Interner::new();
+    let rows = Serializer::new().serialize(rows()).unwrap().into_owned();
+    let value = interner
+        .deserialize(Deserializer::new(rows.clone()))
+        .unwrap();
+    assert_interned(&value, &interner);
+    // The field names are strings too once deserialized.
+    assert_eq!(interner.len(), 10);
+
+    // Structs are lossy, but their fields still make it across as maps.
+    let expected: Value = Deserializer::new(rows).deserialize().unwrap();
+    assert_eq!(value, expected);
+
+    // Byte arrays and other values are the same as without interning.
+    let value = value!([(vec![1u8, 2]), 1u8, 'c', (), None, Some(true)]);
+    let found = interner
+        .deserialize(Deserializer::new(value.clone()))
+        .unwrap();
+    assert_eq!(found, value);
+    assert!(matches!(&found, Value::Seq(v) if matches!(v[0], Value::Bytes(Cow::Owned(_)))));
+}
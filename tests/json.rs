@@ -0,0 +1,222 @@
+#![cfg(all(feature = "derive", feature = "json"))]
+
+extern crate alloc;
+
+use core::fmt;
+use serde::de;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_content::json;
+use serde_content::value;
+use serde_content::BytesEncoding;
+use serde_content::Data;
+use serde_content::Deserializer;
+use serde_content::Number;
+use serde_content::Serializer;
+use serde_content::Struct;
+use serde_content::Value;
+use serde_json::json;
+
+/// Deserializes via `deserialize_byte_buf`, like `serde_bytes::ByteBuf`.
+#[derive(Debug, PartialEq)]
+struct ByteBuf(Vec<u8>);
+
+impl<'de> Deserialize<'de> for ByteBuf {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct Visitor;
+
+        impl de::Visitor<'_> for Visitor {
+            type Value = ByteBuf;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a byte array")
+            }
+
+            fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Ok(ByteBuf(v.to_vec()))
+            }
+        }
+
+        deserializer.deserialize_byte_buf(Visitor)
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[derive(Debug, Serialize)]
+enum Shape {
+    Empty,
+    Circle(f64),
+    Polygon(Vec<Point>),
+    Rect { origin: Point, size: (u8, u8) },
+}
+
+#[test]
+fn from_json() {
+    let json = json!({ "a": [null, true, -1, 2, 1.5, "s"], "b": {} });
+    assert_eq!(
+        Value::from(json),
+        value!({
+            "a": [(), true, -1i64, 2u64, 1.5f64, "s"],
+            "b": {},
+        })
+    );
+    assert_eq!(
+        Number::from(serde_json::Number::from(u64::MAX)),
+        Number::U64(u64::MAX)
+    );
+}
+
+#[test]
+fn default_policy() {
+    let shapes = [
+        Shape::Empty,
+        Shape::Circle(f64::NAN),
+        Shape::Polygon(vec![Point { x: 1, y: 2 }]),
+        Shape::Rect {
+            origin: Point { x: 0, y: 0 },
+            size: (3, 4),
+        },
+    ];
+    let value = Serializer::new().serialize(&shapes).unwrap();
+    assert_eq!(
+        serde_json::Value::try_from(value).unwrap(),
+        json!([
+            "Empty",
+            { "Circle": null },
+            { "Polygon": [{ "x": 1, "y": 2 }] },
+            { "Rect": { "origin": { "x": 0, "y": 0 }, "size": [3, 4] } },
+        ])
+    );
+
+    let value = value!({ 1u8: 'c', true: (Some(u128::MAX)), 'k': (&b"\x01"[..]) });
+    assert_eq!(
+        serde_json::Value::try_from(value).unwrap(),
+        json!({ "1": "c", "true": "340282366920938463463374607431768211455", "k": [1] })
+    );
+
+    let error = serde_json::Value::try_from(value!({ [1u8]: () })).unwrap_err();
+    assert_eq!(error.to_string(), "[1u8] can't be represented in JSON");
+}
+
+#[test]
+fn policies() {
+    let policy = json::Policy::new()
+        .bytes_encoding(BytesEncoding::Base64)
+        .chars(json::Chars::CodePoint)
+        .non_finite(json::NonFinite::String)
+        .keys(json::Keys::Entries)
+        .tuples(json::Tuples::Tagged);
+    let value = value!({
+        "bytes": (&b"abcd"[..]),
+        "char": 'a',
+        "floats": [(f32::INFINITY), (f64::NEG_INFINITY), (f64::NAN)],
+        "tuple": (1u8, "b"),
+        "map": { 1u8: "one" },
+    });
+    assert_eq!(
+        policy.convert(value).unwrap(),
+        json!({
+            "bytes": "YWJjZA==",
+            "char": 97,
+            "floats": ["Infinity", "-Infinity", "NaN"],
+            "tuple": { "$tuple": [1, "b"] },
+            "map": [[1, "one"]],
+        })
+    );
+
+    let policy = json::Policy::new().non_finite(json::NonFinite::Reject);
+    assert!(policy.convert(value!((f64::NAN))).is_err());
+    let policy = json::Policy::new().keys(json::Keys::Reject);
+    assert!(policy.convert(value!({ 1u8: 2u8 })).is_err());
+    let policy = json::Policy::new().wide_integers(json::WideIntegers::Number);
+    assert_eq!(policy.convert(value!(1i128)).unwrap(), json!(1));
+    assert!(policy.convert(value!((u128::MAX))).is_err());
+}
+
+#[test]
+fn duplicate_keys() {
+    let value = value!({ 1u8: "a", "1": "b" });
+    let error = serde_json::Value::try_from(value.clone()).unwrap_err();
+    assert_eq!(error.to_string(), "duplicate key `1` in JSON object");
+    // Writing the text fails the same way.
+    assert_eq!(json::to_string(&value).unwrap_err(), error);
+    let error = serde_json::Value::try_from(value!({ "a": 1u8, "a": 2u8 })).unwrap_err();
+    assert_eq!(error.to_string(), "duplicate key `a` in JSON object");
+    assert_eq!(
+        serde_json::Value::try_from(value!({ 1u8: "a", "2": "b" })).unwrap(),
+        json!({ "1": "a", "2": "b" })
+    );
+
+    // So are struct fields, unless they are shadowed by the tag.
+    let point = |field: &'static str| {
+        Value::Struct(Box::new(Struct {
+            name: "Point".into(),
+            data: Data::Struct {
+                fields: vec![(field.into(), value!(1u8)), (field.into(), value!(2u8))],
+            },
+        }))
+    };
+    let error = serde_json::Value::try_from(point("x")).unwrap_err();
+    assert_eq!(error.to_string(), "duplicate key `x` in JSON object");
+    assert_eq!(json::to_string(&point("x")).unwrap_err(), error);
+    let policy = json::Policy::new().names(json::Names::Tagged);
+    assert_eq!(
+        policy.convert(point("$type")).unwrap(),
+        json!({ "$type": "Point" })
+    );
+    assert_eq!(
+        policy.to_string(&point("$type")).unwrap(),
+        r#"{"$type":"Point"}"#
+    );
+}
+
+#[test]
+fn tagged_names() {
+    let policy = json::Policy::new().names(json::Names::Tagged);
+    let shapes = [
+        Shape::Empty,
+        Shape::Circle(1.0),
+        Shape::Rect {
+            origin: Point { x: 0, y: 0 },
+            size: (3, 4),
+        },
+    ];
+    let value = Serializer::new().serialize(&shapes).unwrap();
+    assert_eq!(
+        policy.convert(value).unwrap(),
+        json!([
+            { "$type": "Shape::Empty" },
+            { "$type": "Shape::Circle", "$value": 1.0 },
+            {
+                "$type": "Shape::Rect",
+                "origin": { "$type": "Point", "x": 0, "y": 0 },
+                "size": [3, 4],
+            },
+        ])
+    );
+}
+
+#[test]
+fn bytes_roundtrip() {
+    let policy = json::Policy::new().bytes_encoding(BytesEncoding::Base64Url);
+    for bytes in [&b""[..], b"f", b"fo", b"foo", b"\xfb\xff\xfe"] {
+        let json = policy.convert(Value::Bytes(bytes.into())).unwrap();
+        let decoded: ByteBuf = Deserializer::new(Value::from(json))
+            .human_readable()
+            .bytes_encoding(BytesEncoding::Base64Url)
+            .deserialize()
+            .unwrap();
+        assert_eq!(decoded.0, bytes);
+    }
+}
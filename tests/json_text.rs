@@ -0,0 +1,272 @@
+#![cfg(feature = "derive")]
+
+extern crate alloc;
+
+use alloc::borrow::Cow;
+use serde::Serialize;
+use serde_content::json;
+use serde_content::value;
+use serde_content::BytesEncoding;
+use serde_content::Data;
+use serde_content::ErrorKind;
+use serde_content::Limit;
+use serde_content::Limits;
+use serde_content::Number;
+use serde_content::Serializer;
+use serde_content::Struct;
+use serde_content::Value;
+
+#[derive(Debug, Serialize)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[derive(Debug, Serialize)]
+enum Shape {
+    Empty,
+    Circle(f64),
+    Rect { origin: Point, size: (u8, u8) },
+}
+
+fn syntax(text: &str) -> (String, usize, usize) {
+    match json::from_str(text).unwrap_err().into_kind() {
+        ErrorKind::Syntax {
+            message,
+            line,
+            column,
+        } => (message, line, column),
+        kind => panic!("unexpected error: {kind:?}"),
+    }
+}
+
+#[test]
+fn read() {
+    let value = json::from_str(" { \"a\": [null, true, false, \"s\"], \"b\": {} } ").unwrap();
+    assert_eq!(value, value!({ "a": [(), true, false, "s"], "b": {} }));
+    assert_eq!(json::from_str("[]").unwrap(), Value::Seq(Vec::new()));
+}
+
+#[test]
+fn read_numbers() {
+    let numbers = [
+        ("0", Number::U8(0)),
+        ("255", Number::U8(255)),
+        ("256", Number::U16(256)),
+        ("70000", Number::U32(70_000)),
+        ("5000000000", Number::U64(5_000_000_000)),
+        ("18446744073709551616", Number::U128(1 << 64)),
+        ("-1", Number::I8(-1)),
+        ("-129", Number::I16(-129)),
+        ("-40000", Number::I32(-40_000)),
+        ("-5000000000", Number::I64(-5_000_000_000)),
+        ("-9223372036854775809", Number::I128(i64::MIN as i128 - 1)),
+        ("1e3", Number::F64(1000.0)),
+        ("-1.5E-1", Number::F64(-0.15)),
+        ("1.0", Number::F64(1.0)),
+        (
+            "1000000000000000000000000000000000000000",
+            Number::F64(1e39),
+        ),
+    ];
+    for (text, number) in numbers {
+        assert_eq!(
+            json::from_str(text).unwrap(),
+            Value::Number(number),
+            "{text}"
+        );
+    }
+    for text in ["01", "-", "1.", ".5", "1e", "+1"] {
+        assert!(json::from_str(text).is_err(), "{text}");
+    }
+    assert_eq!(syntax("[1e999]").0, "number out of range");
+}
+
+#[test]
+fn read_strings() {
+    let value = json::from_str(r#""plain ünïcode""#).unwrap();
+    assert!(matches!(
+        value,
+        Value::String(Cow::Borrowed("plain ünïcode"))
+    ));
+
+    let value = json::from_str(r#""a\"b\\c\/d\né🦀""#).unwrap();
+    match value {
+        Value::String(Cow::Owned(v)) => assert_eq!(v, "a\"b\\c/d\né🦀"),
+        value => panic!("unexpected value: {value:?}"),
+    }
+
+    assert_eq!(syntax(r#""\ud83e""#).0, "lone surrogate in string");
+    assert_eq!(syntax(r#""\udd80""#).0, "lone surrogate in string");
+    assert_eq!(syntax(r#""\x""#).0, "invalid escape");
+    assert_eq!(syntax(r#""\u12""#).0, "invalid unicode escape");
+    assert_eq!(syntax("\"a\nb\"").0, "control character in string");
+}
+
+#[test]
+fn read_errors() {
+    assert_eq!(syntax(""), ("unexpected end of input".into(), 1, 1));
+    assert_eq!(syntax("[1,]"), ("expected value".into(), 1, 4));
+    assert_eq!(syntax("[1 2]"), ("expected `,` or `]`".into(), 1, 4));
+    assert_eq!(syntax("{\n  \"ü\" 1\n}"), ("expected `:`".into(), 2, 7));
+    assert_eq!(syntax("{1: 2}"), ("expected string key".into(), 1, 2));
+    assert_eq!(syntax("nul"), ("expected value".into(), 1, 1));
+    assert_eq!(syntax("1 2"), ("trailing characters".into(), 1, 3));
+    let error = json::from_str("[1 2]").unwrap_err();
+    assert_eq!(error.to_string(), "expected `,` or `]` at line 1 column 4");
+}
+
+#[test]
+fn read_limits() {
+    let deep = "[".repeat(200) + &"]".repeat(200);
+    let error = json::from_str(&deep).unwrap_err();
+    assert_eq!(error.kind(), &ErrorKind::LimitExceeded(Limit::Depth(128)));
+
+    let limits = Limits::new().max_depth(200);
+    assert!(json::Reader::new(&deep).limits(&limits).read().is_ok());
+
+    // Limits without a depth limit keep the default one.
+    let limits = Limits::new().max_elements(1000);
+    let error = json::Reader::new(&deep).limits(&limits).read().unwrap_err();
+    assert_eq!(error.kind(), &ErrorKind::LimitExceeded(Limit::Depth(128)));
+
+    let limits = Limits::new().max_elements(3).max_string_len(2);
+    let reader = json::Reader::new(r#"{"a": [1, 2]}"#).limits(&limits);
+    assert!(reader.read().is_ok());
+    // The counts start over on every read.
+    assert!(reader.read().is_ok());
+    let error = json::Reader::new("[1, 2, 3, 4]")
+        .limits(&limits)
+        .read()
+        .unwrap_err();
+    assert_eq!(error.kind(), &ErrorKind::LimitExceeded(Limit::Elements(3)));
+    let error = json::Reader::new(r#"["abc"]"#)
+        .limits(&limits)
+        .read()
+        .unwrap_err();
+    assert_eq!(
+        error.kind(),
+        &ErrorKind::LimitExceeded(Limit::StringLength(2))
+    );
+}
+
+#[test]
+fn write_duplicate_keys() {
+    let error = json::to_string(&value!({ 1u8: "a", "1": "b" })).unwrap_err();
+    assert_eq!(error.to_string(), "duplicate key `1` in JSON object");
+    let error = json::to_string(&value!({ 'a': 1u8, "a": 2u8 })).unwrap_err();
+    assert_eq!(error.to_string(), "duplicate key `a` in JSON object");
+    let policy = json::Policy::new().keys(json::Keys::Entries);
+    assert_eq!(
+        policy.to_string(&value!({ 1u8: "a", "1": "b" })).unwrap(),
+        r#"[[1,"a"],["1","b"]]"#
+    );
+
+    // Struct fields are checked too.
+    let value = Value::Struct(Box::new(Struct {
+        name: "Point".into(),
+        data: Data::Struct {
+            fields: vec![("x".into(), value!(1u8)), ("x".into(), value!(2u8))],
+        },
+    }));
+    let error = json::to_string(&value).unwrap_err();
+    assert_eq!(error.to_string(), "duplicate key `x` in JSON object");
+}
+
+#[test]
+fn write_limits() {
+    let mut value = value!([]);
+    for _ in 1..128 {
+        value = Value::Seq(vec![value]);
+    }
+    let json = json::to_string(&value).unwrap();
+    assert_eq!(json::from_str(&json).unwrap(), value);
+
+    let value = Value::Seq(vec![value]);
+    let error = json::to_string(&value).unwrap_err();
+    assert_eq!(error.kind(), &ErrorKind::LimitExceeded(Limit::Depth(128)));
+    // Objects and the arrays of map entries count too.
+    let value = value!({ 1u8: (value) });
+    let policy = json::Policy::new().keys(json::Keys::Entries);
+    assert!(json::to_string(&value).is_err());
+    assert!(policy.to_string(&value).is_err());
+}
+
+#[test]
+fn write() {
+    let shapes = [
+        Shape::Empty,
+        Shape::Circle(f64::NAN),
+        Shape::Rect {
+            origin: Point { x: 0, y: -1 },
+            size: (3, 4),
+        },
+    ];
+    let value = Serializer::new().serialize(&shapes).unwrap();
+    assert_eq!(
+        json::to_string(&value).unwrap(),
+        r#"["Empty",{"Circle":null},{"Rect":{"origin":{"x":0,"y":-1},"size":[3,4]}}]"#
+    );
+
+    let value = value!({
+        1u8: 'c',
+        true: (Some(u128::MAX)),
+        'k': (&b"\x01"[..]),
+        "s": "\"\\\n\u{1}é",
+        "f": [1.0f64, 0.5f32, (f64::INFINITY)],
+    });
+    assert_eq!(
+        json::to_string(&value).unwrap(),
+        r#"{"1":"c","true":"340282366920938463463374607431768211455","k":[1],"s":"\"\\\n\u0001é","f":[1.0,0.5,null]}"#
+    );
+
+    let error = json::to_string(&value!({ [1u8]: () })).unwrap_err();
+    assert_eq!(error.to_string(), "[1u8] can't be represented in JSON");
+}
+
+#[test]
+fn write_policies() {
+    let policy = json::Policy::new()
+        .bytes_encoding(BytesEncoding::Base64)
+        .chars(json::Chars::CodePoint)
+        .wide_integers(json::WideIntegers::Number)
+        .non_finite(json::NonFinite::String)
+        .keys(json::Keys::Entries)
+        .tuples(json::Tuples::Tagged);
+    let value = value!({
+        "bytes": (&b"abcd"[..]),
+        "char": 'a',
+        "wide": (u128::MAX),
+        "floats": [(f32::INFINITY), (f64::NAN)],
+        "tuple": (1u8, "b"),
+        "map": { 1u8: "one" },
+    });
+    assert_eq!(
+        policy.to_string(&value).unwrap(),
+        r#"{"bytes":"YWJjZA==","char":97,"wide":340282366920938463463374607431768211455,"floats":["Infinity","NaN"],"tuple":{"$tuple":[1,"b"]},"map":[[1,"one"]]}"#
+    );
+
+    let shapes = [
+        Shape::Empty,
+        Shape::Circle(1.0),
+        Shape::Rect {
+            origin: Point { x: 0, y: 0 },
+            size: (3, 4),
+        },
+    ];
+    let value = Serializer::new().serialize(&shapes).unwrap();
+    let policy = json::Policy::new().names(json::Names::Tagged);
+    assert_eq!(
+        policy.to_string(&value).unwrap(),
+        r#"[{"$type":"Shape::Empty"},{"$type":"Shape::Circle","$value":1.0},{"$type":"Shape::Rect","origin":{"$type":"Point","x":0,"y":0},"size":[3,4]}]"#
+    );
+}
+
+#[test]
+fn roundtrip() {
+    let text =
+        r#"{"a":[null,true,-1,2,1.5,"s\"t"],"b":{"c":340282366920938463463374607431768211455}}"#;
+    let value = json::from_str(text).unwrap();
+    let policy = json::Policy::new().wide_integers(json::WideIntegers::Number);
+    assert_eq!(policy.to_string(&value).unwrap(), text);
+}
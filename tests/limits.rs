@@ -0,0 +1,186 @@
+#![cfg(feature = "derive")]
+
+extern crate alloc;
+
+use serde::Serialize;
+use serde_content::value;
+use serde_content::Deserializer;
+use serde_content::ErrorKind;
+use serde_content::Limit;
+use serde_content::Limits;
+use serde_content::Serializer;
+use serde_content::Value;
+
+#[derive(Debug, Serialize)]
+struct Node {
+    name: String,
+    children: Vec<Node>,
+}
+
+impl Node {
+    fn chain(depth: usize) -> Self {
+        let mut node = Node {
+            name: "leaf".into(),
+            children: Vec::new(),
+        };
+        for _ in 0..depth {
+            node = Node {
+                name: "node".into(),
+                children: vec![node],
+            };
+        }
+        node
+    }
+}
+
+fn nested(depth: usize) -> Value<'static> {
+    let mut value = Value::Unit;
+    for _ in 0..depth {
+        value = Value::Seq(vec![value]);
+    }
+    value
+}
+
+#[test]
+fn serializer() {
+    // Each node is a struct holding a sequence, so it is two levels deep.
+    let limits = Limits::new().max_depth(6);
+    let value = Serializer::new().limits(&limits).serialize(Node::chain(2));
+    assert!(value.is_ok());
+    let error = Serializer::new()
+        .limits(&limits)
+        .serialize(Node::chain(3))
+        .unwrap_err();
+    assert_eq!(error.kind(), &ErrorKind::LimitExceeded(Limit::Depth(6)));
+
+    let limits = Limits::new().max_elements(4);
+    let error = Serializer::new()
+        .limits(&limits)
+        .serialize([1u8, 2, 3, 4, 5])
+        .unwrap_err();
+    assert_eq!(error.kind(), &ErrorKind::LimitExceeded(Limit::Elements(4)));
+
+    let limits = Limits::new().max_string_len(4);
+    let error = Serializer::new()
+        .limits(&limits)
+        .serialize(Node::chain(0))
+        .unwrap_err();
+    assert_eq!(
+        error.kind(),
+        &ErrorKind::LimitExceeded(Limit::StringLength(4))
+    );
+    assert!(Serializer::new().limits(&limits).serialize("leaf").is_ok());
+
+    // Field names count towards the total.
+    let limits = Limits::new().max_total_bytes(16);
+    let error = Serializer::new()
+        .limits(&limits)
+        .serialize(Node::chain(1))
+        .unwrap_err();
+    assert_eq!(
+        error.kind(),
+        &ErrorKind::LimitExceeded(Limit::TotalBytes(16))
+    );
+    assert_eq!(
+        error.to_string(),
+        "exceeded the maximum of 16 bytes in total"
+    );
+}
+
+#[test]
+fn serializer_resets() {
+    let limits = Limits::new().max_elements(3);
+    let serializer = Serializer::new().limits(&limits);
+    for _ in 0..3 {
+        assert!(serializer.serialize([1u8, 2, 3]).is_ok());
+    }
+}
+
+#[test]
+fn deserializer() {
+    let limits = Limits::new().max_depth(100);
+    let value: Vec<Value> = Deserializer::new(nested(100))
+        .limits(&limits)
+        .unwrap()
+        .deserialize()
+        .unwrap();
+    assert_eq!(value.len(), 1);
+
+    // Deep values are rejected before they are deserialized.
+    let error = Deserializer::new(nested(1_000))
+        .limits(&limits)
+        .unwrap_err();
+    assert_eq!(error.kind(), &ErrorKind::LimitExceeded(Limit::Depth(100)));
+
+    let limits = Limits::new().max_elements(3).max_total_bytes(6);
+    let value = value!({ "a": [1u8, 2u8], "b": "c" });
+    assert!(Deserializer::new(value).limits(&limits).is_err());
+    let value = value!({ "ab": "cd", "ef": 1u8 });
+    assert!(Deserializer::new(value).limits(&limits).is_ok());
+    let value = value!({ "ab": "cd", "efg": 1u8 });
+    let error = Deserializer::new(value).limits(&limits).unwrap_err();
+    assert_eq!(
+        error.kind(),
+        &ErrorKind::LimitExceeded(Limit::TotalBytes(6))
+    );
+}
+
+#[test]
+fn foreign() {
+    let limits = Limits::new().max_depth(3).max_elements(4);
+    let value = limits
+        .deserialize(Deserializer::new(value!([[[1u8]], 2u8])))
+        .unwrap();
+    assert_eq!(value, value!([[[1u8]], 2u8]));
+
+    let error = limits
+        .deserialize(Deserializer::new(nested(4)))
+        .unwrap_err();
+    assert_eq!(error.to_string(), "exceeded the maximum depth of 3");
+
+    let error = limits
+        .deserialize(Deserializer::new(
+            value!({ "a": 1u8, "b": [2u8, 3u8, 4u8] }),
+        ))
+        .unwrap_err();
+    assert_eq!(error.to_string(), "exceeded the maximum of 4 elements");
+}
+
+#[test]
+fn shared() {
+    static LIMITS: Limits = Limits::new().max_elements(3).max_total_bytes(4);
+
+    let threads = (0..4)
+        .map(|_| {
+            std::thread::spawn(|| {
+                for _ in 0..8 {
+                    let value = Serializer::new()
+                        .limits(&LIMITS)
+                        .serialize(["ab", "cd"])
+                        .unwrap();
+                    Deserializer::new(value.clone()).limits(&LIMITS).unwrap();
+                    LIMITS.deserialize(Deserializer::new(value)).unwrap();
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+    for thread in threads {
+        thread.join().unwrap();
+    }
+    assert_eq!(LIMITS, Limits::new().max_elements(3).max_total_bytes(4));
+}
+
+#[test]
+fn direct() {
+    let limits = Limits::new().max_depth(1).max_string_len(2).max_elements(1);
+    let serializer = Serializer::new().limits(&limits);
+    assert!(["ab", "cd"].serialize(serializer).is_ok());
+    assert_eq!(
+        ["abc"].serialize(serializer).unwrap_err().kind(),
+        &ErrorKind::LimitExceeded(Limit::StringLength(2))
+    );
+    assert_eq!(
+        [["ab"]].serialize(serializer).unwrap_err().kind(),
+        &ErrorKind::LimitExceeded(Limit::Depth(1))
+    );
+}
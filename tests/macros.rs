@@ -0,0 +1,220 @@
+#![cfg(feature = "derive")]
+
+extern crate alloc;
+
+use alloc::collections::BTreeMap;
+use serde::Serialize;
+use serde_content::value;
+use serde_content::Serializer;
+use serde_content::Value;
+
+#[test]
+fn scalars() {
+    assert_eq!(value!(()), Serializer::new().serialize(()).unwrap());
+    assert_eq!(value!(true), Serializer::new().serialize(true).unwrap());
+    assert_eq!(value!(-1i8), Serializer::new().serialize(-1i8).unwrap());
+    assert_eq!(value!(1u128), Serializer::new().serialize(1u128).unwrap());
+    assert_eq!(value!(3.5f64), Serializer::new().serialize(3.5f64).unwrap());
+    assert_eq!(value!('a'), Serializer::new().serialize('a').unwrap());
+    assert_eq!(value!("foo"), Serializer::new().serialize("foo").unwrap());
+}
+
+#[test]
+fn expressions() {
+    let bytes = vec![1u8, 2];
+    let name = String::from("foo");
+    assert_eq!(value!((bytes.clone())), Value::from(bytes));
+    assert_eq!(
+        value!((name.as_str())),
+        Serializer::new().serialize("foo").unwrap()
+    );
+    assert_eq!(
+        value!([(1 + 1), 3]),
+        Serializer::new().serialize(vec![2, 3]).unwrap()
+    );
+}
+
+#[test]
+fn options() {
+    assert_eq!(
+        value!(None),
+        Serializer::new().serialize(None::<()>).unwrap()
+    );
+    assert_eq!(
+        value!(Some(())),
+        Serializer::new().serialize(Some(())).unwrap()
+    );
+    assert_eq!(
+        value!(Some(Some(1i32))),
+        Serializer::new().serialize(Some(Some(1i32))).unwrap()
+    );
+}
+
+#[test]
+fn sequences() {
+    assert_eq!(
+        value!([]),
+        Serializer::new().serialize(Vec::<()>::new()).unwrap()
+    );
+    assert_eq!(
+        value!([1u8, 2u8]),
+        Serializer::new().serialize(vec![1u8, 2u8]).unwrap()
+    );
+    assert_eq!(
+        value!([[1u8], [],]),
+        Serializer::new()
+            .serialize(vec![vec![1u8], vec![]])
+            .unwrap()
+    );
+}
+
+#[test]
+fn tuples() {
+    assert_eq!(value!((1u8,)), Serializer::new().serialize((1u8,)).unwrap());
+    assert_eq!(
+        value!((1u8, "a", [true])),
+        Serializer::new().serialize((1u8, "a", vec![true])).unwrap()
+    );
+}
+
+#[test]
+fn maps() {
+    let mut map = BTreeMap::new();
+    map.insert("k", vec![1u8, 2u8]);
+    map.insert("l", vec![]);
+    assert_eq!(
+        value!({ "k": [1u8, 2u8], "l": [] }),
+        Serializer::new().serialize(&map).unwrap()
+    );
+    assert_eq!(
+        value!({}),
+        Serializer::new()
+            .serialize(BTreeMap::<(), ()>::new())
+            .unwrap()
+    );
+
+    let mut map = BTreeMap::new();
+    map.insert(Key::A, (1u8, 2u8));
+    assert_eq!(
+        value!({ Key::A @ 0: (1u8, 2u8), }),
+        Serializer::new().serialize(&map).unwrap()
+    );
+
+    #[derive(Serialize, PartialEq, Eq, PartialOrd, Ord)]
+    enum Key {
+        A,
+    }
+}
+
+#[test]
+fn structs() {
+    #[derive(Serialize)]
+    struct Unit;
+
+    #[derive(Serialize)]
+    struct NewType(i32);
+
+    #[derive(Serialize)]
+    struct Tuple(i32, i32);
+
+    #[derive(Serialize)]
+    struct Empty();
+
+    #[derive(Serialize)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    #[derive(Serialize)]
+    struct Line {
+        from: Point,
+        to: Option<Point>,
+    }
+
+    assert_eq!(value!(Unit), Serializer::new().serialize(Unit).unwrap());
+    assert_eq!(
+        value!(NewType(1i32)),
+        Serializer::new().serialize(NewType(1)).unwrap()
+    );
+    assert_eq!(
+        value!(Tuple(1i32, -1i32)),
+        Serializer::new().serialize(Tuple(1, -1)).unwrap()
+    );
+    assert_eq!(
+        value!(Empty()),
+        Serializer::new().serialize(Empty()).unwrap()
+    );
+    assert_eq!(
+        value!(Point { x: 1i32, y: 2i32 }),
+        Serializer::new().serialize(Point { x: 1, y: 2 }).unwrap()
+    );
+    assert_eq!(
+        value!(Line {
+            from: Point { x: 1i32, y: 2i32 },
+            to: None,
+        }),
+        Serializer::new()
+            .serialize(Line {
+                from: Point { x: 1, y: 2 },
+                to: None,
+            })
+            .unwrap()
+    );
+}
+
+#[test]
+fn enums() {
+    #[derive(Serialize)]
+    enum Shape {
+        Empty,
+        Circle(f64),
+        Line(i32, i32),
+        Rect { w: u8, h: u8 },
+    }
+
+    assert_eq!(
+        value!(Shape::Empty @ 0),
+        Serializer::new().serialize(Shape::Empty).unwrap()
+    );
+    assert_eq!(
+        value!(Shape::Circle @ 1 (3.0f64)),
+        Serializer::new().serialize(Shape::Circle(3.0)).unwrap()
+    );
+    assert_eq!(
+        value!(Shape::Line @ 2 (1i32, 2i32)),
+        Serializer::new().serialize(Shape::Line(1, 2)).unwrap()
+    );
+    assert_eq!(
+        value!(Shape::Rect @ 3 { w: 1u8, h: 2u8 }),
+        Serializer::new()
+            .serialize(Shape::Rect { w: 1, h: 2 })
+            .unwrap()
+    );
+
+    // The index is taken as given.
+    assert_ne!(
+        value!(Shape::Circle @ 0 (3.0f64)),
+        Serializer::new().serialize(Shape::Circle(3.0)).unwrap()
+    );
+    // Constants have to be wrapped in parentheses.
+    assert_eq!(
+        value!((u8::MAX)),
+        Serializer::new().serialize(u8::MAX).unwrap()
+    );
+}
+
+#[test]
+fn roundtrip() {
+    #[derive(Debug, serde::Deserialize, PartialEq)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    let value = value!(Point { x: 1i32, y: 2i32 });
+    let point: Point = serde_content::Deserializer::new(value)
+        .deserialize()
+        .unwrap();
+    assert_eq!(point, Point { x: 1, y: 2 });
+}
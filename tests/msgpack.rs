@@ -0,0 +1,85 @@
+#![cfg(all(feature = "derive", feature = "msgpack"))]
+
+extern crate alloc;
+
+use alloc::borrow::Cow;
+use rmpv::Value as MsgPack;
+use serde_content::value;
+use serde_content::Data;
+use serde_content::Struct;
+use serde_content::Value;
+
+#[test]
+fn native() {
+    let value = value!({
+        (&b"key"[..]): (&b"\x00\xff"[..]),
+        [1u8, 2u8]: (),
+        (-1i128): (u128::from(u64::MAX)),
+        "float": 1.5f32,
+    });
+    let msgpack = MsgPack::try_from(value).unwrap();
+    assert_eq!(
+        msgpack,
+        MsgPack::Map(vec![
+            (
+                MsgPack::Binary(b"key".to_vec()),
+                MsgPack::Binary(vec![0, 255])
+            ),
+            (MsgPack::Array(vec![1.into(), 2.into()]), MsgPack::Nil),
+            (MsgPack::from(-1), MsgPack::from(u64::MAX)),
+            (MsgPack::from("float"), MsgPack::F32(1.5)),
+        ])
+    );
+    assert_eq!(
+        Value::from(msgpack),
+        value!({
+            (&b"key"[..]): (&b"\x00\xff"[..]),
+            [1u64, 2u64]: (),
+            (-1i64): (u64::MAX),
+            "float": 1.5f32,
+        })
+    );
+
+    let error = MsgPack::try_from(value!((i128::MIN))).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "-170141183460469231731687303715884105728i128 can't be represented in MessagePack"
+    );
+    let error = MsgPack::try_from(value!((u128::from(u64::MAX) + 1))).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "18446744073709551616u128 can't be represented in MessagePack"
+    );
+    assert_eq!(
+        MsgPack::try_from(value!((i128::from(i64::MIN)))).unwrap(),
+        MsgPack::from(i64::MIN)
+    );
+}
+
+#[test]
+fn extensions() {
+    let msgpack = MsgPack::Ext(-1, vec![0, 0, 0, 1]);
+    let value = Value::from(msgpack.clone());
+    assert_eq!(
+        value,
+        Value::Struct(Box::new(Struct {
+            name: Cow::Borrowed("_ExtStruct"),
+            data: Data::NewType {
+                value: value!((-1i8, (&b"\x00\x00\x00\x01"[..]))),
+            },
+        }))
+    );
+    assert_eq!(MsgPack::try_from(value).unwrap(), msgpack);
+
+    // Anything else named like an extension is an ordinary struct.
+    let value = Value::Struct(Box::new(Struct {
+        name: Cow::Borrowed("_ExtStruct"),
+        data: Data::NewType {
+            value: value!("not an extension"),
+        },
+    }));
+    assert_eq!(
+        MsgPack::try_from(value).unwrap(),
+        MsgPack::from("not an extension")
+    );
+}
@@ -0,0 +1,48 @@
+#![cfg(all(feature = "derive", feature = "proptest"))]
+
+extern crate alloc;
+
+use proptest::prelude::*;
+use serde_content::Data;
+use serde_content::Deserializer;
+use serde_content::Roundtrip;
+use serde_content::Serializer;
+use serde_content::Value;
+
+fn depth(value: &Value) -> usize {
+    let data = |data: &Data| match data {
+        Data::Unit => 0,
+        Data::NewType { value } => depth(value),
+        Data::Tuple { values } => values.iter().map(depth).max().unwrap_or(0),
+        Data::Struct { fields } => fields.iter().map(|(_, v)| depth(v)).max().unwrap_or(0),
+    };
+    1 + match value {
+        Value::Seq(v) | Value::Tuple(v) => v.iter().map(depth).max().unwrap_or(0),
+        Value::Map(v) => v
+            .iter()
+            .map(|(key, value)| depth(key).max(depth(value)))
+            .max()
+            .unwrap_or(0),
+        Value::Option(Some(v)) => depth(v),
+        Value::Struct(v) => data(&v.data),
+        Value::Enum(v) => data(&v.data),
+        _ => 0,
+    }
+}
+
+proptest! {
+    #[test]
+    fn values(value in any::<Value>()) {
+        prop_assert!(depth(&value) <= 4);
+        // Names are `'static`, so the value can be serialized.
+        prop_assert!(Serializer::new().serialize(&value).is_ok());
+    }
+
+    #[test]
+    fn roundtrip(Roundtrip(value) in any::<Roundtrip>()) {
+        prop_assert!(depth(&value) <= 4);
+        prop_assert_eq!(&Serializer::new().serialize(&value).unwrap(), &value);
+        let found: Value = Deserializer::new(value.clone()).deserialize().unwrap();
+        prop_assert_eq!(found, value);
+    }
+}
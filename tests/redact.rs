@@ -0,0 +1,247 @@
+#![cfg(feature = "derive")]
+
+extern crate alloc;
+
+use alloc::borrow::Cow;
+use alloc::collections::BTreeMap;
+use serde::ser;
+use serde::Serialize;
+use serde_content::HashKey;
+use serde_content::Redactor;
+use serde_content::Replacement;
+use serde_content::Serializer;
+use serde_content::Value;
+
+#[derive(Serialize)]
+struct User {
+    name: &'static str,
+    password: &'static str,
+}
+
+#[derive(Serialize)]
+struct Request {
+    user: User,
+    headers: BTreeMap<&'static str, &'static str>,
+    tokens: Vec<&'static str>,
+}
+
+fn request() -> Request {
+    let mut headers = BTreeMap::new();
+    headers.insert("accept", "*/*");
+    headers.insert("x-api-key", "hunter2");
+    Request {
+        user: User {
+            name: "alice",
+            password: "s3cret",
+        },
+        headers,
+        tokens: vec!["abc", "def"],
+    }
+}
+
+// Checks that redacting while serializing gives the same result as redacting afterwards.
+fn redact(redactor: &Redactor) -> Value<'static> {
+    let request = request();
+    let mut value = Serializer::new().serialize(&request).unwrap().into_owned();
+    let serialized = Serializer::new()
+        .redact(redactor)
+        .serialize(&request)
+        .unwrap()
+        .into_owned();
+    redactor.redact(&mut value).unwrap();
+    assert_eq!(value, serialized);
+    value
+}
+
+fn marker() -> Value<'static> {
+    Value::String(Cow::Borrowed("***"))
+}
+
+#[test]
+fn field() {
+    let redactor = Redactor::new().field("User", "password", Replacement::Marker);
+    let value = redact(&redactor);
+    let expected = Serializer::new()
+        .serialize(Request {
+            user: User {
+                name: "alice",
+                password: "***",
+            },
+            ..request()
+        })
+        .unwrap();
+    assert_eq!(value, expected);
+
+    // Fields of other structs are left alone.
+    let redactor = Redactor::new().field("Admin", "password", Replacement::Marker);
+    assert_eq!(
+        redact(&redactor),
+        Serializer::new().serialize(request()).unwrap()
+    );
+}
+
+#[test]
+fn key() {
+    let redactor = Redactor::new().key("x-*-key", Replacement::Mask);
+    let value = redact(&redactor);
+    let mut expected = request();
+    expected.headers.insert("x-api-key", "*******");
+    assert_eq!(value, Serializer::new().serialize(expected).unwrap());
+}
+
+#[test]
+fn path() {
+    let redactor = Redactor::new().path("tokens[*]", Replacement::Marker);
+    let value = redact(&redactor);
+    let mut expected = request();
+    expected.tokens = vec!["***", "***"];
+    assert_eq!(value, Serializer::new().serialize(expected).unwrap());
+
+    let redactor = Redactor::new().path("**.password", Replacement::Marker);
+    let value = redact(&redactor);
+    let mut expected = request();
+    expected.user.password = "***";
+    assert_eq!(value, Serializer::new().serialize(expected).unwrap());
+
+    // Whole subtrees can be redacted.
+    let redactor = Redactor::new().path("user", Replacement::Marker);
+    let Value::Struct(value) = redact(&redactor) else {
+        panic!("expected a struct");
+    };
+    let serde_content::Data::Struct { fields } = value.data else {
+        panic!("expected an object-like struct");
+    };
+    assert_eq!(fields[0], (Cow::Borrowed("user"), marker()));
+}
+
+#[test]
+fn too_many_path_segments() {
+    // 42 patterns of 2 segments, plus one accepting segment each, fill 126 of the 128 segments.
+    let mut redactor = Redactor::new();
+    for _ in 0..42 {
+        redactor = redactor.path("a.b", Replacement::Marker);
+    }
+    let too_many = redactor.clone().path("a.b", Replacement::Marker);
+    let message = "a redactor supports at most 128 path pattern segments";
+    let mut value = Serializer::new().serialize(request()).unwrap();
+    let original = value.clone();
+    let error = too_many.redact(&mut value).unwrap_err();
+    assert_eq!(error.to_string(), message);
+    assert_eq!(value, original);
+    let error = Serializer::new()
+        .redact(&too_many)
+        .serialize(request())
+        .unwrap_err();
+    assert_eq!(error.to_string(), message);
+
+    let redactor = redactor.path("a", Replacement::Marker);
+    assert_eq!(redactor.policies().len(), 43);
+    redact(&redactor);
+}
+
+#[test]
+fn enum_variant() {
+    #[derive(Serialize)]
+    enum Credentials {
+        Basic {
+            user: &'static str,
+            password: &'static str,
+        },
+    }
+
+    let credentials = Credentials::Basic {
+        user: "alice",
+        password: "s3cret",
+    };
+    let redactor = Redactor::new().path("Basic.password", Replacement::Mask);
+    let value = Serializer::new()
+        .redact(&redactor)
+        .serialize(&credentials)
+        .unwrap();
+    let expected = Serializer::new()
+        .serialize(Credentials::Basic {
+            user: "alice",
+            password: "******",
+        })
+        .unwrap();
+    assert_eq!(value, expected);
+}
+
+#[test]
+fn hash() {
+    let redactor = Redactor::new().field("User", "*", Replacement::Hash);
+    let first = redact(&redactor);
+    let second = redact(&redactor);
+    assert_eq!(first, second);
+
+    let a = Replacement::Hash.apply(Value::from("s3cret"));
+    let b = Replacement::Hash.apply(Value::from("hunter2"));
+    assert_ne!(a, b);
+    assert_eq!(a, Replacement::Hash.apply(Value::from("s3cret")));
+}
+
+#[test]
+fn keyed_hash() {
+    let key = Replacement::KeyedHash(HashKey::new([7; 16]));
+    let redactor = Redactor::new().field("User", "*", key);
+    assert_eq!(redact(&redactor), redact(&redactor));
+
+    let a = key.apply(Value::from("s3cret"));
+    assert_eq!(a, key.apply(Value::from("s3cret")));
+    assert_ne!(a, key.apply(Value::from("hunter2")));
+    // Other keys and the unkeyed hash give other hashes.
+    let other = Replacement::KeyedHash(HashKey::new([8; 16]));
+    assert_ne!(a, other.apply(Value::from("s3cret")));
+    assert_ne!(a, Replacement::Hash.apply(Value::from("s3cret")));
+    // The key doesn't leak through debug formatting.
+    assert_eq!(format!("{key:?}"), "KeyedHash(HashKey(..))");
+}
+
+#[test]
+fn values_outlive_redactor() {
+    let input = Value::Map(vec![
+        (Value::from("name"), Value::from("alice")),
+        (Value::from("password"), Value::from("s3cret")),
+    ]);
+    let value = {
+        let redactor = Redactor::new().key("password", Replacement::Marker);
+        Serializer::new()
+            .redact(&redactor)
+            .serialize_borrowed(&input)
+            .unwrap()
+    };
+    let expected = Value::Map(vec![
+        (Value::from("name"), Value::from("alice")),
+        (Value::from("password"), marker()),
+    ]);
+    assert_eq!(value, expected);
+}
+
+#[test]
+fn marker_skips_serialization() {
+    struct Secret;
+
+    impl Serialize for Secret {
+        fn serialize<S>(&self, _serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: ser::Serializer,
+        {
+            Err(ser::Error::custom("secret was serialized"))
+        }
+    }
+
+    #[derive(Serialize)]
+    struct Config<T> {
+        secret: T,
+    }
+
+    let redactor = Redactor::new().field("Config", "secret", Replacement::Marker);
+    let value = Serializer::new()
+        .redact(&redactor)
+        .serialize(Config { secret: Secret })
+        .unwrap();
+    let expected = Serializer::new()
+        .serialize(Config { secret: "***" })
+        .unwrap();
+    assert_eq!(value, expected);
+}
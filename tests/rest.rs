@@ -0,0 +1,190 @@
+#![cfg(feature = "derive")]
+
+extern crate alloc;
+
+use alloc::borrow::Cow;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_content::value;
+use serde_content::Deserializer;
+use serde_content::Number;
+use serde_content::Rest;
+use serde_content::Serializer;
+use serde_content::Value;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+struct Message<'a> {
+    id: u32,
+    #[serde(borrow, flatten)]
+    rest: Rest<'a>,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+struct MessageV2 {
+    id: u32,
+    priority: Option<u8>,
+    tags: Vec<String>,
+    origin: Origin,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+struct Origin {
+    host: String,
+    port: u16,
+}
+
+#[test]
+fn roundtrip() {
+    let value = value!({
+        "id": 1u32,
+        "lang": "en",
+        "priority": (Some(2u8)),
+        "tags": ["a", "b"],
+        "origin": { "host": "localhost", "port": 80u16 },
+    });
+    let message: Message = Deserializer::new(value.clone()).deserialize().unwrap();
+    assert_eq!(message.id, 1);
+    assert_eq!(message.rest.len(), 4);
+    assert_eq!(
+        message.rest.get("priority"),
+        Some(&Value::Option(Some(Box::new(Value::Number(Number::U8(2))))))
+    );
+    assert!(matches!(
+        message.rest.get("lang"),
+        Some(Value::String(Cow::Borrowed("en")))
+    ));
+    assert_eq!(Serializer::new().serialize(&message).unwrap(), value);
+}
+
+#[test]
+fn newer_versions() {
+    let v2 = MessageV2 {
+        id: 1,
+        priority: None,
+        tags: vec!["a".into()],
+        origin: Origin {
+            host: "localhost".into(),
+            port: 80,
+        },
+    };
+    let value = Serializer::new().serialize(&v2).unwrap();
+    let message: Message = Deserializer::new(value)
+        .coerce_shapes()
+        .deserialize()
+        .unwrap();
+    assert_eq!(
+        message.rest,
+        Rest(vec![
+            (Value::from("priority"), Value::Option(None)),
+            (Value::from("tags"), value!(["a"])),
+            (
+                Value::from("origin"),
+                value!({ "host": "localhost", "port": 80u16 })
+            ),
+        ])
+    );
+}
+
+#[test]
+fn unflattened() {
+    let rest: Rest = Deserializer::new(value!({ "a": 1u8, 2u8: 'b' }))
+        .deserialize()
+        .unwrap();
+    assert_eq!(rest.get("a"), Some(&Value::Number(Number::U8(1))));
+    assert_eq!(rest.get("b"), None);
+    assert_eq!(
+        Value::from(rest.into_owned()),
+        value!({ "a": 1u8, 2u8: 'b' })
+    );
+    assert!(Rest::new().is_empty());
+}
+
+#[derive(Debug, Serialize)]
+enum Kind {
+    Plain,
+    Tagged { tag: u8 },
+}
+
+#[derive(Debug, Serialize)]
+struct Lossy {
+    id: u32,
+    origin: Origin,
+    point: (u8, u8),
+}
+
+#[derive(Debug, Serialize)]
+struct WithEnum {
+    id: u32,
+    kind: Kind,
+    kinds: Vec<Kind>,
+    big: u128,
+}
+
+#[test]
+fn lossy() {
+    let lossy = Lossy {
+        id: 1,
+        origin: Origin {
+            host: "localhost".into(),
+            port: 80,
+        },
+        point: (1, 2),
+    };
+    let value = Serializer::new().serialize(&lossy).unwrap();
+    let message: Message = Deserializer::new(value.clone())
+        .coerce_shapes()
+        .deserialize()
+        .unwrap();
+    assert_eq!(
+        message.rest.get("origin"),
+        Some(&value!({ "host": "localhost", "port": 80u16 }))
+    );
+    assert_eq!(message.rest.get("point"), Some(&value!([1u8, 2u8])));
+    assert_ne!(Serializer::new().serialize(&message).unwrap(), value);
+}
+
+#[test]
+fn enums_and_128_bit_numbers() {
+    let with_enum = WithEnum {
+        id: 1,
+        kind: Kind::Tagged { tag: 2 },
+        kinds: vec![Kind::Plain],
+        big: u128::MAX,
+    };
+    let kind = Serializer::new().serialize(&with_enum.kind).unwrap();
+    let kinds = Serializer::new().serialize(&with_enum.kinds).unwrap();
+    let value = value!({ "id": 1u32, "kind": (kind), "kinds": (kinds), "big": (u128::MAX) });
+    let message: Message = Deserializer::new(value.clone()).deserialize().unwrap();
+    let Value::Map(mut fields) = value else {
+        unreachable!()
+    };
+    fields.remove(0);
+    assert_eq!(message.rest, Rest(fields));
+
+    // Enum names are no longer static strings, so enums serialize as maps.
+    assert_eq!(
+        Serializer::new().serialize(&message).unwrap(),
+        value!({
+            "id": 1u32,
+            "kind": { "Tagged": { "tag": 2u8 } },
+            "kinds": ["Plain"],
+            "big": (u128::MAX),
+        })
+    );
+
+    let value = Serializer::new().serialize(&with_enum).unwrap();
+    let message: Message = Deserializer::new(value)
+        .coerce_shapes()
+        .deserialize()
+        .unwrap();
+    assert_eq!(message.rest.len(), 3);
+
+    // Other maps of values get the enums back too.
+    let value = value!({ "kind": (Serializer::new().serialize(Kind::Plain).unwrap()) });
+    let map: std::collections::BTreeMap<String, Value> =
+        Deserializer::new(value.clone()).deserialize().unwrap();
+    assert_eq!(
+        Value::Map(vec![("kind".into(), map["kind"].clone())]),
+        value
+    );
+}
@@ -0,0 +1,80 @@
+#![cfg(all(feature = "derive", feature = "ron"))]
+
+extern crate alloc;
+
+use serde::Serialize;
+use serde_content::value;
+use serde_content::Serializer;
+use serde_content::Value;
+
+#[derive(Debug, Serialize)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[derive(Debug, Serialize)]
+enum Shape {
+    Empty,
+    Circle(f32),
+    Rect { origin: Point },
+}
+
+#[test]
+fn roundtrip() {
+    let value = value!({
+        "unit": (),
+        "bool": true,
+        "char": 'c',
+        "wide": (u128::MAX),
+        "float": 1.5f32,
+        "bytes": (&b"hi"[..]),
+        "option": (Some(-1i8)),
+        "none": (None::<u8>),
+        "map": { 1u8: [2u16] },
+    });
+    let ron = ron::Value::from(value.clone());
+    assert_eq!(
+        ron::to_string(&ron).unwrap(),
+        r#"{"unit":(),"bool":true,"char":'c',"wide":340282366920938463463374607431768211455,"float":1.5,"bytes":b"hi","option":Some(-1),"none":None,"map":{1:[2]}}"#
+    );
+    assert_eq!(Value::from(ron), value);
+}
+
+#[test]
+fn names() {
+    let shapes = [
+        Shape::Empty,
+        Shape::Circle(1.0),
+        Shape::Rect {
+            origin: Point { x: 1, y: 2 },
+        },
+    ];
+    let value = Serializer::new().serialize(&shapes).unwrap();
+
+    // `ron::Value` has no names, so they are dropped and enums are tagged by their variant.
+    let ron = ron::Value::from(value.clone());
+    assert_eq!(
+        ron::to_string(&ron).unwrap(),
+        r#"["Empty",{"Circle":1.0},{"Rect":{"origin":{"x":1,"y":2}}}]"#
+    );
+
+    // Serializing the value itself keeps them.
+    let config = ron::ser::PrettyConfig::new()
+        .struct_names(true)
+        .compact_arrays(true);
+    let text = ron::ser::to_string_pretty(&value, config).unwrap();
+    assert!(text.contains("Rect(\n"), "{text}");
+    assert!(text.contains("origin: Point(\n"), "{text}");
+    assert_eq!(
+        ron::to_string(&value).unwrap(),
+        "(Empty,Circle(1.0),Rect(origin:(x:1,y:2)))"
+    );
+
+    // Parsing RON text into a `ron::Value` drops them too, so converting back can't restore them.
+    let ron: ron::Value = ron::from_str("(Empty,Circle(1.0),Rect(origin:Point(x:1,y:2)))").unwrap();
+    assert_eq!(
+        Value::from(ron),
+        value!([(), [1.0f32], { "origin": { "x": 1u8, "y": 2u8 } }])
+    );
+}
@@ -0,0 +1,75 @@
+#![cfg(feature = "derive")]
+
+extern crate alloc;
+
+use alloc::collections::BTreeMap;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_content::value;
+use serde_content::Deserializer;
+use serde_content::Error;
+use serde_content::Serializer;
+use serde_content::Value;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+struct Pair(i32, i32);
+
+fn coerce<'de, T>(value: Value<'de>) -> Result<T, Error>
+where
+    T: Deserialize<'de>,
+{
+    Deserializer::new(value).coerce_shapes().deserialize()
+}
+
+#[test]
+fn map_from_struct() {
+    let value = Serializer::new().serialize(Point { x: 1, y: 2 }).unwrap();
+    assert!(Deserializer::new(value.clone())
+        .deserialize::<BTreeMap<String, i32>>()
+        .is_err());
+    let map = coerce::<BTreeMap<String, i32>>(value).unwrap();
+    assert_eq!(map, BTreeMap::from([("x".into(), 1), ("y".into(), 2)]));
+    assert!(coerce::<BTreeMap<String, i32>>(value!(Pair(1i32, 2i32))).is_err());
+}
+
+#[test]
+fn seq_from_tuples() {
+    assert!(Deserializer::new(value!((1i32, 2i32)))
+        .deserialize::<Vec<i32>>()
+        .is_err());
+    assert_eq!(coerce::<Vec<i32>>(value!((1i32, 2i32))), Ok(vec![1, 2]));
+    assert_eq!(coerce::<Vec<i32>>(value!(Pair(1i32, 2i32))), Ok(vec![1, 2]));
+    assert!(coerce::<Vec<i32>>(value!(Point { x: 1i32, y: 2i32 })).is_err());
+}
+
+#[test]
+fn tuples_from_seqs() {
+    assert_eq!(coerce::<(i32, i32)>(value!(Pair(1i32, 2i32))), Ok((1, 2)));
+    assert_eq!(coerce::<Pair>(value!((1i32, 2i32))), Ok(Pair(1, 2)));
+    assert!(Deserializer::new(value!((1i32, 2i32)))
+        .deserialize::<Pair>()
+        .is_err());
+    let pair = Deserializer::new(value!([1i32, 2i32]))
+        .strict()
+        .coerce_shapes()
+        .deserialize::<Pair>();
+    assert_eq!(pair, Ok(Pair(1, 2)));
+}
+
+#[test]
+fn structs_from_seqs() {
+    let expected = Ok(Point { x: 1, y: 2 });
+    assert_eq!(coerce::<Point>(value!([1i32, 2i32])), expected);
+    assert_eq!(coerce::<Point>(value!((1i32, 2i32))), expected);
+    assert_eq!(coerce::<Point>(value!(Point(1i32, 2i32))), expected);
+    assert!(Deserializer::new(value!([1i32, 2i32]))
+        .deserialize::<Point>()
+        .is_err());
+    assert!(coerce::<Point>(value!([1i32])).is_err());
+}
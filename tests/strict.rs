@@ -0,0 +1,208 @@
+#![cfg(feature = "derive")]
+
+extern crate alloc;
+
+use serde::Deserialize;
+use serde::Serialize;
+use serde_content::value;
+use serde_content::DataType;
+use serde_content::Deserializer;
+use serde_content::Error;
+use serde_content::Expected;
+use serde_content::Found;
+use serde_content::FoundData;
+use serde_content::Number;
+use serde_content::Serializer;
+use serde_content::Value;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+struct Unit;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+struct NewType(u8);
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+struct Tuple(u8, u8);
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+struct Point {
+    x: u8,
+    y: u8,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+enum Shape {
+    Empty,
+    Line(u8, u8),
+    Rect { w: u8, h: u8 },
+}
+
+fn strict<'de, T>(value: Value<'de>) -> Result<T, Error>
+where
+    T: Deserialize<'de>,
+{
+    Deserializer::new(value).strict().deserialize()
+}
+
+#[test]
+fn roundtrip() {
+    let serializer = Serializer::new();
+    let point = Point { x: 1, y: 2 };
+    let value = serializer.serialize(&point).unwrap();
+    assert_eq!(strict::<Point>(value), Ok(point));
+    let value = serializer.serialize(Unit).unwrap();
+    assert_eq!(strict::<Unit>(value), Ok(Unit));
+    let value = serializer.serialize(NewType(1)).unwrap();
+    assert_eq!(strict::<NewType>(value), Ok(NewType(1)));
+    let value = serializer.serialize(Tuple(1, 2)).unwrap();
+    assert_eq!(strict::<Tuple>(value), Ok(Tuple(1, 2)));
+    let value = serializer.serialize((1u8, 2u8)).unwrap();
+    assert_eq!(strict::<(u8, u8)>(value), Ok((1, 2)));
+    let value = serializer.serialize(Some(1u8)).unwrap();
+    assert_eq!(strict::<Option<u8>>(value), Ok(Some(1)));
+    for shape in [Shape::Empty, Shape::Line(1, 2), Shape::Rect { w: 1, h: 2 }] {
+        let value = serializer.serialize(&shape).unwrap();
+        assert_eq!(strict::<Shape>(value), Ok(shape));
+    }
+}
+
+#[test]
+fn lenient_shapes() {
+    let map = value!({ "x": 1u8, "y": 2u8 });
+    assert!(Deserializer::new(map.clone())
+        .deserialize::<Point>()
+        .is_ok());
+    assert_eq!(
+        strict::<Point>(map.clone()),
+        Err(Error::unexpected(
+            Found::Map(vec![
+                (Found::String("x".into()), Found::Number(Number::U8(1))),
+                (Found::String("y".into()), Found::Number(Number::U8(2))),
+            ]),
+            Expected::Struct {
+                name: Some("Point".into()),
+                typ: Some(DataType::Struct),
+            }
+        ))
+    );
+
+    let seq = value!([1u8, 2u8]);
+    assert!(Deserializer::new(seq.clone())
+        .deserialize::<(u8, u8)>()
+        .is_ok());
+    assert!(strict::<(u8, u8)>(seq.clone()).is_err());
+    assert!(Deserializer::new(seq.clone())
+        .deserialize::<Tuple>()
+        .is_ok());
+    assert!(strict::<Tuple>(seq).is_err());
+
+    assert!(Deserializer::new(Value::Unit).deserialize::<Unit>().is_ok());
+    assert_eq!(
+        strict::<Unit>(Value::Unit),
+        Err(Error::unexpected(
+            Found::Unit,
+            Expected::Struct {
+                name: Some("Unit".into()),
+                typ: Some(DataType::Unit),
+            }
+        ))
+    );
+
+    assert!(Deserializer::new(value!(1u8))
+        .deserialize::<NewType>()
+        .is_ok());
+    assert!(strict::<NewType>(value!(1u8)).is_err());
+
+    assert!(Deserializer::new(value!(1u8))
+        .deserialize::<Option<u8>>()
+        .is_ok());
+    assert_eq!(
+        strict::<Option<u8>>(value!(1u8)),
+        Err(Error::unexpected(
+            Found::Number(Number::U8(1)),
+            Expected::Option
+        ))
+    );
+
+    assert!(Deserializer::new(value!("Empty"))
+        .deserialize::<Shape>()
+        .is_ok());
+    assert!(strict::<Shape>(value!("Empty")).is_err());
+    let map = value!({ "Line": [1u8, 2u8] });
+    assert!(Deserializer::new(map.clone())
+        .deserialize::<Shape>()
+        .is_ok());
+    assert!(strict::<Shape>(map).is_err());
+}
+
+#[test]
+fn names() {
+    let value = value!(Other { x: 1u8, y: 2u8 });
+    assert!(Deserializer::new(value.clone())
+        .deserialize::<Point>()
+        .is_ok());
+    assert_eq!(
+        strict::<Point>(value),
+        Err(Error::unexpected(
+            Found::Struct {
+                name: "Other".into(),
+                data: Box::new(FoundData::Struct(vec![
+                    ("x".into(), Found::Number(Number::U8(1))),
+                    ("y".into(), Found::Number(Number::U8(2))),
+                ])),
+            },
+            Expected::Struct {
+                name: Some("Point".into()),
+                typ: Some(DataType::Struct),
+            }
+        ))
+    );
+
+    assert!(strict::<Unit>(value!(Other)).is_err());
+    assert!(strict::<NewType>(value!(Other(1u8))).is_err());
+    assert!(strict::<Tuple>(value!(Other(1u8, 2u8))).is_err());
+
+    let value = value!(Other::Empty @ 0);
+    assert!(Deserializer::new(value.clone())
+        .deserialize::<Shape>()
+        .is_ok());
+    assert!(strict::<Shape>(value).is_err());
+}
+
+#[test]
+fn tuple_lengths() {
+    let value = value!((1u8, 2u8, 3u8));
+    assert_eq!(
+        strict::<(u8, u8)>(value),
+        Err(Error::unexpected(
+            Found::Tuple(vec![
+                Found::Number(Number::U8(1)),
+                Found::Number(Number::U8(2)),
+                Found::Number(Number::U8(3)),
+            ]),
+            Expected::Tuple(2)
+        ))
+    );
+    assert!(strict::<Tuple>(value!(Tuple(1u8, 2u8, 3u8))).is_err());
+    assert!(strict::<Shape>(value!(Shape::Line @ 1 (1u8, 2u8, 3u8))).is_err());
+}
+
+#[test]
+fn unknown_fields() {
+    let value = value!(Point {
+        x: 1u8,
+        y: 2u8,
+        z: 3u8
+    });
+    assert!(Deserializer::new(value.clone())
+        .deserialize::<Point>()
+        .is_ok());
+    let error = strict::<Point>(value).unwrap_err();
+    assert_eq!(error.to_string(), "unknown field `z`, expected `x` or `y`");
+
+    let value = value!(Shape::Rect @ 2 { w: 1u8, h: 2u8, d: 3u8 });
+    assert!(Deserializer::new(value.clone())
+        .deserialize::<Shape>()
+        .is_ok());
+    assert!(strict::<Shape>(value).is_err());
+}
@@ -0,0 +1,121 @@
+#![cfg(feature = "derive")]
+
+extern crate alloc;
+
+use serde::Deserialize;
+use serde_content::value;
+use serde_content::Deserializer;
+use serde_content::Error;
+use serde_content::Expected;
+use serde_content::Found;
+use serde_content::Number;
+use serde_content::Value;
+
+fn coerce<'de, T>(value: Value<'de>) -> Result<T, Error>
+where
+    T: Deserialize<'de>,
+{
+    Deserializer::new(value).coerce_strings().deserialize()
+}
+
+#[test]
+fn scalars_from_strings() {
+    assert_eq!(coerce::<i32>(value!("-42")), Ok(-42));
+    assert_eq!(coerce::<u8>(value!("255")), Ok(255));
+    assert_eq!(coerce::<u128>(value!("1")), Ok(1));
+    assert_eq!(coerce::<f64>(value!("1.5")), Ok(1.5));
+    assert_eq!(coerce::<f32>(value!("-0.5")), Ok(-0.5));
+    assert_eq!(coerce::<bool>(value!("true")), Ok(true));
+    assert_eq!(coerce::<char>(value!("a")), Ok('a'));
+    assert_eq!(coerce::<()>(value!("")), Ok(()));
+
+    // Strings are not coerced by default.
+    assert_eq!(
+        Deserializer::new(value!("1")).deserialize::<u8>(),
+        Err(Error::unexpected(Found::String("1".into()), Expected::U8))
+    );
+}
+
+#[test]
+fn parse_errors() {
+    assert_eq!(
+        coerce::<u8>(value!("256")),
+        Err(Error::unexpected(Found::String("256".into()), Expected::U8))
+    );
+    assert_eq!(
+        coerce::<i32>(value!("1.5")),
+        Err(Error::unexpected(
+            Found::String("1.5".into()),
+            Expected::I32
+        ))
+    );
+    assert_eq!(
+        coerce::<bool>(value!("yes")),
+        Err(Error::unexpected(
+            Found::String("yes".into()),
+            Expected::Bool
+        ))
+    );
+    assert_eq!(
+        coerce::<char>(value!("ab")),
+        Err(Error::unexpected(
+            Found::String("ab".into()),
+            Expected::Char
+        ))
+    );
+    assert_eq!(
+        coerce::<()>(value!("()")),
+        Err(Error::unexpected(
+            Found::String("()".into()),
+            Expected::Unit
+        ))
+    );
+}
+
+#[test]
+fn strings_from_scalars() {
+    assert_eq!(coerce::<String>(value!(1u8)), Ok("1".into()));
+    assert_eq!(coerce::<String>(value!(-1.5f64)), Ok("-1.5".into()));
+    assert_eq!(coerce::<String>(value!(false)), Ok("false".into()));
+    assert_eq!(coerce::<String>(value!('c')), Ok("c".into()));
+    assert_eq!(
+        Deserializer::new(value!(1u8)).deserialize::<String>(),
+        Err(Error::unexpected(
+            Found::Number(Number::U8(1)),
+            Expected::String
+        ))
+    );
+}
+
+#[test]
+fn config() {
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Config {
+        port: u16,
+        debug: bool,
+        ratio: f64,
+        name: String,
+        retries: Option<u8>,
+    }
+
+    // Built from something like `std::env::vars()`.
+    let env = [
+        ("port", "8080"),
+        ("debug", "false"),
+        ("ratio", "0.25"),
+        ("name", "server"),
+        ("retries", "3"),
+    ];
+    let value: Value = env.into_iter().collect();
+    let config: Config = coerce(value).unwrap();
+    assert_eq!(
+        config,
+        Config {
+            port: 8080,
+            debug: false,
+            ratio: 0.25,
+            name: "server".into(),
+            retries: Some(3),
+        }
+    );
+}
@@ -0,0 +1,240 @@
+#![cfg(feature = "derive")]
+
+extern crate alloc;
+
+use serde::Deserialize;
+use serde::Serialize;
+use serde_content::token;
+use serde_content::token::Token;
+use serde_content::value;
+use serde_content::Serializer;
+use serde_content::Value;
+use std::collections::BTreeMap;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Point {
+    x: i128,
+    y: Option<u8>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+enum Shape {
+    Empty,
+    Circle(f64),
+    Line(Point, Point),
+    Rect { corner: Point, size: (u16, u16) },
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Meters(u32);
+
+#[test]
+fn serialize() {
+    let point = Point { x: -1, y: Some(2) };
+    let tokens = token::Serializer::new().serialize(&point).unwrap();
+    assert_eq!(
+        tokens,
+        [
+            Token::Struct {
+                name: "Point".into(),
+                len: 2
+            },
+            Token::Field("x".into()),
+            Token::I128(-1),
+            Token::Field("y".into()),
+            Token::Some,
+            Token::U8(2),
+            Token::StructEnd,
+        ]
+    );
+
+    let tokens = token::Serializer::new()
+        .serialize(Shape::Circle(1.5))
+        .unwrap();
+    assert_eq!(
+        tokens,
+        [
+            Token::NewtypeVariant {
+                name: "Shape".into(),
+                variant_index: 1,
+                variant: "Circle".into()
+            },
+            Token::F64(1.5),
+        ]
+    );
+
+    let map = BTreeMap::from([("a", vec![Meters(1)])]);
+    let tokens = token::Serializer::new().serialize(&map).unwrap();
+    assert_eq!(
+        tokens,
+        [
+            Token::Map { len: Some(1) },
+            Token::Str("a".into()),
+            Token::Seq { len: Some(1) },
+            Token::NewtypeStruct {
+                name: "Meters".into()
+            },
+            Token::U32(1),
+            Token::SeqEnd,
+            Token::MapEnd,
+        ]
+    );
+}
+
+#[test]
+fn value_tokens() {
+    let shapes = [
+        Shape::Empty,
+        Shape::Circle(1.0),
+        Shape::Line(Point { x: 0, y: None }, Point { x: 1, y: Some(1) }),
+        Shape::Rect {
+            corner: Point { x: 2, y: None },
+            size: (3, 4),
+        },
+    ];
+    for shape in &shapes {
+        let value = Serializer::new().serialize(shape).unwrap();
+        let tokens = value.to_tokens();
+        // A value has the same tokens as the data it was serialized from.
+        assert_eq!(tokens, token::Serializer::new().serialize(shape).unwrap());
+        assert_eq!(Value::from_tokens(&tokens).unwrap(), value);
+    }
+
+    let value = value!({ "bytes": (&b"\x00"[..]), 'c': [(), ('t', 1u128)] });
+    assert_eq!(Value::from_tokens(&value.to_tokens()).unwrap(), value);
+}
+
+#[test]
+fn reserialize_from_tokens() {
+    let shape = Shape::Rect {
+        corner: Point { x: 2, y: None },
+        size: (3, 4),
+    };
+    let value = Serializer::new().serialize(&shape).unwrap();
+    let tokens = value.to_tokens();
+    // Field names stay static, so the value can be serialized again.
+    let copy = Value::from_tokens(&tokens).unwrap();
+    assert_eq!(Serializer::new().serialize(&copy).unwrap(), value);
+    assert_eq!(token::Serializer::new().serialize(&copy).unwrap(), tokens);
+}
+
+#[test]
+fn from_tokens_errors() {
+    let error = Value::from_tokens(&[Token::Seq { len: None }, Token::U8(1)]).unwrap_err();
+    assert_eq!(error.to_string(), "unexpected end of tokens");
+
+    let error = Value::from_tokens(&[Token::Unit, Token::Unit]).unwrap_err();
+    assert_eq!(error.to_string(), "trailing token Unit");
+
+    let error = Value::from_tokens(&[Token::SeqEnd]).unwrap_err();
+    assert_eq!(error.to_string(), "unexpected token SeqEnd");
+
+    let tokens = [
+        Token::Struct {
+            name: "Point".into(),
+            len: 1,
+        },
+        Token::U8(0),
+        Token::StructEnd,
+    ];
+    let error = Value::from_tokens(&tokens).unwrap_err();
+    assert_eq!(error.to_string(), "expected field name, found U8(0)");
+}
+
+#[test]
+fn deserialize() {
+    let shapes = [
+        Shape::Empty,
+        Shape::Circle(1.0),
+        Shape::Line(Point { x: 0, y: None }, Point { x: 1, y: Some(1) }),
+        Shape::Rect {
+            corner: Point { x: 2, y: None },
+            size: (3, 4),
+        },
+    ];
+    for shape in shapes {
+        let tokens = token::Serializer::new().serialize(&shape).unwrap();
+        let found: Shape = token::Deserializer::new(&tokens).deserialize().unwrap();
+        assert_eq!(found, shape);
+    }
+
+    // Unit variants can be named by a string, and values stand in for `Some`.
+    let tokens = [
+        Token::Seq { len: None },
+        Token::Str("Empty".into()),
+        Token::SeqEnd,
+    ];
+    let found: Vec<Shape> = token::Deserializer::new(&tokens).deserialize().unwrap();
+    assert_eq!(found, [Shape::Empty]);
+    let found: Option<Meters> = token::Deserializer::new(&[Token::U32(5)])
+        .deserialize()
+        .unwrap();
+    assert_eq!(found, Some(Meters(5)));
+}
+
+#[test]
+fn deserialize_borrowed() {
+    let text = String::from("borrowed");
+    let tokens = [
+        Token::Tuple { len: 2 },
+        Token::Str(text.as_str().into()),
+        Token::Bytes(text.as_bytes().into()),
+        Token::TupleEnd,
+    ];
+    let (string, bytes): (&str, &[u8]) = token::Deserializer::new(&tokens).deserialize().unwrap();
+    assert_eq!(string, "borrowed");
+    assert_eq!(bytes, b"borrowed");
+}
+
+#[test]
+fn deserialize_errors() {
+    let tokens = [Token::U8(1), Token::U8(2)];
+    let error = token::Deserializer::new(&tokens)
+        .deserialize::<u8>()
+        .unwrap_err();
+    assert_eq!(error.to_string(), "trailing token U8(2)");
+
+    // The tuple has more elements than the type.
+    let tokens = [
+        Token::Tuple { len: 2 },
+        Token::U8(1),
+        Token::U8(2),
+        Token::TupleEnd,
+    ];
+    let error = token::Deserializer::new(&tokens)
+        .deserialize::<(u8,)>()
+        .unwrap_err();
+    assert_eq!(error.to_string(), "expected TupleEnd, found U8(2)");
+
+    let tokens = [Token::UnitVariant {
+        name: "Shape".into(),
+        variant_index: 1,
+        variant: "Circle".into(),
+    }];
+    let error = token::Deserializer::new(&tokens)
+        .deserialize::<Shape>()
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "invalid type: unit variant, expected newtype variant"
+    );
+}
+
+#[test]
+fn human_readable() {
+    let tokens = token::Serializer::new()
+        .human_readable()
+        .serialize(std::net::Ipv4Addr::LOCALHOST)
+        .unwrap();
+    assert_eq!(tokens, [Token::Str("127.0.0.1".into())]);
+    let addr: std::net::Ipv4Addr = token::Deserializer::new(&tokens)
+        .human_readable()
+        .deserialize()
+        .unwrap();
+    assert_eq!(addr, std::net::Ipv4Addr::LOCALHOST);
+
+    let tokens = token::Serializer::new()
+        .serialize(std::net::Ipv4Addr::LOCALHOST)
+        .unwrap();
+    assert_eq!(tokens[0], Token::Tuple { len: 4 });
+}
@@ -0,0 +1,101 @@
+#![cfg(all(feature = "derive", feature = "toml"))]
+
+extern crate alloc;
+
+use serde::Deserialize;
+use serde::Serialize;
+use serde_content::value;
+use serde_content::Deserializer;
+use serde_content::Serializer;
+use serde_content::Value;
+use toml::value::Datetime;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Config {
+    name: String,
+    port: u16,
+    created: Datetime,
+    mode: Mode,
+    proxy: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+enum Mode {
+    Fast,
+    Limited { max: u8 },
+}
+
+const TEXT: &str = r#"
+name = "server"
+port = 8080
+created = 1979-05-27T07:32:00Z
+mode = { Limited = { max = 3 } }
+"#;
+
+// `toml::Value::try_from` is an inherent method that serializes, so convert with `try_into`.
+fn to_toml(value: Value) -> Result<toml::Value, serde_content::Error> {
+    value.try_into()
+}
+
+#[test]
+fn from_toml() {
+    let table: toml::Table = TEXT.parse().unwrap();
+    let value = Value::from(table);
+    let Value::Map(entries) = &value else {
+        panic!("expected a map, found {value:?}");
+    };
+    assert_eq!(entries[0], (value!("name"), value!("server")));
+    assert_eq!(entries[1].1, value!(8080i64));
+
+    let config: Config = Deserializer::new(value)
+        .coerce_numbers()
+        .deserialize()
+        .unwrap();
+    assert_eq!(config.created.to_string(), "1979-05-27T07:32:00Z");
+    assert_eq!(config.mode, Mode::Limited { max: 3 });
+    assert_eq!(config.proxy, None);
+}
+
+#[test]
+fn into_toml() {
+    let config = Config {
+        name: "server".into(),
+        port: 8080,
+        created: "1979-05-27T07:32:00Z".parse().unwrap(),
+        mode: Mode::Fast,
+        proxy: None,
+    };
+    let value = Serializer::new().serialize(&config).unwrap();
+    let toml = to_toml(value).unwrap();
+    assert_eq!(
+        toml,
+        toml::Value::Table(toml::toml! {
+            name = "server"
+            port = 8080
+            created = 1979-05-27T07:32:00Z
+            mode = "Fast"
+        })
+    );
+    assert_eq!(to_toml(Value::from(toml.clone())), Ok(toml));
+}
+
+#[test]
+fn unrepresentable() {
+    let error = to_toml(value!([1u8, ()])).unwrap_err();
+    assert_eq!(error.to_string(), "() can't be represented in TOML");
+    let error = to_toml(value!((u64::MAX))).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "18446744073709551615u64 can't be represented in TOML"
+    );
+    assert!(to_toml(value!({ 1u8: true })).is_err());
+}
+
+#[test]
+fn duplicate_keys() {
+    let error = to_toml(value!({ 'a': 1u8, "a": 2u8 })).unwrap_err();
+    assert_eq!(error.to_string(), "duplicate key `a` in TOML table");
+    // Null entries are left out, so they don't collide.
+    let toml = to_toml(value!({ "a": (), 'a': 1u8 })).unwrap();
+    assert_eq!(toml, toml::toml! { a = 1 }.into());
+}
@@ -0,0 +1,89 @@
+#![cfg(all(feature = "derive", feature = "yaml"))]
+
+extern crate alloc;
+
+use serde::Deserialize;
+use serde::Serialize;
+use serde_content::value;
+use serde_content::Deserializer;
+use serde_content::Serializer;
+use serde_content::Value;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+enum Shape {
+    Empty,
+    Circle(f64),
+    Pair(u8, u8),
+    Rect { width: u8, height: u8 },
+}
+
+#[test]
+fn from_yaml() {
+    let yaml: serde_yaml::Value = serde_yaml::from_str(
+        "
+- Empty
+- !Circle 1.5
+- !Pair [1, 2]
+- !Rect { width: 3, height: 4 }
+",
+    )
+    .unwrap();
+    let value = Value::from(yaml);
+    let Value::Seq(values) = &value else {
+        panic!("expected a sequence, found {value:?}");
+    };
+    let Value::Enum(circle) = &values[1] else {
+        panic!("expected an enum, found {:?}", values[1]);
+    };
+    assert_eq!(circle.variant, "Circle");
+
+    let shapes: Vec<Shape> = Deserializer::new(value)
+        .coerce_numbers()
+        .deserialize()
+        .unwrap();
+    assert_eq!(
+        shapes,
+        [
+            Shape::Empty,
+            Shape::Circle(1.5),
+            Shape::Pair(1, 2),
+            Shape::Rect {
+                width: 3,
+                height: 4
+            },
+        ]
+    );
+
+    let yaml: serde_yaml::Value = serde_yaml::from_str("{ 1: ~, [a]: -2 }").unwrap();
+    assert_eq!(Value::from(yaml), value!({ 1u64: (), ["a"]: -2i64 }));
+}
+
+#[test]
+fn to_yaml() {
+    let shapes = [
+        Shape::Empty,
+        Shape::Circle(1.5),
+        Shape::Pair(1, 2),
+        Shape::Rect {
+            width: 3,
+            height: 4,
+        },
+    ];
+    let value = Serializer::new().serialize(&shapes).unwrap();
+    let yaml = serde_yaml::Value::try_from(value).unwrap();
+    assert_eq!(yaml, serde_yaml::to_value(&shapes).unwrap());
+
+    let error = serde_yaml::Value::try_from(value!((u128::MAX))).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "340282366920938463463374607431768211455u128 can't be represented in YAML"
+    );
+}
+
+#[test]
+fn duplicate_keys() {
+    let error = serde_yaml::Value::try_from(value!({ 'a': 1u8, "a": 2u8 })).unwrap_err();
+    assert_eq!(error.to_string(), "duplicate key \"a\" in YAML mapping");
+    let error = serde_yaml::Value::try_from(value!({ 1u8: true, 1u64: false })).unwrap_err();
+    assert_eq!(error.to_string(), "duplicate key 1u64 in YAML mapping");
+}